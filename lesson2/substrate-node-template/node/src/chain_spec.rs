@@ -1,7 +1,7 @@
 use sp_core::{Pair, Public, sr25519};
 use node_template_runtime::{
 	AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig,
-	SudoConfig, SystemConfig, WASM_BINARY, Signature
+	KittiesConfig, SudoConfig, SystemConfig, WASM_BINARY, Signature
 };
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_finality_grandpa::AuthorityId as GrandpaId;
@@ -120,5 +120,12 @@ fn testnet_genesis(initial_authorities: Vec<(AuraId, GrandpaId)>,
 		sudo: Some(SudoConfig {
 			key: root_key,
 		}),
+		kitties: Some(KittiesConfig {
+			// One kitty per endowed account, with a readable seed so the
+			// resulting DNA can be re-derived when reviewing the spec.
+			kitties: endowed_accounts.iter().cloned().enumerate()
+				.map(|(i, k)| (k, format!("genesis-kitty-{}", i).into_bytes()))
+				.collect(),
+		}),
 	}
 }
@@ -32,6 +32,10 @@ macro_rules! new_full_start {
 
 		let mut import_setup = None;
 		let inherent_data_providers = sp_inherents::InherentDataProviders::new();
+		inherent_data_providers
+			.register_provider(kitties::GenZeroDropInherentDataProvider)
+			.map_err(Into::into)
+			.map_err(sc_service::Error::InherentDataProviders)?;
 
 		let builder = sc_service::ServiceBuilder::new_full::<
 			node_template_runtime::opaque::Block, node_template_runtime::RuntimeApi, crate::service::Executor
@@ -194,6 +198,10 @@ pub fn new_full(config: Configuration) -> Result<impl AbstractService, ServiceEr
 /// Builds a new service for a light client.
 pub fn new_light(config: Configuration) -> Result<impl AbstractService, ServiceError> {
 	let inherent_data_providers = InherentDataProviders::new();
+	inherent_data_providers
+		.register_provider(kitties::GenZeroDropInherentDataProvider)
+		.map_err(Into::into)
+		.map_err(ServiceError::InherentDataProviders)?;
 
 	ServiceBuilder::new_light::<Block, RuntimeApi, Executor>(config)?
 		.with_select_chain(|_config, backend| {
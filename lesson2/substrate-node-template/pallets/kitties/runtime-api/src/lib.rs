@@ -0,0 +1,60 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// Runtime API giving light clients and explorers access to the canonical
+/// kitty phenotype decoding, so the gene math is not duplicated client side.
+
+use codec::Codec;
+use pallet_kitties::{AggregateStats, KittyAttributes, KittyStats};
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait KittiesApi<KittyIndex, Balance, AccountId> where
+		KittyIndex: Codec,
+		Balance: Codec,
+		AccountId: Codec,
+	{
+		/// Return the decoded attributes of the given kitty, if it exists.
+		fn attributes(kitty_id: KittyIndex) -> Option<KittyAttributes>;
+
+		/// Return the kitty's stats with equipment bonuses applied, if it
+		/// exists.
+		fn effective_stats(kitty_id: KittyIndex) -> Option<KittyStats>;
+
+		/// Return the rarity leaderboard: the rarest kitties and their
+		/// scores, sorted descending.
+		fn rarity_leaderboard() -> Vec<(KittyIndex, u32)>;
+
+		/// Return a page of the kitties in `generation`: ascending ids
+		/// starting at `start`, at most `limit` entries.
+		fn generation_page(generation: u32, start: KittyIndex, limit: u32) -> Vec<KittyIndex>;
+
+		/// Return the current bonding-curve price of a gen-0 mint, so
+		/// wallets can quote it before submitting `create`.
+		fn mint_price() -> Balance;
+
+		/// Return every holder in the given ownership snapshot with their
+		/// frozen kitty count, for airdrop and reward programs.
+		fn snapshot(snapshot_id: u32) -> Vec<(AccountId, u32)>;
+
+		/// Return the unique-owner count and the coarse holder histogram
+		/// (accounts holding 1, 2-5, 6-20 and 21+ kitties).
+		fn holder_stats() -> (u32, [u32; 4]);
+
+		/// Return the aggregate chain statistics — supply, generation
+		/// counts, burns, trade volume and open listings — in one call.
+		fn stats() -> AggregateStats<Balance>;
+
+		/// Return the exits root and the number of leaves folded into it,
+		/// for successor chains tracking kitty migrations.
+		fn exits_root() -> ([u8; 32], u32);
+
+		/// Whether the kitty falls inside a council-verified official
+		/// drop, so marketplaces can warn about look-alikes.
+		fn is_verified(kitty_id: KittyIndex) -> bool;
+
+		/// Return the storage keys whose read proof verifies ownership of
+		/// the kitty; pair with the node's `state_getReadProof` RPC for a
+		/// compact Merkle proof light clients and bridges can check.
+		fn ownership_proof_keys(kitty_id: KittyIndex) -> Vec<Vec<u8>>;
+	}
+}
@@ -0,0 +1,170 @@
+//! A chain extension surface for `pallet-contracts`, so ink! contracts
+//! can build on the kitty primitives.
+//!
+//! The `ChainExtension` trait postdates this Substrate version, so this
+//! module holds the version-independent core: function ids, SCALE-encoded
+//! argument and result shapes, and a weight price per function. A
+//! contracts-enabled runtime wires it up with a thin shim that reads the
+//! contract's input buffer, charges [`KittiesExt::weight_of`] against the
+//! gas meter before doing any work, maps the calling contract's account
+//! id as the origin, and writes the returned bytes back to the contract.
+
+use crate::{Error, Module, RawEvent, Trait, TransferKind};
+use codec::{Decode, Encode};
+use frame_support::{
+	traits::{Get, ReservableCurrency},
+	weights::Weight,
+};
+use sp_runtime::DispatchError;
+use sp_std::prelude::*;
+
+/// `owner_of(kitty_id: KittyIndex) -> AccountId`
+pub const FUNC_OWNER_OF: u32 = 0x0001;
+/// `transfer(to: AccountId, kitty_id: KittyIndex)`
+pub const FUNC_TRANSFER: u32 = 0x0002;
+/// `breed(kitty_id_1: KittyIndex, kitty_id_2: KittyIndex) -> KittyIndex`
+pub const FUNC_BREED: u32 = 0x0003;
+/// `attributes(kitty_id: KittyIndex) -> KittyAttributes`
+pub const FUNC_ATTRIBUTES: u32 = 0x0004;
+
+/// The extension itself; the runtime shim delegates here.
+pub struct KittiesExt<T>(sp_std::marker::PhantomData<T>);
+
+impl<T: Trait> KittiesExt<T> {
+	/// The weight to charge for `func_id`, priced like the corresponding
+	/// extrinsics. Unknown ids price at zero; `call` rejects them before
+	/// touching state.
+	pub fn weight_of(func_id: u32) -> Weight {
+		match func_id {
+			FUNC_OWNER_OF => T::DbWeight::get().reads(1),
+			FUNC_TRANSFER => T::DbWeight::get().reads_writes(9, 8) + 10_000,
+			FUNC_BREED => T::DbWeight::get().reads_writes(18, 20) + 10_000,
+			FUNC_ATTRIBUTES => T::DbWeight::get().reads(1),
+			_ => 0,
+		}
+	}
+
+	/// Dispatch `func_id` on behalf of `origin` — the contract's account
+	/// id — decoding the arguments from `input` and returning the
+	/// SCALE-encoded result.
+	pub fn call(
+		func_id: u32,
+		origin: T::AccountId,
+		mut input: &[u8],
+	) -> Result<Vec<u8>, DispatchError> {
+		match func_id {
+			FUNC_OWNER_OF => {
+				let kitty_id = Self::decode::<T::KittyIndex>(&mut input)?;
+				let owner =
+					Module::<T>::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+				Ok(owner.encode())
+			},
+			FUNC_TRANSFER => {
+				let (to, kitty_id) =
+					Self::decode::<(T::AccountId, T::KittyIndex)>(&mut input)?;
+				Self::transfer(origin, to, kitty_id)?;
+				Ok(Vec::new())
+			},
+			FUNC_BREED => {
+				let (kitty_id_1, kitty_id_2) =
+					Self::decode::<(T::KittyIndex, T::KittyIndex)>(&mut input)?;
+				let kitty_id = Self::breed(origin, kitty_id_1, kitty_id_2)?;
+				Ok(kitty_id.encode())
+			},
+			FUNC_ATTRIBUTES => {
+				let kitty_id = Self::decode::<T::KittyIndex>(&mut input)?;
+				let attributes =
+					Module::<T>::attributes(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+				Ok(attributes.encode())
+			},
+			_ => Err(DispatchError::Other("unknown kitties chain extension function")),
+		}
+	}
+
+	fn decode<V: Decode>(input: &mut &[u8]) -> Result<V, DispatchError> {
+		V::decode(input)
+			.map_err(|_| DispatchError::Other("malformed kitties chain extension input"))
+	}
+
+	/// The `transfer` extrinsic's checks and deposit shuffle, with the
+	/// contract as sender.
+	fn transfer(
+		sender: T::AccountId,
+		to: T::AccountId,
+		kitty_id: T::KittyIndex,
+	) -> Result<(), DispatchError> {
+		let owner = Module::<T>::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+		frame_support::ensure!(owner == sender, Error::<T>::NotKittyOwner);
+		frame_support::ensure!(
+			Module::<T>::fraction_shares(kitty_id).is_none(),
+			Error::<T>::KittyFractionalized
+		);
+		frame_support::ensure!(
+			Module::<T>::bridged_out(kitty_id).is_none(),
+			Error::<T>::KittyBridgedOut
+		);
+		frame_support::ensure!(
+			Module::<T>::kitty_lock(kitty_id).is_none(),
+			Error::<T>::KittyLocked
+		);
+		frame_support::ensure!(
+			Module::<T>::escrows(kitty_id).is_none(),
+			Error::<T>::KittyInEscrow
+		);
+		frame_support::ensure!(!Module::<T>::is_departed(kitty_id), Error::<T>::KittyDeparted);
+		Module::<T>::ensure_not_soulbound(kitty_id)?;
+		Module::<T>::ensure_can_hold_one_more(&to)?;
+		Module::<T>::ensure_accepts_transfer(&to)?;
+
+		T::Currency::reserve(&to, T::KittyDeposit::get())?;
+		T::Currency::unreserve(&sender, T::KittyDeposit::get());
+		Module::<T>::do_transfer(&sender, &to, kitty_id);
+		Module::<T>::note_provenance(kitty_id, &to, TransferKind::Transfer);
+		Module::<T>::deposit_event(RawEvent::Transferred(sender, to, kitty_id));
+		Ok(())
+	}
+
+	/// The `breed` extrinsic's rights checks; the child goes to the first
+	/// parent's owner just as it does there.
+	fn breed(
+		sender: T::AccountId,
+		kitty_id_1: T::KittyIndex,
+		kitty_id_2: T::KittyIndex,
+	) -> Result<T::KittyIndex, DispatchError> {
+		let owner1 = Module::<T>::kitty_owner(kitty_id_1).ok_or(Error::<T>::InvalidKittyId)?;
+		let owner2 = Module::<T>::kitty_owner(kitty_id_2).ok_or(Error::<T>::InvalidKittyId)?;
+		let now = <frame_system::Module<T>>::block_number();
+		frame_support::ensure!(
+			owner1 == sender || Module::<T>::has_breeding_delegation(&sender, kitty_id_1, now),
+			Error::<T>::NoBreedingRights
+		);
+		frame_support::ensure!(
+			owner2 == sender || Module::<T>::has_breeding_delegation(&sender, kitty_id_2, now),
+			Error::<T>::NoBreedingRights
+		);
+
+		// Mirror the extrinsic's siring settlement: a foreign second
+		// parent's owner is paid the share agreed in the delegation.
+		let mut sire_shares: Vec<(T::AccountId, sp_runtime::Percent)> = Vec::new();
+		if owner2 != owner1 {
+			if let Some((_, _, share)) = Module::<T>::breeding_delegations(kitty_id_2, &sender) {
+				sire_shares.push((owner2.clone(), share));
+			}
+		}
+		let kitty_id = Module::<T>::do_breed(&owner1, kitty_id_1, kitty_id_2, &sire_shares)?;
+		if owner1 != sender {
+			Module::<T>::consume_breeding_delegation(kitty_id_1, &sender);
+		}
+		if owner2 != sender {
+			Module::<T>::consume_breeding_delegation(kitty_id_2, &sender);
+		}
+		Module::<T>::deposit_event(RawEvent::Bred(
+			owner1.clone(),
+			kitty_id,
+			kitty_id_1,
+			kitty_id_2,
+			Module::<T>::breeder_id(&owner1),
+		));
+		Ok(kitty_id)
+	}
+}
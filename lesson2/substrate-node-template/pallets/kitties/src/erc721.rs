@@ -0,0 +1,181 @@
+//! An ERC-721 adapter over the kitties registry.
+//!
+//! EVM-enabled runtimes expose this through a Frontier precompile: the
+//! runtime's `Precompile` impl is a thin shim forwarding the call input
+//! and caller address to [`Erc721::call`]. The Frontier crates do not
+//! build against this Substrate version, so the adapter deliberately
+//! carries no EVM dependency of its own and sticks to plain types:
+//! 20-byte addresses and ABI-encoded words in, ABI-encoded words out.
+//! Solidity sees `ownerOf`, `balanceOf`, `transferFrom`, `approve` and
+//! `tokenURI` with standard selectors and semantics.
+
+use crate::{Erc721Approvals, KittyProvider, Module, RawEvent, Trait, TransferKind};
+use frame_support::traits::{Get, ReservableCurrency};
+use sp_runtime::{traits::SaturatedConversion, RuntimeDebug};
+use sp_std::prelude::*;
+
+/// `ownerOf(uint256)`
+pub const SELECTOR_OWNER_OF: [u8; 4] = [0x63, 0x52, 0x21, 0x1e];
+/// `balanceOf(address)`
+pub const SELECTOR_BALANCE_OF: [u8; 4] = [0x70, 0xa0, 0x82, 0x31];
+/// `transferFrom(address,address,uint256)`
+pub const SELECTOR_TRANSFER_FROM: [u8; 4] = [0x23, 0xb8, 0x72, 0xdd];
+/// `approve(address,uint256)`
+pub const SELECTOR_APPROVE: [u8; 4] = [0x09, 0x5e, 0xa7, 0xb3];
+/// `tokenURI(uint256)`
+pub const SELECTOR_TOKEN_URI: [u8; 4] = [0xc8, 0x7b, 0x56, 0xdd];
+
+/// How EVM addresses map onto this chain's account ids, both ways.
+/// EVM-enabled runtimes already have such a scheme; the mock pins a
+/// trivial one for tests.
+pub trait AddressMapping<AccountId> {
+	/// The account id behind an EVM address.
+	fn account(address: &[u8; 20]) -> AccountId;
+
+	/// The EVM address representing an account id.
+	fn address(who: &AccountId) -> [u8; 20];
+}
+
+/// Why an ERC-721 call was rejected. The precompile shim maps these onto
+/// EVM reverts.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum Erc721Error {
+	/// The input's four-byte selector is not part of the interface.
+	UnknownSelector,
+	/// The input is shorter than the selector demands, or a word carries
+	/// dirt outside its type's width.
+	MalformedInput,
+	/// The token id does not name a live kitty.
+	TokenNotFound,
+	/// The caller is neither the owner nor approved for the token.
+	NotAuthorized,
+	/// The registry refused the transfer (lock, escrow, soulbound,
+	/// recipient at capacity or unable to cover the deposit).
+	TransferRejected,
+}
+
+/// The adapter itself, generic over the runtime and its address scheme.
+pub struct Erc721<T, M>(sp_std::marker::PhantomData<(T, M)>);
+
+impl<T: Trait, M: AddressMapping<T::AccountId>> Erc721<T, M> {
+	/// Dispatch an ABI-encoded ERC-721 call made by `caller`, returning
+	/// the ABI-encoded output.
+	pub fn call(caller: [u8; 20], input: &[u8]) -> Result<Vec<u8>, Erc721Error> {
+		let selector = input.get(..4).ok_or(Erc721Error::MalformedInput)?;
+		match [selector[0], selector[1], selector[2], selector[3]] {
+			SELECTOR_OWNER_OF => Self::owner_of(input),
+			SELECTOR_BALANCE_OF => Self::balance_of(input),
+			SELECTOR_TRANSFER_FROM => Self::transfer_from(caller, input),
+			SELECTOR_APPROVE => Self::approve(caller, input),
+			SELECTOR_TOKEN_URI => Self::token_uri(input),
+			_ => Err(Erc721Error::UnknownSelector),
+		}
+	}
+
+	fn owner_of(input: &[u8]) -> Result<Vec<u8>, Erc721Error> {
+		let token = Self::token(input, 0)?;
+		let owner = Module::<T>::kitty_owner(token).ok_or(Erc721Error::TokenNotFound)?;
+		Ok(Self::encode_address(M::address(&owner)))
+	}
+
+	fn balance_of(input: &[u8]) -> Result<Vec<u8>, Erc721Error> {
+		let who = M::account(&Self::address(input, 0)?);
+		Ok(Self::encode_u256(Module::<T>::owned_kitties_count(&who) as u128))
+	}
+
+	fn approve(caller: [u8; 20], input: &[u8]) -> Result<Vec<u8>, Erc721Error> {
+		let spender = M::account(&Self::address(input, 0)?);
+		let token = Self::token(input, 1)?;
+		let owner = Module::<T>::kitty_owner(token).ok_or(Erc721Error::TokenNotFound)?;
+		if M::account(&caller) != owner {
+			return Err(Erc721Error::NotAuthorized);
+		}
+
+		<Erc721Approvals<T>>::insert(token, spender);
+		Ok(Vec::new())
+	}
+
+	fn transfer_from(caller: [u8; 20], input: &[u8]) -> Result<Vec<u8>, Erc721Error> {
+		let from = M::account(&Self::address(input, 0)?);
+		let to = M::account(&Self::address(input, 1)?);
+		let token = Self::token(input, 2)?;
+		let owner = Module::<T>::kitty_owner(token).ok_or(Erc721Error::TokenNotFound)?;
+		if owner != from {
+			return Err(Erc721Error::NotAuthorized);
+		}
+		let caller = M::account(&caller);
+		if caller != owner && Module::<T>::erc721_approval(token) != Some(caller) {
+			return Err(Erc721Error::NotAuthorized);
+		}
+		// The same gates and deposit shuffle as the `transfer` extrinsic;
+		// ERC-721 has no notion of recipient preferences, so those are
+		// not consulted.
+		if !Module::<T>::can_transfer(token) {
+			return Err(Erc721Error::TransferRejected);
+		}
+		Module::<T>::ensure_can_hold_one_more(&to)
+			.map_err(|_| Erc721Error::TransferRejected)?;
+		T::Currency::reserve(&to, T::KittyDeposit::get())
+			.map_err(|_| Erc721Error::TransferRejected)?;
+
+		T::Currency::unreserve(&from, T::KittyDeposit::get());
+		Module::<T>::do_transfer(&from, &to, token);
+		Module::<T>::note_provenance(token, &to, TransferKind::Transfer);
+		Module::<T>::deposit_event(RawEvent::Transferred(from, to, token));
+		Ok(Vec::new())
+	}
+
+	fn token_uri(input: &[u8]) -> Result<Vec<u8>, Erc721Error> {
+		let token = Self::token(input, 0)?;
+		Module::<T>::kitties(token).ok_or(Erc721Error::TokenNotFound)?;
+		let mut uri = b"kitties://".to_vec();
+		Module::<T>::push_decimal(&mut uri, token.saturated_into::<u32>());
+		Ok(Self::encode_string(&uri))
+	}
+
+	/// The `index`-th 32-byte argument word of the call input.
+	fn word(input: &[u8], index: usize) -> Result<&[u8], Erc721Error> {
+		let start = 4 + index * 32;
+		input.get(start..start + 32).ok_or(Erc721Error::MalformedInput)
+	}
+
+	fn address(input: &[u8], index: usize) -> Result<[u8; 20], Erc721Error> {
+		let word = Self::word(input, index)?;
+		if word[..12].iter().any(|byte| *byte != 0) {
+			return Err(Erc721Error::MalformedInput);
+		}
+		let mut out = [0u8; 20];
+		out.copy_from_slice(&word[12..]);
+		Ok(out)
+	}
+
+	fn token(input: &[u8], index: usize) -> Result<T::KittyIndex, Erc721Error> {
+		let word = Self::word(input, index)?;
+		if word[..28].iter().any(|byte| *byte != 0) {
+			return Err(Erc721Error::MalformedInput);
+		}
+		let mut raw = [0u8; 4];
+		raw.copy_from_slice(&word[28..]);
+		Ok(u32::from_be_bytes(raw).into())
+	}
+
+	fn encode_u256(value: u128) -> Vec<u8> {
+		let mut out = vec![0u8; 32];
+		out[16..].copy_from_slice(&value.to_be_bytes());
+		out
+	}
+
+	fn encode_address(address: [u8; 20]) -> Vec<u8> {
+		let mut out = vec![0u8; 32];
+		out[12..].copy_from_slice(&address);
+		out
+	}
+
+	fn encode_string(bytes: &[u8]) -> Vec<u8> {
+		let mut out = Self::encode_u256(32);
+		out.extend(Self::encode_u256(bytes.len() as u128));
+		out.extend(bytes);
+		out.resize(64 + (bytes.len() + 31) / 32 * 32, 0);
+		out
+	}
+}
@@ -0,0 +1,7751 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// A pallet implementing collectible kitties: accounts can create kitties,
+/// transfer them and breed two of their kitties to produce a new one.
+///
+/// Creating a kitty reserves a deposit from the owner, breeding charges a
+/// fee, and supply is bounded both globally and per account. All of these
+/// economic and gameplay parameters are configured by the runtime and
+/// exposed as module constants so they can be read from metadata.
+///
+/// # Atomicity
+///
+/// This Substrate version predates storage transactions, so a dispatch
+/// that errors after writing leaves those writes in place. Every
+/// multi-step extrinsic therefore runs all of its checks and fallible
+/// operations (withdrawals, reserves, payment settlement) before the
+/// first storage write, and the few unavoidable multi-part currency
+/// dances roll back explicitly on failure. Keep to that discipline when
+/// adding dispatchables; `with_transaction` is the cleaner tool once an
+/// upgrade makes it available.
+
+use codec::{Decode, Encode};
+use frame_support::{
+	decl_error, decl_event, decl_module, decl_storage,
+	dispatch::{DispatchResult, DispatchResultWithPostInfo},
+	ensure,
+	inherent::{InherentData, InherentIdentifier, MakeFatalError, ProvideInherent},
+	traits::{
+		Currency, EnsureOrigin, ExistenceRequirement, Get, Imbalance, Randomness,
+		ReservableCurrency, WithdrawReason,
+	},
+	unsigned::ValidateUnsigned,
+	weights::{DispatchClass, FunctionOf, Pays, Weight},
+	IterableStorageDoubleMap, IterableStorageMap, Parameter,
+};
+use frame_system::{
+	self as system, ensure_none, ensure_signed,
+	offchain::{SendTransactionTypes, SubmitTransaction},
+};
+use sp_io::hashing::{blake2_128, blake2_256, sha2_256};
+use sp_runtime::{
+	offchain::storage::StorageValueRef,
+	traits::{
+		AccountIdConversion, AtLeast32Bit, Bounded, Dispatchable, DispatchInfoOf, Member, One,
+		SaturatedConversion, Saturating, SignedExtension, Zero,
+	},
+	transaction_validity::{
+		InvalidTransaction, TransactionPriority, TransactionSource, TransactionValidity,
+		TransactionValidityError, ValidTransaction,
+	},
+	DispatchError, ModuleId, Percent, RuntimeDebug,
+};
+use sp_std::prelude::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+pub mod chain_ext;
+pub mod erc721;
+pub mod migration;
+
+pub type BalanceOf<T> =
+	<<T as Trait>::Currency as Currency<<T as system::Trait>::AccountId>>::Balance;
+type NegativeImbalanceOf<T> =
+	<<T as Trait>::Currency as Currency<<T as system::Trait>::AccountId>>::NegativeImbalance;
+
+/// The inherent identifier of the scheduled gen-0 drops.
+pub const INHERENT_IDENTIFIER: InherentIdentifier = *b"ktydrop0";
+
+/// The module account holding dropped gen-0 kitties until they are
+/// claimed.
+const DROP_POOL_ID: ModuleId = ModuleId(*b"kty/drop");
+
+/// The module account holding liquidated loan collateral while it is
+/// auctioned, and the liquidation proceeds until they are routed.
+const LOAN_POOL_ID: ModuleId = ModuleId(*b"kty/lend");
+
+/// The module account named as the lock holder of bundled kitties; it
+/// never owns them or holds funds.
+const BUNDLE_LOCK_ID: ModuleId = ModuleId(*b"kty/bndl");
+
+/// The module account named as the lock holder of kitties with active
+/// market state — listings, auctions and breeding delegations; like the
+/// bundle lock it never owns them or holds funds.
+const MARKET_LOCK_ID: ModuleId = ModuleId(*b"kty/mrkt");
+
+/// The module account holding custodially listed kitties; it owns them
+/// deposit-free while the listing stands but never holds funds.
+const LISTING_CUSTODY_ID: ModuleId = ModuleId(*b"kty/cstd");
+
+/// The lock placed on kitties sitting inside a hash-timelocked swap;
+/// like the market lock it never owns them or holds funds.
+const HTLC_LOCK_ID: ModuleId = ModuleId(*b"kty/htlc");
+
+/// The lock placed on kitties awaiting a scheduled timelocked transfer;
+/// like the market lock it never owns them or holds funds.
+const TIMED_LOCK_ID: ModuleId = ModuleId(*b"kty/time");
+
+/// The module account pooling asset-denominated offer and bid funds,
+/// which have no reserve mechanism of their own, until settlement or
+/// release.
+const PAYMENT_POOL_ID: ModuleId = ModuleId(*b"kty/payp");
+
+/// The module account holding the governance-funded buyback pool that
+/// pays owners for burning their kitties.
+const BUYBACK_POOL_ID: ModuleId = ModuleId(*b"kty/buyb");
+
+/// The keyless holder recorded in `KittyLocks` for the life of a
+/// fractionalized kitty's shares, so every lock-checking path refuses to
+/// move or burn it.
+const FRACTION_LOCK_ID: ModuleId = ModuleId(*b"kty/frac");
+
+/// The keyless holder recorded in `KittyLocks` while a kitty is bridged
+/// out, so no local path can move or burn it while the wrapped ERC-721
+/// circulates on Ethereum.
+const BRIDGE_LOCK_ID: ModuleId = ModuleId(*b"kty/brdg");
+pub type AssetIdOf<T> =
+	<<T as Trait>::Fungibles as Fungibles<<T as system::Trait>::AccountId>>::AssetId;
+
+/// A kitty is identified on chain by its 16 bytes of DNA.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Kitty(pub [u8; 16]);
+
+/// How a kitty came to its current owner; recorded in the provenance log.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum TransferKind {
+	/// Created out of nothing (genesis or `create`).
+	Mint,
+	/// Born from two parents.
+	Breed,
+	/// Plain transfer between accounts.
+	Transfer,
+	/// Bought at a fixed-price listing.
+	Sale,
+	/// Won at auction.
+	Auction,
+	/// Sold via an accepted offer.
+	Offer,
+	/// Seized by a collateral taker.
+	Seizure,
+	/// Forged by burning two parents.
+	Fusion,
+	/// Born from a kitty and a foreign creature.
+	Hybrid,
+	/// Exchanged in a kitty-for-kitty swap.
+	Swap,
+	/// Claimed from a hash-timelocked swap.
+	Htlc,
+	/// Claimed by a designated heir after owner inactivity.
+	Inheritance,
+}
+
+/// A reference to one of a kitty's parents: another kitty, or a creature
+/// living in a registered foreign pallet, tagged with its species.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum ParentRef<KittyIndex, CreatureId> {
+	/// A kitty in this registry.
+	Kitty(KittyIndex),
+	/// A creature from the foreign registry: `(species_tag, creature_id)`.
+	Foreign([u8; 4], CreatureId),
+}
+
+/// Interface to a foreign creature pallet (puppies, dragons, …) whose
+/// creatures can sire hybrid kittens. The species tag ends up in the
+/// hybrid's pedigree, so it should be stable across runtime upgrades.
+pub trait ForeignCreature<AccountId, CreatureId> {
+	/// A stable four-byte tag identifying the species.
+	const SPECIES: [u8; 4];
+
+	/// The owner of the given creature, if it exists.
+	fn owner_of(creature_id: &CreatureId) -> Option<AccountId>;
+
+	/// The creature's 16 bytes of gene material, if it exists.
+	fn dna_of(creature_id: &CreatureId) -> Option<[u8; 16]>;
+}
+
+/// The default foreign registry for runtimes without a partner pallet:
+/// no creatures exist and cross-species breeding always fails.
+pub struct NoForeignCreatures;
+impl<AccountId, CreatureId> ForeignCreature<AccountId, CreatureId> for NoForeignCreatures {
+	const SPECIES: [u8; 4] = *b"none";
+
+	fn owner_of(_creature_id: &CreatureId) -> Option<AccountId> {
+		None
+	}
+
+	fn dna_of(_creature_id: &CreatureId) -> Option<[u8; 16]> {
+		None
+	}
+}
+
+/// Interface to a fungible-asset pallet backing fractionalized kitties.
+/// A runtime wires this to `pallet-assets` or similar; the share amounts
+/// are plain `u128` so the issuer's balance type stays its own business.
+pub trait Fungibles<AccountId> {
+	/// The issuer's asset class identifier.
+	type AssetId: Parameter + Member + Copy;
+
+	/// Create a fresh asset class of `total` units, all credited to
+	/// `beneficiary`.
+	fn issue(beneficiary: &AccountId, total: u128) -> sp_std::result::Result<Self::AssetId, DispatchError>;
+
+	/// `who`'s holding of the given asset class.
+	fn balance_of(asset: &Self::AssetId, who: &AccountId) -> u128;
+
+	/// Destroy the asset class. Only called once `who` has reassembled
+	/// the full supply.
+	fn retire(asset: &Self::AssetId, who: &AccountId) -> DispatchResult;
+
+	/// Move `amount` units of the asset between accounts.
+	fn transfer(
+		asset: &Self::AssetId,
+		from: &AccountId,
+		to: &AccountId,
+		amount: u128,
+	) -> DispatchResult;
+}
+
+/// The default issuer for runtimes without a fungible-asset pallet:
+/// issuance always fails, which disables fractionalization.
+pub struct NoFungibles;
+impl<AccountId> Fungibles<AccountId> for NoFungibles {
+	type AssetId = u32;
+
+	fn issue(_beneficiary: &AccountId, _total: u128) -> sp_std::result::Result<u32, DispatchError> {
+		Err(DispatchError::Other("no fungible issuer configured"))
+	}
+
+	fn balance_of(_asset: &u32, _who: &AccountId) -> u128 {
+		0
+	}
+
+	fn retire(_asset: &u32, _who: &AccountId) -> DispatchResult {
+		Err(DispatchError::Other("no fungible issuer configured"))
+	}
+
+	fn transfer(
+		_asset: &u32,
+		_from: &AccountId,
+		_to: &AccountId,
+		_amount: u128,
+	) -> DispatchResult {
+		Err(DispatchError::Other("no fungible issuer configured"))
+	}
+}
+
+/// A price feed quoting the native currency against an off-chain
+/// reference unit (USD or similar), so long-lived listings can track a
+/// stable value instead of the token price.
+pub trait PriceOracle<Balance> {
+	/// Convert `amount` reference units into the native currency at the
+	/// current quote, or `None` while the feed has no usable price.
+	fn reference_to_native(amount: Balance) -> Option<Balance>;
+}
+
+/// The default feed for runtimes without an oracle: never quotes, which
+/// disables reference-priced listings.
+pub struct NoPriceOracle;
+impl<Balance> PriceOracle<Balance> for NoPriceOracle {
+	fn reference_to_native(_amount: Balance) -> Option<Balance> {
+		None
+	}
+}
+
+/// A view into an identity registry such as `pallet-identity`, gating
+/// breeder registration on a judged, human-readable identity.
+pub trait IdentityProvider<AccountId> {
+	/// Whether `who` holds an identity with a positive judgement.
+	fn is_verified(who: &AccountId) -> bool;
+}
+
+/// The default registry for runtimes without an identity pallet: nobody
+/// verifies, which disables breeder registration.
+pub struct NoIdentity;
+impl<AccountId> IdentityProvider<AccountId> for NoIdentity {
+	fn is_verified(_who: &AccountId) -> bool {
+		false
+	}
+}
+
+/// A `Randomness` implementation derived purely from the current block
+/// number and the subject, for use as the `Randomness` config type in mock
+/// and dev runtimes: DNA outcomes in tests and local demos become
+/// reproducible. It is trivially predictable, so never use it on a
+/// value-bearing network.
+pub struct DeterministicRandomness<T>(sp_std::marker::PhantomData<T>);
+
+impl<T: system::Trait> Randomness<T::Hash> for DeterministicRandomness<T> {
+	fn random(subject: &[u8]) -> T::Hash {
+		let hash = (<system::Module<T>>::block_number(), subject).using_encoded(blake2_256);
+		let mut output = T::Hash::default();
+		let len = output.as_mut().len().min(hash.len());
+		output.as_mut()[..len].copy_from_slice(&hash[..len]);
+		output
+	}
+}
+
+/// The decoded phenotype attributes of a kitty, derived from its DNA. This
+/// is the canonical decoding; explorers and wallets should use the runtime
+/// API rather than duplicating the gene math client side.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct KittyAttributes {
+	/// Fur colour, one of 8 variants.
+	pub fur: u8,
+	/// Eye colour, one of 8 variants.
+	pub eyes: u8,
+	/// Coat pattern, one of 16 variants.
+	pub pattern: u8,
+}
+
+/// The filter of a collection-wide offer. A kitty qualifies if its
+/// generation is at or below `max_generation` and every set attribute
+/// constraint matches its decoded phenotype.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct OfferFilter {
+	pub max_generation: u32,
+	pub fur: Option<u8>,
+	pub eyes: Option<u8>,
+	pub pattern: Option<u8>,
+}
+
+/// Cheap per-kitty usage counters, updated by the respective extrinsics so
+/// rarity and valuation formulas can incorporate usage without scanning
+/// events.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Default, RuntimeDebug)]
+pub struct KittyCounters {
+	/// Times the kitty changed owner (mints excluded).
+	pub transfers: u32,
+	/// Times the kitty was used as a breeding parent.
+	pub breedings: u32,
+	/// Battles (races, tournaments) the kitty took part in.
+	pub battles: u32,
+}
+
+/// A proposed cross-owner breeding. The proposer owns the first kitty and
+/// offers `fee` to the owner of the second; on acceptance the breed executes
+/// atomically, paying the fee and assigning the child to
+/// `offspring_recipient`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct BreedingAgreement<AccountId, Balance> {
+	pub proposer: AccountId,
+	pub fee: Balance,
+	pub offspring_recipient: AccountId,
+}
+
+/// A fixed-price listing. Besides the asking price the seller may define a
+/// bounded revenue split: each share of the net proceeds (after the market
+/// commission) goes to the named beneficiary, the remainder to the seller.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Listing<AccountId, Balance, AssetId> {
+	pub seller: AccountId,
+	pub price: Balance,
+	pub splits: Vec<(AccountId, Percent)>,
+	/// The asset class the price is denominated in; `None` is the
+	/// native currency.
+	pub asset: Option<AssetId>,
+	/// Whether `price` is in the oracle's reference unit, converted to
+	/// the native currency at settlement.
+	pub reference_priced: bool,
+	/// An optional donation out of the net proceeds to an approved
+	/// charity, routed like an extra revenue split at settlement.
+	pub donation: Option<(AccountId, Percent)>,
+}
+
+/// Profile milestones, unlocked once per account and kept forever.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum Achievement {
+	/// Acquired a first kitty, by any means.
+	FirstKitty,
+	/// Received ten bred kittens.
+	TenBreedings,
+	/// Bred a kitten of generation five or later.
+	BredGenFive,
+	/// Held a kitty with the maximum-rarity pattern.
+	MaxRarityOwner,
+}
+
+/// One-call aggregate chain statistics for dashboards, assembled from
+/// counters the pallet keeps in step on every mint, burn and trade so the
+/// read never walks the kitty maps.
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct AggregateStats<Balance> {
+	/// Kitties currently in existence.
+	pub total_supply: u32,
+	/// `(generation, live count)` pairs, ascending, empty generations
+	/// omitted.
+	pub generations: Vec<(u32, u32)>,
+	/// Kitties ever burned (fused away, reaped or redeemed out).
+	pub total_burned: u32,
+	/// The lifetime native-denominated sale volume; asset-denominated
+	/// trades are not folded in since they have no native price.
+	pub trade_volume: Balance,
+	/// Fixed-price listings currently open.
+	pub active_listings: u32,
+}
+
+/// Derived combat/racing stats. All game subsystems must go through
+/// `effective_stats` so the DNA decoding and equipment bonuses are applied
+/// in exactly one place.
+#[derive(Encode, Decode, Default, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct KittyStats {
+	pub strength: u32,
+	pub agility: u32,
+	pub stamina: u32,
+	pub charm: u32,
+}
+
+/// A kitty's well-being. `energy` is the value as of `updated_at`; readers
+/// must apply the per-block decay since then (see `current_energy`). Energy
+/// gates battles and degrades breeding success when low.
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct KittyVitals<BlockNumber> {
+	pub energy: u32,
+	pub updated_at: BlockNumber,
+}
+
+/// An account's transfer-acceptance preferences. The default accepts any
+/// incoming transfer without a cap; accounts opt in to stricter settings
+/// to keep unsolicited kitties out.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct AccountPreferences {
+	/// Whether unsolicited transfers are accepted at all.
+	pub auto_accept: bool,
+	/// An optional cap on holdings enforced against incoming transfers,
+	/// tighter than `MaxKittiesPerAccount` when set.
+	pub max_incoming: Option<u32>,
+}
+
+impl Default for AccountPreferences {
+	fn default() -> Self {
+		AccountPreferences { auto_accept: true, max_incoming: None }
+	}
+}
+
+/// A knockout tournament. Entry is open until `start`; from then on one
+/// round resolves per block, pairing off the remaining contenders, until a
+/// single winner takes the prize pool. `end` is a deadline: a tournament
+/// still unresolved there goes to the strongest remaining kitty.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Tournament<Balance, BlockNumber> {
+	pub entry_fee: Balance,
+	pub max_entrants: u32,
+	pub start: BlockNumber,
+	pub end: BlockNumber,
+	pub prize_pool: Balance,
+}
+
+/// A limited edition: a named, admin-curated minting window whose kitties
+/// carry a fixed DNA prefix byte as their edition marker and count against
+/// a dedicated supply cap.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Edition<BlockNumber> {
+	pub name: Vec<u8>,
+	pub supply_cap: u32,
+	pub dna_prefix: u8,
+	pub open: BlockNumber,
+	pub close: BlockNumber,
+	pub minted: u32,
+}
+
+/// What a verified-collection entry covers: a contiguous range of kitty
+/// ids, or everything minted in a named edition.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum VerifiedScope<KittyIndex> {
+	/// The ids from the first to the second bound, inclusive.
+	Range(KittyIndex, KittyIndex),
+	/// Every kitty minted in the given edition.
+	Edition(u32),
+}
+
+/// A breeder-run cattery: a named grouping an account brands its kitties
+/// under, so drops can be browsed and filtered as one line. Membership is
+/// advisory metadata for market filters; it never restricts what a kitty
+/// can do.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Cattery<AccountId> {
+	pub owner: AccountId,
+	pub name: Vec<u8>,
+	pub members: u32,
+}
+
+/// What a member account may do with a cattery's kitties. Roles are not
+/// ranked: a manager lists and delists, a breeder breeds, and a viewer
+/// is recognized as affiliated but can do neither.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum CatteryRole {
+	Manager,
+	Breeder,
+	Viewer,
+}
+
+/// A one-off race. Entry is open until `start`, where all runners compete
+/// at once: finishing order is decided by DNA-derived speed plus a random
+/// roll, and the pooled entry fees are paid to the top finishers.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Race<Balance, BlockNumber> {
+	pub entry_fee: Balance,
+	pub max_runners: u32,
+	pub start: BlockNumber,
+	pub prize_pool: Balance,
+}
+
+/// A kitty's lifetime racing record, kept after the races themselves are
+/// gone because it feeds into breeding value.
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct KittyRaceRecord {
+	pub races: u32,
+	pub wins: u32,
+	pub podiums: u32,
+}
+
+/// A pending two-phase transfer. The kitty and its deposit stay with the
+/// sender until the recipient claims; once `expires_at` has passed the
+/// offer is dead and the kitty simply remains where it always was.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct PendingTransfer<AccountId, BlockNumber> {
+	pub from: AccountId,
+	pub to: AccountId,
+	pub expires_at: BlockNumber,
+}
+
+/// The audit trail of a mint: the chain data the DNA was derived from,
+/// recorded so anyone can later recompute the derivation and check that
+/// the genes were not hand-picked.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct BirthRecord<AccountId, Hash, BlockNumber> {
+	/// The block in which the kitty was minted.
+	pub block: BlockNumber,
+	/// The position of the minting extrinsic within that block.
+	pub extrinsic_index: Option<u32>,
+	/// The randomness seed the DNA preimage committed to.
+	pub seed_hash: Hash,
+	/// The account the DNA preimage committed to.
+	pub minter: AccountId,
+}
+
+/// An escrowed sale. The buyer's payment stays reserved and the kitty is
+/// held immobile until the dispute window passes, after which settlement
+/// finalizes automatically; a raised dispute instead waits for the arbiter.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Escrow<AccountId, Balance, BlockNumber> {
+	pub seller: AccountId,
+	pub buyer: AccountId,
+	pub price: Balance,
+	pub release_at: BlockNumber,
+	pub disputed: bool,
+}
+
+/// A hash-timelocked kitty transfer, the on-chain half of a cross-chain
+/// atomic swap. The recipient can claim the kitty with the hash's
+/// preimage until the timeout; afterwards only the owner can refund.
+/// `hash` is the sha2-256 of the shared secret — the digest the usual
+/// cross-chain HTLC tooling speaks.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct HtlcSwap<AccountId, BlockNumber> {
+	pub owner: AccountId,
+	pub recipient: AccountId,
+	pub hash: [u8; 32],
+	pub timeout_at: BlockNumber,
+}
+
+/// A scheduled kitty transfer: the kitty stays with the sender under a
+/// trade lock and is delivered to the recipient at the unlock block, or
+/// on claim afterwards if automatic delivery could not land it.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct TimedTransfer<AccountId, BlockNumber> {
+	pub from: AccountId,
+	pub to: AccountId,
+	pub unlock_at: BlockNumber,
+}
+
+/// An installment purchase of a listed kitty. The down payment and every
+/// installment paid so far sit reserved on the buyer; the kitty stays with
+/// the seller under a trade lock until the last installment settles. A
+/// missed due date forfeits the down payment to the seller and refunds the
+/// rest.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct InstallmentPlan<AccountId, Balance, BlockNumber> {
+	pub seller: AccountId,
+	pub buyer: AccountId,
+	pub price: Balance,
+	pub down_payment: Balance,
+	pub per_installment: Balance,
+	/// The amount still unpaid; the final installment absorbs any
+	/// rounding remainder.
+	pub outstanding: Balance,
+	pub remaining: u32,
+	pub next_due: BlockNumber,
+	pub splits: Vec<(AccountId, Percent)>,
+}
+
+/// The commitment recorded when a kitty exits to a successor chain: the
+/// final owner, the DNA and a hash of the pedigree, everything the other
+/// side needs to re-mint the kitty faithfully.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct ExitCommitment<AccountId, BlockNumber> {
+	pub owner: AccountId,
+	pub dna: [u8; 16],
+	pub pedigree_hash: [u8; 32],
+	pub exited_at: BlockNumber,
+}
+
+/// An English auction for a kitty. The highest bid is held in reserve on the
+/// bidder's account until the auction settles or the bid is outbid.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Auction<AccountId, Balance, BlockNumber, AssetId> {
+	pub seller: AccountId,
+	pub reserve_price: Balance,
+	pub end: BlockNumber,
+	pub top_bidder: Option<AccountId>,
+	pub top_bid: Balance,
+	/// The asset class bids are denominated in; `None` is the native
+	/// currency. Asset bids are pooled rather than reserved.
+	pub asset: Option<AssetId>,
+}
+
+/// A kitty-collateralized loan. Until funded, `lender` is `None` and the
+/// terms are an open request; once funded the borrower owes the principal
+/// plus `interest_per_block` for every block since `funded_at`, and the
+/// collateral can be liquidated once `duration` blocks have passed.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Loan<AccountId, Balance, BlockNumber> {
+	pub borrower: AccountId,
+	pub lender: Option<AccountId>,
+	pub principal: Balance,
+	pub interest_per_block: Balance,
+	pub duration: BlockNumber,
+	pub funded_at: Option<BlockNumber>,
+	pub in_liquidation: bool,
+}
+
+/// A bundle listing: several kitties sold together at one price. The
+/// kitties stay locked while the bundle is live and settle atomically.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Bundle<AccountId, Balance, KittyId> {
+	pub seller: AccountId,
+	pub price: Balance,
+	pub kitties: Vec<KittyId>,
+	pub splits: Vec<(AccountId, Percent)>,
+}
+
+/// A sealed-bid (Vickrey) auction. Bidders commit to a hashed bid during
+/// the commit phase, backing it with a deposit, and reveal the amount
+/// during the reveal phase; the highest revealed bid wins but pays only
+/// the second-highest price (or the reserve). Deposits of unrevealed
+/// commitments are slashed at settlement.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct SealedAuction<AccountId, Balance, BlockNumber> {
+	pub seller: AccountId,
+	pub reserve_price: Balance,
+	pub bid_deposit: Balance,
+	pub commit_end: BlockNumber,
+	pub reveal_end: BlockNumber,
+}
+
+/// An auction for a reserved premium name. Unlike kitty auctions the
+/// proceeds go to the market-fee beneficiary (or are burned), and the
+/// winner receives a claim allowing them to assign the name to one of
+/// their kitties.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct NameAuction<AccountId, Balance, BlockNumber> {
+	pub reserve_price: Balance,
+	pub end: BlockNumber,
+	pub top_bidder: Option<AccountId>,
+	pub top_bid: Balance,
+}
+
+/// Interface for other pallets (e.g. a lending pallet) to take kitties as
+/// collateral. A locked kitty cannot be transferred by its owner until the
+/// locker releases it or seizes it for a new owner. Lockers are identified
+/// by an account id (typically a pallet's module account) and must be
+/// registered via `register_collateral_taker` before they can lock anything.
+pub trait NonFungibleCollateral<AccountId, KittyId> {
+	/// Lock a kitty as collateral on behalf of `locker`.
+	fn lock_collateral(kitty_id: KittyId, locker: &AccountId) -> DispatchResult;
+	/// Release a collateral lock previously taken by `locker`.
+	fn release_collateral(kitty_id: KittyId, locker: &AccountId) -> DispatchResult;
+	/// Seize a locked kitty, transferring it to `new_owner` and clearing the lock.
+	fn seize_collateral(kitty_id: KittyId, locker: &AccountId, new_owner: &AccountId) -> DispatchResult;
+}
+
+/// Read-only view of the kitty registry for game, market and lending
+/// pallets. Depending on this trait instead of the pallet's storage keeps
+/// those pallets decoupled from its internal layout.
+pub trait KittyProvider<AccountId, KittyId> {
+	/// The current owner of the kitty, if it exists.
+	fn owner_of(kitty_id: KittyId) -> Option<AccountId>;
+	/// Whether a kitty exists under this id.
+	fn exists(kitty_id: KittyId) -> bool;
+	/// The kitty's DNA, if it exists.
+	fn dna_of(kitty_id: KittyId) -> Option<[u8; 16]>;
+	/// Whether the kitty could currently change owners: it exists and is
+	/// neither locked as collateral, held in escrow, nor departed.
+	fn can_transfer(kitty_id: KittyId) -> bool;
+}
+
+/// The pallet's configuration trait. The `SendTransactionTypes` bound
+/// lets the off-chain worker submit its findings back as unsigned
+/// transactions, validated by this module's `ValidateUnsigned`.
+pub trait Trait: system::Trait + SendTransactionTypes<Call<Self>> {
+	/// The overarching event type.
+	type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
+
+	/// The currency used for kitty deposits and fees.
+	type Currency: ReservableCurrency<Self::AccountId>;
+
+	/// The source of randomness used when generating DNA.
+	type Randomness: Randomness<Self::Hash>;
+
+	/// The type used to identify kitties.
+	type KittyIndex: Parameter + Member + AtLeast32Bit + Bounded + Default + Copy;
+
+	/// When `true`, kitty ids are derived from the DNA (the first four bytes
+	/// of `blake2_128(dna ++ disambiguator)`) instead of being allocated
+	/// sequentially. This removes the dependence on the single `KittiesCount`
+	/// hot key for id allocation and makes ids collision-checked.
+	///
+	/// NOTE: only flip this on a fresh chain. Enabling it on a live chain
+	/// would leave the existing densely-numbered kitties in place, and any
+	/// tooling assuming dense ids must be migrated; a storage migration
+	/// re-keying `Kitties`/`KittyOwners` would be required to convert old
+	/// entries to content-addressed ids.
+	type ContentAddressedIds: Get<bool>;
+
+	/// The deposit reserved from the owner for every kitty they hold.
+	type KittyDeposit: Get<BalanceOf<Self>>;
+
+	/// The fee charged for breeding two kitties.
+	type BreedFee: Get<BalanceOf<Self>>;
+
+	/// The number of blocks a kitty must rest between breedings.
+	type BreedCooldown: Get<Self::BlockNumber>;
+
+	/// The maximum number of kitties that can ever exist.
+	type MaxKittySupply: Get<u32>;
+
+	/// The lifetime cap on gen-0 kitties minted through `create`.
+	type GenZeroCap: Get<u32>;
+
+	/// The number of additional gen-0 mints released per tranche period; one
+	/// tranche is available from genesis.
+	type GenZeroTrancheSize: Get<u32>;
+
+	/// The number of blocks between gen-0 allowance releases.
+	type GenZeroTranchePeriod: Get<Self::BlockNumber>;
+
+	/// The maximum number of kitties a single account may hold.
+	type MaxKittiesPerAccount: Get<u32>;
+
+	/// The maximum number of auctions settled in `on_initialize` per block;
+	/// any overflow is carried over to the next block.
+	type MaxAuctionSettlementsPerBlock: Get<u32>;
+
+	/// The commission deducted from every sale (fixed price, auction and
+	/// offer acceptance), as a percentage of the sale price.
+	type MarketFeePercent: Get<Percent>;
+
+	/// Where the market commission goes: a beneficiary account such as the
+	/// treasury, or, if `None`, burned.
+	type MarketFeeBeneficiary: Get<Option<Self::AccountId>>;
+
+	/// The share of every breed fee and market commission destroyed
+	/// outright before the beneficiary sees the rest, as a deflationary
+	/// counterweight to issuance. Everything burned is tallied in
+	/// `TotalFeesBurned`.
+	type FeeBurnPercent: Get<Percent>;
+
+	/// The maximum number of revenue split entries a listing may define.
+	type MaxSaleSplits: Get<u32>;
+
+	/// The maximum number of provenance entries kept per kitty; the oldest
+	/// entries are dropped first.
+	type MaxProvenanceEntries: Get<u32>;
+
+	/// How many blocks after birth a kitty's appearance can be re-rolled.
+	type RerollWindow: Get<Self::BlockNumber>;
+
+	/// The fee burned to re-roll a newborn's DNA.
+	type RerollFee: Get<BalanceOf<Self>>;
+
+	/// The fee burned to fuse two kitties into one.
+	type FusionFee: Get<BalanceOf<Self>>;
+
+	/// The id type of foreign breeding partners.
+	type ForeignCreatureId: Parameter;
+
+	/// The foreign creature registry allowed as a breeding partner;
+	/// `NoForeignCreatures` disables cross-species breeding.
+	type ForeignCreature: ForeignCreature<Self::AccountId, Self::ForeignCreatureId>;
+
+	/// The fungible-asset issuer backing fractionalized kitties.
+	type Fungibles: Fungibles<Self::AccountId>;
+
+	/// The maximum length of a kitty name in bytes.
+	type MaxNameLength: Get<u32>;
+
+	/// Whether free creations must carry a valid proof-of-work nonce. A
+	/// sybil cost for faucet-style chains where a token fee is no barrier.
+	type PowMintEnabled: Get<bool>;
+
+	/// Whether primary issuance is permissioned: with this on, the
+	/// `create` family of calls only accepts origins admitted by
+	/// `MintOrigin`. Breeding and trading stay open either way.
+	type PermissionedMinting: Get<bool>;
+
+	/// The origin allowed to mint while `PermissionedMinting` is on,
+	/// resolving to the minting account — typically `EnsureSignedBy` over
+	/// a breeders membership instance. Ignored while minting is open.
+	type MintOrigin: EnsureOrigin<Self::Origin, Success = Self::AccountId>;
+
+	/// Whether the testnet faucet is open: one free kitty per account per
+	/// era, with the transaction fee waived and the kitty deposit minted
+	/// on the spot. Inflationary by design — never enable this on a
+	/// value-bearing network.
+	type FaucetEnabled: Get<bool>;
+
+	/// How many blocks one faucet era lasts.
+	type FaucetEra: Get<Self::BlockNumber>;
+
+	/// How many blocks before its unlock a timelocked transfer stops
+	/// being cancellable, so the recipient gets a firm delivery promise.
+	type TimedTransferCancelBuffer: Get<Self::BlockNumber>;
+
+	/// How long an inheritance claim stays challengeable before the heir
+	/// can finalize it; the window in which the owner can veto.
+	type InheritanceChallengeWindow: Get<Self::BlockNumber>;
+
+	/// The minimum number of blocks an account must wait between free
+	/// creations. Expedited (paid) creations bypass the interval.
+	type CreateInterval: Get<Self::BlockNumber>;
+
+	/// The fee burned by `create_expedited` to skip the creation interval.
+	type ExpeditedCreateFee: Get<BalanceOf<Self>>;
+
+	/// The maximum number of kitties minted (created or bred) per block,
+	/// protecting block space and the randomness source from mint floods.
+	type MaxMintsPerBlock: Get<u32>;
+
+	/// The breeding-fee credit a referrer earns per onboarded creator.
+	type ReferralCredit: Get<BalanceOf<Self>>;
+
+	/// The discount on the breed fee when it is prepaid as a breeding
+	/// pass bundle.
+	type BreedingPassDiscount: Get<Percent>;
+
+	/// The largest loan principal as a fraction of the collateral kitty's
+	/// floor valuation.
+	type LoanToValue: Get<Percent>;
+
+	/// How long a liquidation auction runs.
+	type LiquidationAuctionLength: Get<Self::BlockNumber>;
+
+	/// The maximum number of kitties in one bundle listing.
+	type MaxBundleSize: Get<u32>;
+
+	/// Whether fixed-price listings are custodial: the kitty moves into
+	/// the pallet's custody account while listed instead of carrying a
+	/// lock flag, so ownership and listings can never diverge.
+	type CustodialListings: Get<bool>;
+
+	/// The price feed behind reference-priced listings; `NoPriceOracle`
+	/// disables them.
+	type PriceOracle: PriceOracle<BalanceOf<Self>>;
+
+	/// The identity registry gating breeder registration; `NoIdentity`
+	/// disables it.
+	type Identity: IdentityProvider<Self::AccountId>;
+
+	/// The maximum number of items a kitty can wear at once.
+	type MaxEquippedItems: Get<u32>;
+
+	/// The energy ceiling; newborn kitties start here.
+	type MaxEnergy: Get<u32>;
+
+	/// How much energy a kitty loses per block without feeding.
+	type EnergyDecayPerBlock: Get<u32>;
+
+	/// How much balance one point of energy costs when feeding. Must be
+	/// non-zero.
+	type FeedCostPerEnergy: Get<BalanceOf<Self>>;
+
+	/// The energy a parent spends per breeding; a parent below this breeds
+	/// at half the usual success rate.
+	type BreedEnergyCost: Get<u32>;
+
+	/// Whether neglect is fatal. When enabled, a kitty whose energy has sat
+	/// at zero for longer than the grace period is departed: it can no
+	/// longer be transferred or bred and its state may be reaped.
+	type PermaDeathEnabled: Get<bool>;
+
+	/// How long a kitty may sit at zero energy before it departs.
+	type DepartureGracePeriod: Get<Self::BlockNumber>;
+
+	/// The minimum number of blocks a hibernating kitty must sleep, so
+	/// hibernation cannot be toggled to game cooldowns.
+	type MinHibernationPeriod: Get<Self::BlockNumber>;
+
+	/// How long an escrowed sale can be disputed before it finalizes.
+	type EscrowDisputeWindow: Get<Self::BlockNumber>;
+
+	/// The origin allowed to resolve escrow disputes.
+	type ArbiterOrigin: EnsureOrigin<Self::Origin>;
+
+	/// The share of the price due up front in an installment purchase,
+	/// forfeited to the seller if a later installment is missed.
+	type InstallmentDownPayment: Get<Percent>;
+
+	/// How many blocks the buyer has between installments.
+	type InstallmentInterval: Get<Self::BlockNumber>;
+
+	/// The most installments a purchase may be split into.
+	type MaxInstallments: Get<u32>;
+
+	/// The origin allowed to change registry parameters and curated lists:
+	/// breeding seasons, reserved names, items, mint difficulty and the
+	/// blacklist. Root in simple deployments, a council or committee in
+	/// governed ones.
+	type AdminOrigin: EnsureOrigin<Self::Origin>;
+
+	/// The origin allowed to override individual kitties outright, such as
+	/// rewriting DNA. Usually stricter than `AdminOrigin`.
+	type ForceOrigin: EnsureOrigin<Self::Origin>;
+
+	/// How many entries the rarity leaderboard retains.
+	type MaxLeaderboardSize: Get<u32>;
+
+	/// How many kitty ids the per-block market-state sweep examines.
+	type CleanupBudget: Get<u32>;
+
+	/// How many blocks one payment of state rent keeps a kitty current.
+	/// The first period is included in the mint.
+	type RentPeriod: Get<Self::BlockNumber>;
+
+	/// The burned fee that renews a kitty's state rent for one period.
+	type RentPerPeriod: Get<BalanceOf<Self>>;
+
+	/// How long after its rent lapses a kitty is safe from reaping.
+	type RentGracePeriod: Get<Self::BlockNumber>;
+
+	/// The cut of the kitty deposit paid to whoever reaps a rent-lapsed
+	/// kitty. Must not exceed `KittyDeposit`.
+	type ReapFinderFee: Get<BalanceOf<Self>>;
+
+	// This Substrate version has no `BoundedVec` or `MaxEncodedLen`, so
+	// vector-valued storage items are bounded the manual way: every push
+	// is guarded by one of the caps below (or by an existing cap such as
+	// `MaxProvenanceEntries`), keeping all storage items bounded.
+
+	/// The most auctions, sealed auctions, name auctions or escrows that
+	/// may end at any one block.
+	type MaxSameBlockEndings: Get<u32>;
+
+	/// The largest entrant cap a tournament or race may declare.
+	type MaxContestEntrants: Get<u32>;
+
+	/// The most tournaments (or races) that may be pending at once.
+	type MaxActiveContests: Get<u32>;
+
+	/// The most rows the breeding difficulty table may hold.
+	type MaxDifficultyRows: Get<u32>;
+
+	/// How many relayer approvals release a bridged-out kitty. Relayers
+	/// sign the unlock as ordinary extrinsics; the threshold stands in
+	/// for an on-chain multisignature check.
+	type RelayerThreshold: Get<u32>;
+
+	/// How many kitties an account must hold to qualify for the holder
+	/// perks checked by `CheckKittyHolder`. Holding a maximum-rarity
+	/// kitty (the `MaxRarityOwner` achievement) qualifies regardless.
+	type HolderPerkMinKitties: Get<u32>;
+
+	/// The transaction priority bump granted to qualifying holders.
+	type HolderPriorityBump: Get<TransactionPriority>;
+
+	/// The transaction fee discount granted to qualifying holders; the
+	/// runtime's fee-charging adapter applies it via `discounted_fee`.
+	type HolderFeeDiscount: Get<Percent>;
+}
+
+decl_storage! {
+	trait Store for Module<T: Trait> as Kitties {
+		/// All kitties, indexed by kitty id.
+		pub Kitties get(fn kitties): map hasher(blake2_128_concat) T::KittyIndex => Option<Kitty>;
+		/// The total number of kitties in existence. When ids are allocated
+		/// sequentially this doubles as the next free kitty id.
+		pub KittiesCount get(fn kitties_count): T::KittyIndex;
+		/// The owner of each kitty.
+		pub KittyOwners get(fn kitty_owner): map hasher(blake2_128_concat) T::KittyIndex => Option<T::AccountId>;
+		/// The number of kitties each account currently holds.
+		pub OwnedKittiesCount get(fn owned_kitties_count): map hasher(blake2_128_concat) T::AccountId => u32;
+		/// Whether the one-shot owner-index repair migration has run; see
+		/// `migration::IndexRepairMigration`.
+		pub IndexRepaired get(fn index_repaired): bool;
+		/// Frozen per-account kitty counts, keyed by snapshot id and
+		/// holder. Airdrop baselines; written once, never updated.
+		pub Snapshots get(fn snapshots): double_map hasher(twox_64_concat) u32, hasher(blake2_128_concat) T::AccountId => u32;
+		/// The block and holder count of each snapshot.
+		pub SnapshotMeta get(fn snapshot_meta): map hasher(twox_64_concat) u32 => Option<(T::BlockNumber, u32)>;
+		/// The id the next snapshot will use.
+		pub NextSnapshotId get(fn next_snapshot_id): u32;
+		/// How many accounts currently hold at least one kitty.
+		pub UniqueOwners get(fn unique_owners): u32;
+		/// A coarse holder histogram: accounts holding 1, 2–5, 6–20 and
+		/// 21+ kitties. Maintained on every mint, transfer and removal.
+		pub HolderDistribution get(fn holder_distribution): [u32; 4];
+		/// Live kitty counts per generation, kept in step by the mint,
+		/// re-generation and removal paths.
+		pub GenerationCounts get(fn generation_count): map hasher(blake2_128_concat) u32 => u32;
+		/// How many kitties have ever been removed from existence.
+		pub BurnedKitties get(fn burned_kitties): u32;
+		/// The lifetime native-denominated sale volume settled through the
+		/// market.
+		pub TradeVolume get(fn trade_volume): BalanceOf<T>;
+		/// The cumulative native amount of fees destroyed by the
+		/// deflationary burn, for anyone auditing the supply.
+		pub TotalFeesBurned get(fn total_fees_burned): BalanceOf<T>;
+		/// How many fixed-price listings are currently open. All `Listings`
+		/// writes go through `insert_listing`/`take_listing` to keep this
+		/// in step.
+		pub ActiveListings get(fn active_listings): u32;
+		/// The block in which each kitty last bred.
+		pub LastBreedAt get(fn last_breed_at): map hasher(blake2_128_concat) T::KittyIndex => T::BlockNumber;
+		/// Accounts (typically module accounts of other pallets) allowed to
+		/// take kitties as collateral.
+		pub CollateralTakers get(fn is_collateral_taker): map hasher(blake2_128_concat) T::AccountId => bool;
+		/// The transfer lock on a kitty, if any, keyed to the locker: an
+		/// external collateral taker or one of the pallet's own lock
+		/// accounts (loans, bundles, market state).
+		pub KittyLocks get(fn kitty_lock): map hasher(blake2_128_concat) T::KittyIndex => Option<T::AccountId>;
+		/// The fixed-price listing of a kitty, if any.
+		pub Listings get(fn listings): map hasher(blake2_128_concat) T::KittyIndex => Option<Listing<T::AccountId, BalanceOf<T>, AssetIdOf<T>>>;
+		/// The linear bonding curve pricing gen-0 mints: `(base, slope)`.
+		/// A mint costs `base + slope * GenZeroSupply`; both zero (the
+		/// default) makes minting free apart from the deposit.
+		pub BondingCurve get(fn bonding_curve): (BalanceOf<T>, BalanceOf<T>);
+		/// How many generation-zero kitties currently exist. The bonding
+		/// curve's input; bred kitties never count.
+		pub GenZeroSupply get(fn gen_zero_supply): u32;
+		/// How many generation-zero kitties have ever been minted through
+		/// `create`. Never decreases, so burnt kitties do not refund
+		/// allowance under the release schedule.
+		pub GenZeroMinted get(fn gen_zero_minted): u32;
+		/// Gen-0 drops scheduled by the admin: block number to kitty
+		/// count. Executed as an inherent by whoever authors that block.
+		pub GenZeroDrops get(fn gen_zero_drop): map hasher(blake2_128_concat) T::BlockNumber => Option<u32>;
+		/// Dropped gen-0 kitties sitting in the distribution pool,
+		/// claimable first-come-first-served.
+		pub DistributionPool get(fn distribution_pool): Vec<T::KittyIndex>;
+		/// The phenotype hash of each kitty — the blake2-256 of its
+		/// trait-JSON render — submitted unsigned by the off-chain worker
+		/// and verified against the on-chain render before storage.
+		pub PhenotypeHashes get(fn phenotype_hash): map hasher(blake2_128_concat) T::KittyIndex => Option<[u8; 32]>;
+		/// The off-chain worker's suggested floor price for a kitty and
+		/// the block it was computed at. Advisory only; nothing on chain
+		/// enforces it.
+		pub SuggestedPrices get(fn suggested_price): map hasher(blake2_128_concat) T::KittyIndex => Option<(BalanceOf<T>, T::BlockNumber)>;
+		/// Ownership queries this chain has issued to sibling parachains,
+		/// awaiting a response: `(sibling para, kitty, claimed owner)`.
+		pub OwnershipQueries get(fn ownership_query): map hasher(blake2_128_concat) u64 => Option<(u32, T::KittyIndex, T::AccountId)>;
+		/// The id the next outbound ownership query will take.
+		pub NextQueryId get(fn next_query_id): u64;
+		/// Accounts trusted to relay between this chain and the Ethereum
+		/// bridge contract. Managed by `AdminOrigin`.
+		pub Relayers get(fn is_relayer): map hasher(blake2_128_concat) T::AccountId => bool;
+		/// Kitties locked for the bridge: the local owner awaiting return
+		/// and the Ethereum address the wrapped token was minted to. While
+		/// present the kitty is inert locally.
+		pub BridgedKitties get(fn bridged_out): map hasher(blake2_128_concat) T::KittyIndex => Option<(T::AccountId, [u8; 20])>;
+		/// The exit commitment of every kitty that has left for a
+		/// successor chain. Permanent; an exited kitty never returns.
+		pub Exits get(fn exit_commitment): map hasher(blake2_128_concat) T::KittyIndex => Option<ExitCommitment<T::AccountId, T::BlockNumber>>;
+		/// A rolling hash chained over every exit leaf, in order. A
+		/// successor chain tracking this single value can verify any
+		/// claimed exit against the leaves it has seen.
+		pub ExitsRoot get(fn exits_root): [u8; 32];
+		/// How many exit commitments have been folded into the root.
+		pub ExitCount get(fn exit_count): u32;
+		/// Relayers who have approved releasing a bridged kitty. Cleared
+		/// when the threshold is met; bounded by the relayer set.
+		pub UnlockVotes get(fn unlock_votes): map hasher(blake2_128_concat) T::KittyIndex => Vec<T::AccountId>;
+		/// The share issue backing a fractionalized kitty: the asset class
+		/// and its total supply. While present the kitty cannot move.
+		pub FractionShares get(fn fraction_shares): map hasher(blake2_128_concat) T::KittyIndex => Option<(AssetIdOf<T>, u128)>;
+		/// Per-token approvals made through the ERC-721 adapter: one approved
+		/// spender per kitty, cleared whenever the kitty changes hands.
+		pub Erc721Approvals get(fn erc721_approval): map hasher(blake2_128_concat) T::KittyIndex => Option<T::AccountId>;
+		/// Standing offers on a kitty, keyed by kitty and offerer. The offered
+		/// amount is held in reserve on the offerer's account until the offer
+		/// is accepted, cancelled, or — past the optional expiry — swept.
+		pub Offers get(fn offers): double_map hasher(blake2_128_concat) T::KittyIndex, hasher(blake2_128_concat) T::AccountId => Option<(BalanceOf<T>, Option<T::BlockNumber>, Option<AssetIdOf<T>>)>;
+		/// The running auction for a kitty, if any.
+		pub Auctions get(fn auctions): map hasher(blake2_128_concat) T::KittyIndex => Option<Auction<T::AccountId, BalanceOf<T>, T::BlockNumber, AssetIdOf<T>>>;
+		/// Requested and active loans, keyed by the collateral kitty.
+		pub Loans get(fn loans): map hasher(blake2_128_concat) T::KittyIndex => Option<Loan<T::AccountId, BalanceOf<T>, T::BlockNumber>>;
+		/// Live bundle listings by bundle id.
+		pub Bundles get(fn bundles): map hasher(twox_64_concat) u32 => Option<Bundle<T::AccountId, BalanceOf<T>, T::KittyIndex>>;
+		/// The id the next bundle will use.
+		pub NextBundleId get(fn next_bundle_id): u32;
+		/// The bundle each kitty is listed in, if any.
+		pub BundleOf get(fn bundle_of): map hasher(blake2_128_concat) T::KittyIndex => Option<u32>;
+		/// Open kitty-for-kitty swap proposals, keyed by the offered and
+		/// the wanted kitty. The value is the proposer, the sweetener they
+		/// reserved on top of their kitty, and the sweetener they ask of
+		/// the other side.
+		pub SwapProposals get(fn swap_proposal): double_map hasher(blake2_128_concat) T::KittyIndex, hasher(blake2_128_concat) T::KittyIndex => Option<(T::AccountId, BalanceOf<T>, BalanceOf<T>)>;
+		/// Open collection-wide offers by id: the buyer, the price they
+		/// reserved, and the filter a kitty must pass to fill the offer.
+		pub CollectionOffers get(fn collection_offer): map hasher(twox_64_concat) u32 => Option<(T::AccountId, BalanceOf<T>, OfferFilter)>;
+		/// The id the next collection offer will use.
+		pub NextCollectionOfferId get(fn next_collection_offer_id): u32;
+		/// The auctions ending at a given block, keyed by end block.
+		pub AuctionsByEnd get(fn auctions_by_end): map hasher(blake2_128_concat) T::BlockNumber => Vec<T::KittyIndex>;
+		/// Auctions that were due but did not fit under the per-block
+		/// settlement cap; settled first in the next block.
+		pub SettlementOverflow get(fn settlement_overflow): Vec<T::KittyIndex>;
+		/// The block up to which each kitty's state rent is paid. The first
+		/// period comes with the mint; reaping becomes possible once the
+		/// rent and the grace period have both lapsed.
+		pub RentPaidUntil get(fn rent_paid_until): map hasher(blake2_128_concat) T::KittyIndex => T::BlockNumber;
+		/// Tombstones of reaped kitties: the DNA and final owner, kept so
+		/// explorers can still resolve archived ids.
+		pub ArchivedKitties get(fn archived_kitty): map hasher(blake2_128_concat) T::KittyIndex => Option<([u8; 16], T::AccountId)>;
+		/// Where the next market-state sweep resumes; wraps around at the
+		/// end of the id space.
+		pub CleanupCursor get(fn cleanup_cursor): T::KittyIndex;
+		/// The running sealed-bid auction for a kitty, if any.
+		pub SealedAuctions get(fn sealed_auctions): map hasher(blake2_128_concat) T::KittyIndex => Option<SealedAuction<T::AccountId, BalanceOf<T>, T::BlockNumber>>;
+		/// The commitment and, once revealed, the bid amount of every bidder
+		/// in a sealed auction, keyed by kitty and bidder.
+		pub SealedBids get(fn sealed_bids): double_map hasher(blake2_128_concat) T::KittyIndex, hasher(blake2_128_concat) T::AccountId => Option<([u8; 32], Option<BalanceOf<T>>)>;
+		/// The sealed auctions whose reveal phase ends at a given block.
+		pub SealedAuctionsByEnd get(fn sealed_auctions_by_end): map hasher(blake2_128_concat) T::BlockNumber => Vec<T::KittyIndex>;
+		/// The block in which each kitty was minted or bred.
+		pub BornAt get(fn born_at): map hasher(blake2_128_concat) T::KittyIndex => T::BlockNumber;
+		/// Whether a kitty's appearance has already been re-rolled; one
+		/// re-roll per kitty, ever.
+		pub Rerolled get(fn rerolled): map hasher(blake2_128_concat) T::KittyIndex => bool;
+		/// The top kitties by rarity score, sorted descending and bounded by
+		/// `MaxLeaderboardSize`, so front-ends need not scan the registry.
+		pub RarityLeaderboard get(fn rarity_leaderboard): Vec<(T::KittyIndex, u32)>;
+		/// Which kitty carries each DNA; minting re-rolls collisions so this
+		/// is a bijection and rarity assumptions hold.
+		pub DnaIndex get(fn dna_index): map hasher(blake2_128_concat) [u8; 16] => Option<T::KittyIndex>;
+		/// Each kitty's display name, if one has been set.
+		pub Names get(fn names): map hasher(blake2_128_concat) T::KittyIndex => Option<Vec<u8>>;
+		/// Which kitty currently uses each name; names are exclusive.
+		pub NameTaken get(fn name_taken): map hasher(blake2_128_concat) Vec<u8> => Option<T::KittyIndex>;
+		/// The governance-curated premium names that can only be obtained by
+		/// winning a name auction.
+		pub ReservedNames get(fn reserved_names): map hasher(blake2_128_concat) Vec<u8> => bool;
+		/// The running auction for each reserved name.
+		pub NameAuctions get(fn name_auctions): map hasher(blake2_128_concat) Vec<u8> => Option<NameAuction<T::AccountId, BalanceOf<T>, T::BlockNumber>>;
+		/// The name auctions ending at a given block.
+		pub NameAuctionsByEnd get(fn name_auctions_by_end): map hasher(blake2_128_concat) T::BlockNumber => Vec<Vec<u8>>;
+		/// Unredeemed claims on reserved names won at auction.
+		pub NameClaims get(fn name_claims): map hasher(blake2_128_concat) Vec<u8> => Option<T::AccountId>;
+		/// The proof-of-work target: `blake2_256(sender ++ parent_hash ++
+		/// nonce)` must not exceed it. Unset means no work is required even
+		/// with PoW minting enabled.
+		pub MintDifficulty get(fn mint_difficulty): Option<[u8; 32]>;
+		/// The block in which each account last created a kitty.
+		pub LastCreateAt get(fn last_create_at): map hasher(blake2_128_concat) T::AccountId => T::BlockNumber;
+		/// How many kitties have been minted in the current block; reset in
+		/// `on_initialize`.
+		pub MintsThisBlock get(fn mints_this_block): u32;
+		/// Who referred each account, recorded at their first creation.
+		pub ReferredBy get(fn referred_by): map hasher(blake2_128_concat) T::AccountId => Option<T::AccountId>;
+		/// Breeding-fee credit earned from referrals, spent before any
+		/// balance is withdrawn for breeding fees.
+		pub FeeCredits get(fn fee_credits): map hasher(blake2_128_concat) T::AccountId => BalanceOf<T>;
+		/// Prepaid breeding passes per account; each pass covers one
+		/// breeding's fee in full and is spent before fee credits.
+		pub BreedingPasses get(fn breeding_passes): map hasher(blake2_128_concat) T::AccountId => u32;
+		/// Each kitty's generation: zero for minted kitties, one more than
+		/// the elder parent for bred ones.
+		pub Generations get(fn generation): map hasher(blake2_128_concat) T::KittyIndex => u32;
+		/// The kitties of each generation, indexed so per-generation queries
+		/// are a prefix iteration instead of a registry scan.
+		pub KittiesByGeneration get(fn kitties_by_generation): double_map hasher(blake2_128_concat) u32, hasher(blake2_128_concat) T::KittyIndex => ();
+		/// All tournaments that have not finished yet.
+		pub Tournaments get(fn tournaments): map hasher(blake2_128_concat) u32 => Option<Tournament<BalanceOf<T>, T::BlockNumber>>;
+		/// The id the next tournament will get.
+		pub NextTournamentId get(fn next_tournament_id): u32;
+		/// The ids of tournaments awaiting rounds or settlement.
+		pub ActiveTournaments get(fn active_tournaments): Vec<u32>;
+		/// The remaining contenders of each tournament, with the account
+		/// that entered them.
+		pub TournamentEntrants get(fn tournament_entrants): map hasher(blake2_128_concat) u32 => Vec<(T::KittyIndex, T::AccountId)>;
+		/// All editions ever opened, kept for display after they close.
+		pub Editions get(fn editions): map hasher(blake2_128_concat) u32 => Option<Edition<T::BlockNumber>>;
+		/// The id the next edition will get.
+		pub NextEditionId get(fn next_edition_id): u32;
+		/// Each bred kitty's parents, generalized so foreign creatures can
+		/// appear in the pedigree. Empty for plain mints.
+		pub Pedigrees get(fn pedigree): map hasher(blake2_128_concat) T::KittyIndex => Vec<ParentRef<T::KittyIndex, T::ForeignCreatureId>>;
+		/// Each kitty's fusion tier: zero unless forged, one more than the
+		/// higher-tier parent otherwise.
+		pub Tiers get(fn tier): map hasher(blake2_128_concat) T::KittyIndex => u32;
+		/// Kitties bound to their owner for life: never transferable,
+		/// sellable or usable as collateral.
+		pub Soulbound get(fn is_soulbound): map hasher(blake2_128_concat) T::KittyIndex => bool;
+		/// Which edition each kitty was minted in, if any.
+		pub KittyEdition get(fn kitty_edition): map hasher(blake2_128_concat) T::KittyIndex => Option<u32>;
+		/// Council-verified official drops: named id ranges or editions
+		/// marketplaces can trust against force-minted or bridged
+		/// look-alikes.
+		pub VerifiedCollections get(fn verified_collection): map hasher(blake2_128_concat) u32 => Option<(Vec<u8>, VerifiedScope<T::KittyIndex>)>;
+		/// The id the next verified collection will use.
+		pub NextVerifiedCollectionId get(fn next_verified_collection_id): u32;
+		/// Breeder-run catteries by id.
+		pub Catteries get(fn cattery): map hasher(blake2_128_concat) u32 => Option<Cattery<T::AccountId>>;
+		/// The id the next cattery will use.
+		pub NextCatteryId get(fn next_cattery_id): u32;
+		/// The cattery each kitty is assigned to, if any. The key market
+		/// filters index on.
+		pub KittyCattery get(fn kitty_cattery): map hasher(blake2_128_concat) T::KittyIndex => Option<u32>;
+		/// The role each member account holds in each cattery. Granted by
+		/// the founder; finer-grained than a full breeding delegation.
+		pub CatteryRoles get(fn cattery_role): double_map hasher(blake2_128_concat) u32, hasher(blake2_128_concat) T::AccountId => Option<CatteryRole>;
+		/// The buyback schedule as `(base, per_rarity_point)`: burning a
+		/// kitty pays `(base + per_rarity_point * rarity_score) /
+		/// (generation + 1)` from the pool while it lasts. Unset means
+		/// the program is closed.
+		pub BuybackSchedule get(fn buyback_schedule): Option<(BalanceOf<T>, BalanceOf<T>)>;
+		/// All races that have not run yet.
+		pub Races get(fn races): map hasher(blake2_128_concat) u32 => Option<Race<BalanceOf<T>, T::BlockNumber>>;
+		/// The id the next race will get.
+		pub NextRaceId get(fn next_race_id): u32;
+		/// The ids of races awaiting their start block.
+		pub ActiveRaces get(fn active_races): Vec<u32>;
+		/// The runners of each race, with the account that entered them.
+		pub RaceRunners get(fn race_runners): map hasher(blake2_128_concat) u32 => Vec<(T::KittyIndex, T::AccountId)>;
+		/// Each kitty's lifetime racing record.
+		pub RaceRecords get(fn race_record): map hasher(blake2_128_concat) T::KittyIndex => KittyRaceRecord;
+		/// Outstanding two-phase transfer offers, one per kitty.
+		pub PendingTransfers get(fn pending_transfer): map hasher(blake2_128_concat) T::KittyIndex => Option<PendingTransfer<T::AccountId, T::BlockNumber>>;
+		/// Accounts barred from minting, breeding, listing or receiving
+		/// kitties. Managed by the admin origin for compliance needs.
+		pub Blacklist get(fn blacklisted): map hasher(blake2_128_concat) T::AccountId => bool;
+		/// Governance-approved charities that listings may name as
+		/// donation beneficiaries.
+		pub Charities get(fn is_approved_charity): map hasher(blake2_128_concat) T::AccountId => bool;
+		/// Each account's transfer-acceptance preferences.
+		pub Preferences get(fn preferences): map hasher(blake2_128_concat) T::AccountId => AccountPreferences;
+		/// How many bred kittens each account has received.
+		pub BreederScore get(fn breeder_score): map hasher(blake2_128_concat) T::AccountId => u32;
+		/// The block at which each account unlocked each achievement.
+		pub Achievements get(fn achievements): double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) Achievement => Option<T::BlockNumber>;
+		/// The breeding season as `(open_length, period)`: breeding is open
+		/// for the first `open_length` blocks of every `period`-block cycle,
+		/// measured from block zero. Unset means always open.
+		pub BreedingSeason get(fn breeding_season): Option<(T::BlockNumber, T::BlockNumber)>;
+		/// The stat bonuses of each registered equipment item.
+		pub Items get(fn items): map hasher(blake2_128_concat) u32 => Option<KittyStats>;
+		/// The items each kitty currently wears.
+		pub Equipped get(fn equipped): map hasher(blake2_128_concat) T::KittyIndex => Vec<u32>;
+		/// Each kitty's cached stat sheet, refreshed whenever its DNA or
+		/// equipment changes so game subsystems never redo the gene math.
+		pub StatSheets get(fn stat_sheet): map hasher(blake2_128_concat) T::KittyIndex => Option<KittyStats>;
+		/// Each kitty's vitals as of their last update; apply decay on read.
+		pub Vitals get(fn vitals): map hasher(blake2_128_concat) T::KittyIndex => KittyVitals<T::BlockNumber>;
+		/// The block each hibernating kitty went to sleep at. A sleeping
+		/// kitty neither decays nor owes rent, and cannot breed, battle
+		/// or be listed.
+		pub Hibernating get(fn hibernating): map hasher(blake2_128_concat) T::KittyIndex => Option<T::BlockNumber>;
+		/// The total amount ever tipped to each kitty's owners, used for
+		/// popularity rankings.
+		pub LifetimeTips get(fn lifetime_tips): map hasher(blake2_128_concat) T::KittyIndex => BalanceOf<T>;
+		/// The escrowed sale of a kitty, if any.
+		pub Escrows get(fn escrows): map hasher(blake2_128_concat) T::KittyIndex => Option<Escrow<T::AccountId, BalanceOf<T>, T::BlockNumber>>;
+		/// The escrows whose dispute window ends at a given block.
+		pub EscrowsByEnd get(fn escrows_by_end): map hasher(blake2_128_concat) T::BlockNumber => Vec<T::KittyIndex>;
+		/// The hash-timelocked swap a kitty sits in, if any.
+		pub HtlcSwaps get(fn htlc_swap): map hasher(blake2_128_concat) T::KittyIndex => Option<HtlcSwap<T::AccountId, T::BlockNumber>>;
+		/// The scheduled timelocked transfer of a kitty, if any.
+		pub TimedTransfers get(fn timed_transfer): map hasher(blake2_128_concat) T::KittyIndex => Option<TimedTransfer<T::AccountId, T::BlockNumber>>;
+		/// The timelocked transfers unlocking at a given block.
+		pub TimedTransfersByUnlock get(fn timed_transfers_by_unlock): map hasher(blake2_128_concat) T::BlockNumber => Vec<T::KittyIndex>;
+		/// The block each account last minted, bred or moved a kitty in,
+		/// or explicitly heartbeated. The inactivity clock inheritance
+		/// claims run against.
+		pub LastActivity get(fn last_activity): map hasher(blake2_128_concat) T::AccountId => T::BlockNumber;
+		/// Each account's designated heir and the inactivity threshold in
+		/// blocks after which that heir may claim.
+		pub Heirs get(fn heir): map hasher(blake2_128_concat) T::AccountId => Option<(T::AccountId, T::BlockNumber)>;
+		/// The open inheritance claim against a dormant account:
+		/// `(claimant, block the claim can be finalized at)`.
+		pub InheritanceClaims get(fn inheritance_claim): map hasher(blake2_128_concat) T::AccountId => Option<(T::AccountId, T::BlockNumber)>;
+		/// The running installment purchase of a kitty, if any.
+		pub Installments get(fn installments): map hasher(blake2_128_concat) T::KittyIndex => Option<InstallmentPlan<T::AccountId, BalanceOf<T>, T::BlockNumber>>;
+		/// The installment plans with a payment due at a given block.
+		pub InstallmentsByDue get(fn installments_by_due): map hasher(blake2_128_concat) T::BlockNumber => Vec<T::KittyIndex>;
+		/// The registration id of each registered breeder. Registration
+		/// needs a verified identity; the id is stable for the life of
+		/// the registration and echoed in breeding and sale events.
+		pub BreederRegistry get(fn breeder_id): map hasher(blake2_128_concat) T::AccountId => Option<u32>;
+		/// The next breeder registration id.
+		pub NextBreederId get(fn next_breeder_id): u32;
+		/// The faucet era in which each account last claimed its free
+		/// kitty.
+		pub LastFaucetEra get(fn last_faucet_era): map hasher(blake2_128_concat) T::AccountId => Option<u32>;
+		/// A bounded history of each kitty's ownership changes, newest last.
+		pub Provenance get(fn provenance): map hasher(blake2_128_concat) T::KittyIndex => Vec<(T::AccountId, T::BlockNumber, TransferKind)>;
+		/// Usage counters per kitty.
+		pub Counters get(fn counters): map hasher(blake2_128_concat) T::KittyIndex => KittyCounters;
+		/// Breeding rights delegated per kitty, keyed by kitty and delegate.
+		/// The value is the remaining number of uses, the expiry block and
+		/// the owner's agreed share of the breed fee when the delegation
+		/// covers a kitty the fee payer does not own.
+		pub BreedingDelegations get(fn breeding_delegations): double_map hasher(blake2_128_concat) T::KittyIndex, hasher(blake2_128_concat) T::AccountId => Option<(u32, T::BlockNumber, Percent)>;
+		/// Proposed cross-owner breedings, keyed by the two parents.
+		pub BreedingAgreements get(fn breeding_agreements): double_map hasher(blake2_128_concat) T::KittyIndex, hasher(blake2_128_concat) T::KittyIndex => Option<BreedingAgreement<T::AccountId, BalanceOf<T>>>;
+		/// Each minted kitty's birth record: the block, extrinsic index and
+		/// randomness seed its DNA was derived from. Unset for bred, hybrid
+		/// and forged kitties, whose genes come from their parents.
+		pub BirthRecords get(fn birth_record): map hasher(blake2_128_concat) T::KittyIndex => Option<BirthRecord<T::AccountId, T::Hash, T::BlockNumber>>;
+		/// The breeding difficulty table as `(supply_at_least, fee_percent,
+		/// cooldown_percent)` rows sorted by ascending threshold. The row
+		/// with the highest threshold not exceeding the total supply scales
+		/// the base breeding fee and cooldown; an empty table means 100%.
+		pub DifficultyTable get(fn difficulty_table): Vec<(u32, u32, u32)>;
+		/// A governed override of the base breeding fee; the compiled
+		/// `BreedFee` applies while unset.
+		pub BreedFeeOverride get(fn breed_fee_override): Option<BalanceOf<T>>;
+		/// A governed override of the expedited-creation fee; the compiled
+		/// `ExpeditedCreateFee` applies while unset.
+		pub CreateFeeOverride get(fn create_fee_override): Option<BalanceOf<T>>;
+		/// A governed override of the base breeding cooldown; the compiled
+		/// `BreedCooldown` applies while unset.
+		pub BreedCooldownOverride get(fn breed_cooldown_override): Option<T::BlockNumber>;
+		/// The chance that a bred kitten mutates one gene byte.
+		pub MutationRate get(fn mutation_rate): Percent;
+		/// A governed override of the market commission; the compiled
+		/// `MarketFeePercent` applies while unset.
+		pub MarketFeeOverride get(fn market_fee_override): Option<Percent>;
+	}
+	add_extra_genesis {
+		/// Genesis kitties as `(owner, seed)` pairs. The DNA is derived as
+		/// `blake2_128(seed)`, so chain specs stay reproducible and human
+		/// reviewable instead of embedding opaque 16-byte blobs.
+		config(kitties): Vec<(T::AccountId, Vec<u8>)>;
+		build(|config| {
+			for (owner, seed) in config.kitties.iter() {
+				let dna = blake2_128(seed);
+				let kitty_id = <Module<T>>::kitty_id_for(&dna)
+					.expect("genesis kitties must fit within the supply limits; qed");
+				<Module<T>>::insert_kitty(owner, kitty_id, Kitty(dna));
+			}
+		});
+	}
+}
+
+decl_event!(
+	pub enum Event<T> where
+		AccountId = <T as system::Trait>::AccountId,
+		KittyIndex = <T as Trait>::KittyIndex,
+		Balance = BalanceOf<T>,
+		BlockNumber = <T as system::Trait>::BlockNumber,
+		CreatureId = <T as Trait>::ForeignCreatureId,
+		AssetId = AssetIdOf<T>,
+	{
+		/// A kitty was created. \[owner, kitty_id\]
+		Created(AccountId, KittyIndex),
+		/// A kitty was transferred. \[from, to, kitty_id\]
+		Transferred(AccountId, AccountId, KittyIndex),
+		/// A kitty was bred from two parents. The last field is the owner's
+		/// breeder registration id, if registered. \[owner, kitty_id,
+		/// parent_1, parent_2, breeder_id\]
+		Bred(AccountId, KittyIndex, KittyIndex, KittyIndex, Option<u32>),
+		/// An account was registered as a collateral taker. \[taker\]
+		CollateralTakerRegistered(AccountId),
+		/// An account's collateral taker registration was removed. \[taker\]
+		CollateralTakerUnregistered(AccountId),
+		/// A kitty was locked as collateral. \[kitty_id, locker\]
+		CollateralLocked(KittyIndex, AccountId),
+		/// A kitty's collateral lock was released. \[kitty_id, locker\]
+		CollateralReleased(KittyIndex, AccountId),
+		/// A locked kitty was seized for a new owner. \[kitty_id, locker, new_owner\]
+		CollateralSeized(KittyIndex, AccountId, AccountId),
+		/// An auction was started. \[seller, kitty_id, reserve_price, end,
+		/// asset\]
+		AuctionStarted(AccountId, KittyIndex, Balance, BlockNumber, Option<AssetId>),
+		/// A bid was placed on an auction. \[bidder, kitty_id, amount\]
+		BidPlaced(AccountId, KittyIndex, Balance),
+		/// An auction settled with a winner. \[kitty_id, winner, price, fee\]
+		AuctionSettled(KittyIndex, AccountId, Balance, Balance),
+		/// An auction ended without a successful sale. \[kitty_id\]
+		AuctionPassed(KittyIndex),
+		/// A sealed-bid auction was started.
+		/// \[seller, kitty_id, reserve_price, commit_end, reveal_end\]
+		SealedAuctionStarted(AccountId, KittyIndex, Balance, BlockNumber, BlockNumber),
+		/// A hashed bid was committed on a sealed auction. \[bidder, kitty_id\]
+		BidCommitted(AccountId, KittyIndex),
+		/// A committed bid was revealed. \[bidder, kitty_id, amount\]
+		BidRevealed(AccountId, KittyIndex, Balance),
+		/// A sealed auction settled; the winner paid the second price.
+		/// \[kitty_id, winner, price, fee\]
+		SealedAuctionSettled(KittyIndex, AccountId, Balance, Balance),
+		/// A sealed auction ended without an able winner. \[kitty_id\]
+		SealedAuctionPassed(KittyIndex),
+		/// An unrevealed commitment's deposit was slashed. \[bidder, kitty_id, amount\]
+		BidDepositSlashed(AccountId, KittyIndex, Balance),
+		/// Expired or stale market state was swept. \[entries_removed\]
+		MarketStateSwept(u32),
+		/// A kitty changed hands and the market state naming its previous
+		/// owner was cancelled, refunding any reserves. \[kitty_id\]
+		MarketStateInvalidated(KittyIndex),
+		/// A kitty's state rent was renewed. \[payer, kitty_id, paid_until\]
+		RentPaid(AccountId, KittyIndex, BlockNumber),
+		/// A rent-lapsed kitty was archived; the reaper took a finder's fee
+		/// from the deposit. \[reaper, kitty_id, finder_fee\]
+		KittyReaped(AccountId, KittyIndex, Balance),
+		/// A kitty was fractionalized into fungible shares. \[owner,
+		/// kitty_id, total_shares\]
+		Fractionalized(AccountId, KittyIndex, u128),
+		/// A full set of shares was redeemed for the kitty. \[redeemer,
+		/// kitty_id\]
+		Redeemed(AccountId, KittyIndex),
+		/// An account joined the relayer set. \[relayer\]
+		RelayerAdded(AccountId),
+		/// An account left the relayer set. \[relayer\]
+		RelayerRemoved(AccountId),
+		/// A kitty was locked for the bridge; relayers mint the wrapped
+		/// token at the given address. \[owner, kitty_id, eth_address\]
+		BridgedOut(AccountId, KittyIndex, [u8; 20]),
+		/// A relayer approved releasing a bridged kitty. \[relayer,
+		/// kitty_id, approvals\]
+		UnlockApproved(AccountId, KittyIndex, u32),
+		/// A bridged kitty returned to its local owner. \[owner, kitty_id\]
+		BridgedIn(AccountId, KittyIndex),
+		/// A sibling parachain's ownership query was answered; the runtime's
+		/// message handler returns the verdict as a `QueryResponse`.
+		/// \[query_id, kitty_id, claimed_owner, verdict\]
+		OwnershipQueryAnswered(u64, KittyIndex, AccountId, bool),
+		/// An ownership query was issued to a sibling parachain.
+		/// \[query_id, para_id, kitty_id, claimed_owner\]
+		OwnershipQueryIssued(u64, u32, KittyIndex, AccountId),
+		/// A sibling parachain answered one of our ownership queries.
+		/// \[query_id, verdict\]
+		OwnershipQueryResolved(u64, bool),
+		/// A kitty's phenotype hash was recorded. \[kitty_id\]
+		PhenotypeHashRecorded(KittyIndex),
+		/// The off-chain worker suggested a floor price. \[kitty_id, price\]
+		PriceSuggested(KittyIndex, Balance),
+		/// The gen-0 bonding curve was reparameterized. \[base, slope\]
+		BondingCurveSet(Balance, Balance),
+		/// A gen-0 drop was scheduled. \[block, count\]
+		GenZeroDropScheduled(BlockNumber, u32),
+		/// A scheduled gen-0 drop was cancelled. \[block\]
+		GenZeroDropCancelled(BlockNumber),
+		/// A gen-0 drop was minted into the distribution pool. \[block,
+		/// count\]
+		GenZeroDropped(BlockNumber, u32),
+		/// A kitty was claimed from the distribution pool. \[claimer,
+		/// kitty_id\]
+		DropClaimed(AccountId, KittyIndex),
+		/// A kitty was listed at a fixed price. \[seller, kitty_id, price,
+		/// asset, verified\]
+		Listed(AccountId, KittyIndex, Balance, Option<AssetId>, bool),
+		/// A listed kitty was bought. The last field is the seller's breeder
+		/// registration id, if registered. \[seller, buyer, kitty_id, price,
+		/// fee, breeder_id\]
+		Sold(AccountId, AccountId, KittyIndex, Balance, Balance, Option<u32>),
+		/// A listing was cancelled and the kitty delisted. \[seller, kitty_id\]
+		ListingCancelled(AccountId, KittyIndex),
+		/// An auction was cancelled before any bid. \[seller, kitty_id\]
+		AuctionCancelled(AccountId, KittyIndex),
+		/// An offer was made on a kitty. \[offerer, kitty_id, amount, asset\]
+		OfferMade(AccountId, KittyIndex, Balance, Option<AssetId>),
+		/// An offer was accepted by the owner. \[owner, offerer, kitty_id, amount, fee\]
+		OfferAccepted(AccountId, AccountId, KittyIndex, Balance, Balance),
+		/// An offer was cancelled and the reserved funds released. \[offerer, kitty_id\]
+		OfferCancelled(AccountId, KittyIndex),
+		/// A cross-owner breeding was proposed. \[proposer, kitty_a, kitty_b, fee, offspring_recipient\]
+		BreedingProposed(AccountId, KittyIndex, KittyIndex, Balance, AccountId),
+		/// A breeding proposal was cancelled. \[proposer, kitty_a, kitty_b\]
+		BreedingProposalCancelled(AccountId, KittyIndex, KittyIndex),
+		/// Breeding rights were delegated. \[owner, kitty_id, delegate, max_uses, expiry\]
+		BreedingDelegated(AccountId, KittyIndex, AccountId, u32, BlockNumber),
+		/// A breeding delegation was revoked. \[owner, kitty_id, delegate\]
+		BreedingDelegationRevoked(AccountId, KittyIndex, AccountId),
+		/// A sire owner received their agreed share of a breeding fee.
+		/// \[sire_owner, child_id, amount\]
+		BreedFeeSplit(AccountId, KittyIndex, Balance),
+		/// A newborn's DNA was re-rolled. \[owner, kitty_id\]
+		Rerolled(AccountId, KittyIndex),
+		/// An administrator rewrote a kitty's DNA. \[kitty_id, old_dna, new_dna\]
+		DnaForceSet(KittyIndex, [u8; 16], [u8; 16]),
+		/// An account updated its transfer preferences.
+		/// \[who, auto_accept, max_incoming\]
+		PreferencesSet(AccountId, bool, Option<u32>),
+		/// An account's blacklist status changed. \[who, blacklisted\]
+		BlacklistUpdated(AccountId, bool),
+		/// A tournament was opened. \[creator, tournament_id, start, end\]
+		TournamentCreated(AccountId, u32, BlockNumber, BlockNumber),
+		/// A kitty entered a tournament. \[who, tournament_id, kitty_id\]
+		TournamentEntered(AccountId, u32, KittyIndex),
+		/// A tournament finished and the prize pool was paid out.
+		/// \[tournament_id, kitty_id, winner, prize\]
+		TournamentWon(u32, KittyIndex, AccountId, Balance),
+		/// A hybrid kitten was born from a kitty and a foreign creature.
+		/// \[owner, kitten_id, kitty_parent, foreign_parent\]
+		HybridBorn(AccountId, KittyIndex, KittyIndex, CreatureId),
+		/// Two kitties were fused into an upgraded one.
+		/// \[owner, parent_1, parent_2, forged_id, tier\]
+		Fused(AccountId, KittyIndex, KittyIndex, KittyIndex, u32),
+		/// A soulbound kitty was minted. \[owner, kitty_id\]
+		SoulboundMinted(AccountId, KittyIndex),
+		/// A limited edition was opened. \[edition_id, open, close\]
+		EditionOpened(u32, BlockNumber, BlockNumber),
+		/// A kitty was minted from an edition. \[who, edition_id, kitty_id\]
+		EditionMinted(AccountId, u32, KittyIndex),
+		/// A race was opened. \[creator, race_id, start\]
+		RaceCreated(AccountId, u32, BlockNumber),
+		/// A kitty entered a race. \[who, race_id, kitty_id\]
+		RaceEntered(AccountId, u32, KittyIndex),
+		/// A race finished. \[race_id, kitty_id, winner, prize\]
+		RaceFinished(u32, KittyIndex, AccountId, Balance),
+		/// Every transferable kitty moved to a new owner.
+		/// \[from, to, moved_count\]
+		TransferredAll(AccountId, AccountId, u32),
+		/// A two-phase transfer was offered. \[from, to, kitty_id, expires_at\]
+		TransferOffered(AccountId, AccountId, KittyIndex, BlockNumber),
+		/// A pending transfer was claimed. \[from, to, kitty_id\]
+		TransferClaimed(AccountId, AccountId, KittyIndex),
+		/// A pending transfer offer was withdrawn. \[kitty_id\]
+		TransferOfferCancelled(KittyIndex),
+		/// A kitty was named. \[owner, kitty_id, name\]
+		NameSet(AccountId, KittyIndex, Vec<u8>),
+		/// A name was added to the reserved list. \[name\]
+		NameReserved(Vec<u8>),
+		/// An auction for a reserved name started. \[name, reserve_price, end\]
+		NameAuctionStarted(Vec<u8>, Balance, BlockNumber),
+		/// A bid was placed on a name auction. \[bidder, name, amount\]
+		NameBidPlaced(AccountId, Vec<u8>, Balance),
+		/// A name auction settled: the winner holds a claim on the name and
+		/// the proceeds went to the fee beneficiary. \[name, winner, price\]
+		NameAuctionSettled(Vec<u8>, AccountId, Balance),
+		/// A name auction ended without an able winner. \[name\]
+		NameAuctionPassed(Vec<u8>),
+		/// The proof-of-work minting difficulty was changed. \[target\]
+		MintDifficultySet(Option<[u8; 32]>),
+		/// A referrer earned a breeding-fee credit for onboarding a new
+		/// creator. \[referrer, new_creator, credit\]
+		ReferralCredited(AccountId, AccountId, Balance),
+		/// An account bought prepaid breeding passes.
+		/// \[buyer, count, total_price\]
+		BreedingPassesBought(AccountId, u32, Balance),
+		/// A loan was requested against a kitty.
+		/// \[borrower, kitty_id, principal\]
+		LoanRequested(AccountId, KittyIndex, Balance),
+		/// A loan request was cancelled. \[borrower, kitty_id\]
+		LoanRequestCancelled(AccountId, KittyIndex),
+		/// A loan was funded. \[lender, kitty_id\]
+		LoanFunded(AccountId, KittyIndex),
+		/// A loan was repaid in full. \[borrower, kitty_id, amount\]
+		LoanRepaid(AccountId, KittyIndex, Balance),
+		/// Overdue collateral went to liquidation auction. \[kitty_id\]
+		LoanLiquidationStarted(KittyIndex),
+		/// A liquidation settled; the lender received the given amount.
+		/// \[borrower, kitty_id, to_lender\]
+		LoanLiquidated(AccountId, KittyIndex, Balance),
+		/// An ownership snapshot was taken. \[snapshot_id, holders\]
+		SnapshotTaken(u32, u32),
+		/// A bundle of kitties was listed. \[seller, bundle_id, price\]
+		BundleListed(AccountId, u32, Balance),
+		/// A bundle listing was cancelled. \[seller, bundle_id\]
+		BundleCancelled(AccountId, u32),
+		/// A bundle sold atomically. \[seller, buyer, bundle_id, price, fee\]
+		BundleSold(AccountId, AccountId, u32, Balance, Balance),
+		/// A swap was proposed.
+		/// \[proposer, offered, wanted, sweetener, requested_sweetener\]
+		SwapProposed(AccountId, KittyIndex, KittyIndex, Balance, Balance),
+		/// A swap proposal was cancelled. \[proposer, offered, wanted\]
+		SwapCancelled(AccountId, KittyIndex, KittyIndex),
+		/// A swap settled. \[proposer, acceptor, offered, wanted\]
+		SwapAccepted(AccountId, AccountId, KittyIndex, KittyIndex),
+		/// A collection-wide offer was placed. \[buyer, offer_id, price\]
+		CollectionOfferMade(AccountId, u32, Balance),
+		/// A collection-wide offer was cancelled. \[buyer, offer_id\]
+		CollectionOfferCancelled(AccountId, u32),
+		/// A collection-wide offer was filled.
+		/// \[seller, buyer, offer_id, kitty_id, price, fee\]
+		CollectionOfferFilled(AccountId, AccountId, u32, KittyIndex, Balance, Balance),
+		/// An account unlocked an achievement. \[who, achievement\]
+		AchievementUnlocked(AccountId, Achievement),
+		/// The breeding season was changed. \[open_length, period\]
+		BreedingSeasonSet(Option<(BlockNumber, BlockNumber)>),
+		/// The breeding difficulty table was replaced.
+		/// \[(supply_at_least, fee_percent, cooldown_percent) rows\]
+		DifficultyTableSet(Vec<(u32, u32, u32)>),
+		/// An equipment item was registered. \[item_id\]
+		ItemRegistered(u32),
+		/// An item was equipped on a kitty. \[owner, kitty_id, item_id\]
+		ItemEquipped(AccountId, KittyIndex, u32),
+		/// An item was taken off a kitty. \[owner, kitty_id, item_id\]
+		ItemUnequipped(AccountId, KittyIndex, u32),
+		/// A departed kitty was buried and its owner's deposit refunded.
+		/// \[owner, kitty_id\]
+		Departed(AccountId, KittyIndex),
+		/// A kitty was fed. \[feeder, kitty_id, amount, new_energy\]
+		Fed(AccountId, KittyIndex, Balance, u32),
+		/// A kitty's owner was tipped. \[tipper, kitty_id, amount\]
+		Tipped(AccountId, KittyIndex, Balance),
+		/// An escrowed sale was opened. \[seller, buyer, kitty_id, price, release_at\]
+		EscrowOpened(AccountId, AccountId, KittyIndex, Balance, BlockNumber),
+		/// An escrow was disputed by one of the parties. \[who, kitty_id\]
+		EscrowDisputed(AccountId, KittyIndex),
+		/// An escrowed sale settled: the kitty and payment changed hands.
+		/// \[seller, buyer, kitty_id, price, fee\]
+		EscrowSettled(AccountId, AccountId, KittyIndex, Balance, Balance),
+		/// An escrow was unwound and the buyer refunded. \[kitty_id\]
+		EscrowRefunded(KittyIndex),
+		/// An installment purchase began: the down payment is reserved and
+		/// the kitty stays locked with the seller until the schedule
+		/// completes. \[seller, buyer, kitty_id, price, down_payment\]
+		InstallmentPlanStarted(AccountId, AccountId, KittyIndex, Balance, Balance),
+		/// An installment was paid. \[buyer, kitty_id, amount, outstanding\]
+		InstallmentPaid(AccountId, KittyIndex, Balance, Balance),
+		/// The final installment settled and the kitty changed hands.
+		/// \[seller, buyer, kitty_id, price, fee\]
+		InstallmentsCompleted(AccountId, AccountId, KittyIndex, Balance, Balance),
+		/// A missed installment forfeited the down payment to the seller
+		/// and ended the purchase. \[buyer, kitty_id, forfeited\]
+		InstallmentDefaulted(AccountId, KittyIndex, Balance),
+		/// An account registered as a breeder. \[who, breeder_id\]
+		BreederRegistered(AccountId, u32),
+		/// A breeder registration was removed. \[who, breeder_id\]
+		BreederDeregistered(AccountId, u32),
+		/// A kitty went into hibernation. \[owner, kitty_id\]
+		Hibernated(AccountId, KittyIndex),
+		/// A kitty woke from hibernation. \[owner, kitty_id\]
+		Awakened(AccountId, KittyIndex),
+		/// The governed base breeding fee changed; `None` means the
+		/// compiled default. \[fee\]
+		BreedFeeSet(Option<Balance>),
+		/// The governed expedited-creation fee changed. \[fee\]
+		CreateFeeSet(Option<Balance>),
+		/// The governed base breeding cooldown changed. \[cooldown\]
+		BreedCooldownSet(Option<BlockNumber>),
+		/// The breeding mutation chance changed. \[rate\]
+		MutationRateSet(Percent),
+		/// The governed market commission changed. \[fee\]
+		MarketFeeSet(Option<Percent>),
+		/// A kitty exited to a successor chain: its commitment leaf was
+		/// folded into the exits root. \[owner, kitty_id, leaf, new_root\]
+		ExitProofGenerated(AccountId, KittyIndex, [u8; 32], [u8; 32]),
+		/// A faucet kitty was claimed. \[claimer, kitty_id, era\]
+		FaucetKittyClaimed(AccountId, KittyIndex, u32),
+		/// A kitty was locked into a hash-timelocked swap.
+		/// \[owner, recipient, kitty_id, hash, timeout_at\]
+		HtlcLocked(AccountId, AccountId, KittyIndex, [u8; 32], BlockNumber),
+		/// A hash-timelocked kitty was claimed, revealing the preimage.
+		/// \[recipient, kitty_id, preimage\]
+		HtlcClaimed(AccountId, KittyIndex, Vec<u8>),
+		/// An expired hash-timelocked swap was refunded to its owner.
+		/// \[owner, kitty_id\]
+		HtlcRefunded(AccountId, KittyIndex),
+		/// A timelocked transfer was scheduled.
+		/// \[from, to, kitty_id, unlock_block\]
+		TimedTransferScheduled(AccountId, AccountId, KittyIndex, BlockNumber),
+		/// A timelocked transfer was delivered. \[from, to, kitty_id\]
+		TimedTransferDelivered(AccountId, AccountId, KittyIndex),
+		/// A timelocked transfer was cancelled by its sender.
+		/// \[from, kitty_id\]
+		TimedTransferCancelled(AccountId, KittyIndex),
+		/// An heir was designated. \[owner, heir, inactivity_threshold\]
+		HeirDesignated(AccountId, AccountId, BlockNumber),
+		/// An heir designation was cleared. \[owner\]
+		HeirCleared(AccountId),
+		/// An inheritance claim was opened against a dormant account.
+		/// \[heir, dormant, finalize_at\]
+		InheritanceClaimed(AccountId, AccountId, BlockNumber),
+		/// An inheritance claim was vetoed by its owner. \[owner, heir\]
+		InheritanceVetoed(AccountId, AccountId),
+		/// An inheritance claim was finalized. \[heir, dormant, kitties\]
+		InheritanceFinalized(AccountId, AccountId, u32),
+		/// A charity was approved or revoked. \[charity, approved\]
+		CharityUpdated(AccountId, bool),
+		/// A sale routed a donation to an approved charity.
+		/// \[seller, charity, kitty_id, amount\]
+		DonationRouted(AccountId, AccountId, KittyIndex, Balance),
+		/// A collection was marked as a verified official drop.
+		/// \[collection_id, scope\]
+		CollectionVerified(u32, VerifiedScope<KittyIndex>),
+		/// A collection's verification was revoked. \[collection_id\]
+		CollectionRevoked(u32),
+		/// A cattery was founded. \[owner, cattery_id\]
+		CatteryCreated(AccountId, u32),
+		/// A kitty was assigned to a cattery. \[kitty_id, cattery_id\]
+		AssignedToCattery(KittyIndex, u32),
+		/// A kitty left a cattery, by request or because it changed
+		/// hands. \[kitty_id, cattery_id\]
+		RemovedFromCattery(KittyIndex, u32),
+		/// A cattery role was granted or cleared.
+		/// \[cattery_id, member, role\]
+		CatteryRoleSet(u32, AccountId, Option<CatteryRole>),
+		/// The buyback schedule was set or the program closed.
+		/// \[base, per_rarity_point\]
+		BuybackScheduleSet(Option<(Balance, Balance)>),
+		/// The buyback pool was topped up. \[funder, amount\]
+		BuybackFunded(AccountId, Balance),
+		/// A kitty was burned into the buyback pool. \[owner, kitty_id,
+		/// payout\]
+		BuybackRedeemed(AccountId, KittyIndex, Balance),
+	}
+);
+
+decl_error! {
+	pub enum Error for Module<T: Trait> {
+		/// The kitty id space has been exhausted.
+		KittiesCountOverflow,
+		/// The given kitty id does not exist.
+		InvalidKittyId,
+		/// The sender does not own the kitty.
+		NotKittyOwner,
+		/// Breeding requires two different parents.
+		RequireDifferentParent,
+		/// The global kitty supply limit has been reached.
+		MaxKittySupplyReached,
+		/// The account already holds the maximum number of kitties.
+		TooManyKittiesPerAccount,
+		/// One of the parents is still resting from a previous breeding.
+		BreedCooldownActive,
+		/// No collision-free content-addressed id could be found for the DNA.
+		KittyIdCollision,
+		/// The kitty is locked as collateral and cannot be moved by its owner.
+		KittyLocked,
+		/// The kitty has no collateral lock.
+		KittyNotLocked,
+		/// The caller is not the locker holding the collateral lock.
+		NotCollateralLocker,
+		/// The account is not registered as a collateral taker.
+		NotRegisteredCollateralTaker,
+		/// The kitty already has a running auction.
+		KittyAlreadyOnAuction,
+		/// No auction exists for the kitty.
+		AuctionNotFound,
+		/// The auction has already ended.
+		AuctionEnded,
+		/// The auction duration must be at least one block.
+		InvalidAuctionDuration,
+		/// The bid is below the reserve price or the current top bid.
+		BidTooLow,
+		/// The seller cannot bid on their own auction.
+		BidOnOwnAuction,
+		/// The kitty is not listed for sale.
+		NotForSale,
+		/// The owner cannot buy or make an offer on their own kitty.
+		OwnKittyMarketAction,
+		/// An offer by this account already exists for the kitty.
+		OfferAlreadyExists,
+		/// No such offer exists.
+		OfferNotFound,
+		/// An offer expiry must lie in the future.
+		InvalidOfferExpiry,
+		/// A custodially held listing cannot be bought through escrow.
+		ListingInCustody,
+		/// An asset-denominated listing cannot be bought through escrow.
+		EscrowRequiresNative,
+		/// The payer does not hold enough of the payment asset.
+		InsufficientAssetBalance,
+		/// The price oracle has no usable quote right now.
+		PriceUnavailable,
+		/// Reference-priced listings settle in the native currency and
+		/// cannot also name a payment asset.
+		ReferencePricingRequiresNative,
+		/// The offer has expired and can no longer be accepted.
+		OfferExpired,
+		/// An auction with bids cannot be cancelled.
+		AuctionHasBids,
+		/// The listing defines more revenue splits than allowed.
+		TooManySaleSplits,
+		/// The revenue split shares add up to more than 100%.
+		InvalidSaleSplit,
+		/// No breeding agreement exists for this pair of kitties.
+		NoBreedingAgreement,
+		/// The proposer no longer owns the first kitty of the agreement.
+		BreedingAgreementStale,
+		/// A breeding agreement requires parents with different owners.
+		SameOwnerAgreement,
+		/// The sender neither owns the kitty nor holds a valid breeding
+		/// delegation for it.
+		NoBreedingRights,
+		/// A delegation must grant at least one use and cannot go to the owner.
+		InvalidDelegation,
+		/// The kitty is held in an escrowed sale.
+		KittyInEscrow,
+		/// No escrow exists for the kitty.
+		NoEscrow,
+		/// Only the escrow's buyer or seller may do this.
+		NotEscrowParty,
+		/// The escrow is not disputed, so there is nothing to arbitrate.
+		EscrowNotDisputed,
+		/// An installment purchase needs between one and `MaxInstallments`
+		/// payments, each of a positive amount.
+		InvalidInstallmentCount,
+		/// No installment purchase is running for the kitty.
+		NoInstallmentPlan,
+		/// Only the plan's buyer may pay installments.
+		NotInstallmentBuyer,
+		/// Breeder registration needs an identity with a positive
+		/// judgement.
+		NoVerifiedIdentity,
+		/// The account is already a registered breeder.
+		AlreadyRegisteredBreeder,
+		/// The account is not a registered breeder.
+		NotRegisteredBreeder,
+		/// The kitty is hibernating.
+		KittyHibernating,
+		/// The kitty is not hibernating.
+		NotHibernating,
+		/// A hibernating kitty must sleep at least the minimum period.
+		HibernationTooShort,
+		/// The market commission cannot exceed half the sale price.
+		MarketFeeTooHigh,
+		/// The faucet is not enabled on this chain.
+		FaucetClosed,
+		/// The account already claimed its free kitty this era.
+		FaucetAlreadyClaimed,
+		/// The account has created a kitty before, so the feeless first
+		/// creation is spent.
+		NotFirstCreation,
+		/// A hash-timelocked swap cannot name its own sender as the
+		/// recipient.
+		HtlcToSelf,
+		/// The hash-timelocked swap's timeout must be in the future.
+		InvalidHtlcTimeout,
+		/// No hash-timelocked swap exists for this kitty.
+		HtlcNotFound,
+		/// Only the swap's named recipient can claim.
+		NotHtlcRecipient,
+		/// The hash-timelocked swap's timeout has passed; only a refund
+		/// is possible now.
+		HtlcExpired,
+		/// The hash-timelocked swap has not timed out yet.
+		HtlcNotExpired,
+		/// The revealed preimage does not hash to the swap's lock.
+		HtlcPreimageMismatch,
+		/// A timelocked transfer cannot name its own sender as the
+		/// recipient.
+		TimedTransferToSelf,
+		/// The unlock block of a timelocked transfer must be in the
+		/// future.
+		UnlockBlockInPast,
+		/// No timelocked transfer exists for this kitty.
+		TimedTransferNotFound,
+		/// Only the scheduled recipient can claim a timelocked transfer.
+		NotTimedTransferRecipient,
+		/// The cancel buffer before the unlock block has started; the
+		/// delivery promise is now firm.
+		TimedTransferCutoffPassed,
+		/// The timelocked transfer's unlock block has not been reached.
+		TimedTransferStillLocked,
+		/// An account cannot name itself as its heir.
+		HeirIsSelf,
+		/// The inactivity threshold must be at least one block.
+		InvalidInactivityThreshold,
+		/// The account has not designated an heir.
+		NoHeirDesignated,
+		/// Only the designated heir can claim or finalize an inheritance.
+		NotDesignatedHeir,
+		/// The owner has shown activity within the inactivity threshold or
+		/// the challenge window.
+		OwnerStillActive,
+		/// An inheritance claim is already standing against this account.
+		InheritanceClaimPending,
+		/// No inheritance claim is standing against this account.
+		NoInheritanceClaim,
+		/// The challenge window of the inheritance claim is still open.
+		ChallengeWindowOpen,
+		/// The named donation beneficiary is not an approved charity.
+		CharityNotApproved,
+		/// A verified range must run from a lower id to a higher one.
+		InvalidVerifiedRange,
+		/// No verified collection exists under this id.
+		VerifiedCollectionNotFound,
+		/// The call only exists in builds with the `dev` feature.
+		DevOnly,
+		/// No cattery exists under this id.
+		CatteryNotFound,
+		/// Only the cattery's founder may manage its membership.
+		NotCatteryOwner,
+		/// The kitty is already assigned to this cattery.
+		AlreadyInCattery,
+		/// The kitty is not assigned to any cattery.
+		NotInCattery,
+		/// No buyback schedule is set; the program is closed.
+		BuybackClosed,
+		/// The buyback pool cannot cover this kitty's payout.
+		BuybackPoolDry,
+		/// A tip must be a positive amount.
+		ZeroTip,
+		/// The feeding amount buys less than one point of energy.
+		FeedTooSmall,
+		/// An underfed parent's breeding attempt did not take.
+		BreedingFailed,
+		/// The kitty has departed and can no longer be moved or bred.
+		KittyDeparted,
+		/// The kitty is alive, or perma-death is disabled.
+		KittyNotDeparted,
+		/// This block's minting cap has been reached; retry next block.
+		MintRateExceeded,
+		/// The account must wait before creating another kitty for free.
+		CreateRateLimited,
+		/// The supplied nonce does not satisfy the minting difficulty.
+		InvalidPowNonce,
+		/// No unique DNA could be derived within the attempt bound.
+		DuplicateDna,
+		/// The kitty's appearance has already been re-rolled once.
+		AlreadyRerolled,
+		/// The re-roll window after birth has closed.
+		RerollWindowClosed,
+		/// The name exceeds the maximum length.
+		NameTooLong,
+		/// Another kitty already uses this name.
+		NameAlreadyTaken,
+		/// The name is reserved; it must be won at auction first.
+		NameIsReserved,
+		/// No auction is running for this name.
+		NameAuctionNotFound,
+		/// A name auction is already running for this name.
+		NameAuctionExists,
+		/// Accounts cannot refer themselves.
+		SelfReferral,
+		/// Referrals only apply to an account's very first kitty.
+		NotNewAccount,
+		/// Breeding is closed until the next season window opens.
+		BreedingClosed,
+		/// A season must have a non-zero period no shorter than its window.
+		InvalidBreedingSeason,
+		/// No item is registered under this id.
+		UnknownItem,
+		/// The kitty already wears this item.
+		ItemAlreadyEquipped,
+		/// The kitty does not wear this item.
+		ItemNotEquipped,
+		/// The kitty cannot wear any more items.
+		TooManyEquippedItems,
+		/// The recipient does not accept unsolicited transfers.
+		RecipientRefusesTransfers,
+		/// No pending transfer offer exists for this kitty.
+		NoPendingTransfer,
+		/// Only the named recipient may claim a pending transfer.
+		NotTransferRecipient,
+		/// The pending transfer offer has expired.
+		TransferOfferExpired,
+		/// A transfer offer must expire in the future.
+		InvalidTransferExpiry,
+		/// The account is blacklisted from using the kitty registry.
+		Blacklisted,
+		/// The tournament does not exist.
+		TournamentNotFound,
+		/// The tournament already has its maximum number of entrants.
+		TournamentFull,
+		/// Entry has closed because the tournament has started.
+		TournamentClosed,
+		/// The kitty is already entered in this tournament.
+		AlreadyEntered,
+		/// A tournament must start in the future and end after it starts.
+		InvalidTournamentTimes,
+		/// The race does not exist.
+		RaceNotFound,
+		/// The race already has its maximum number of runners.
+		RaceFull,
+		/// Entry has closed because the race has started.
+		RaceClosed,
+		/// The kitty is already entered in this race.
+		AlreadyRacing,
+		/// A race must start in the future.
+		InvalidRaceStart,
+		/// The edition does not exist.
+		EditionNotFound,
+		/// The edition's minting window is not currently open.
+		EditionClosed,
+		/// The edition's supply cap has been reached.
+		EditionSoldOut,
+		/// An edition must close after it opens.
+		InvalidEditionWindow,
+		/// A soulbound kitty can never change hands.
+		SoulboundKitty,
+		/// The foreign creature does not exist.
+		ForeignCreatureNotFound,
+		/// The sender does not own the foreign creature.
+		NotForeignCreatureOwner,
+		/// The recipient's self-imposed incoming cap is already reached.
+		RecipientAtCapacity,
+		/// Difficulty table rows must have strictly ascending thresholds.
+		InvalidDifficultyTable,
+		/// No sealed auction is running for this kitty.
+		SealedAuctionNotFound,
+		/// The commit phase of the sealed auction has closed.
+		CommitPhaseClosed,
+		/// The sealed auction is not currently in its reveal phase.
+		NotInRevealPhase,
+		/// The account has already committed a bid on this sealed auction.
+		BidAlreadyCommitted,
+		/// The account has no bid commitment on this sealed auction.
+		NoBidCommitment,
+		/// The committed bid has already been revealed.
+		BidAlreadyRevealed,
+		/// The revealed amount and salt do not match the commitment.
+		BadBidReveal,
+		/// A rent payment must cover at least one period.
+		ZeroRentPeriods,
+		/// The kitty's rent or grace period has not lapsed yet.
+		RentNotLapsed,
+		/// Too many auctions or escrows already end at this block.
+		TooManyEndingAtBlock,
+		/// The declared entrant cap exceeds the global contest limit.
+		ContestTooLarge,
+		/// Too many tournaments or races are already pending.
+		TooManyActiveContests,
+		/// The kitty is fractionalized; its shares govern it now.
+		KittyFractionalized,
+		/// The kitty has not been fractionalized.
+		NotFractionalized,
+		/// A fractionalization needs at least two shares.
+		TooFewShares,
+		/// Redeeming requires holding every share of the kitty.
+		NotAllShares,
+		/// The kitty is locked for the Ethereum bridge.
+		KittyBridgedOut,
+		/// The kitty is not locked for the bridge.
+		NotBridgedOut,
+		/// The sender is not a registered relayer.
+		NotRelayer,
+		/// The account is already a registered relayer.
+		AlreadyRelayer,
+		/// This relayer has already approved the unlock.
+		AlreadyApproved,
+		/// No ownership query is open under this id.
+		QueryNotFound,
+		/// The submitted hash does not match the on-chain render.
+		BadPhenotypeHash,
+		/// The kitty's phenotype hash is already recorded.
+		HashAlreadyRecorded,
+		/// The suggested price does not match the canonical derivation.
+		BadPriceSuggestion,
+		/// The stored price suggestion is already current.
+		StaleSuggestion,
+		/// Drops can only be scheduled for future blocks.
+		DropInPast,
+		/// The drop count is zero or exceeds the per-block mint cap.
+		BadDropCount,
+		/// No drop of this size is scheduled for the current block.
+		NoScheduledDrop,
+		/// The distribution pool has no kitties to claim.
+		DropPoolEmpty,
+		/// The gen-0 allowance released so far is fully consumed; more
+		/// unlocks with the next tranche, if under the cap.
+		GenZeroAllowanceExhausted,
+		/// A breeding pass bundle must contain at least one pass.
+		ZeroPassCount,
+		/// A loan already exists against this kitty.
+		LoanAlreadyExists,
+		/// No loan exists against this kitty.
+		LoanNotFound,
+		/// The loan has already been funded.
+		LoanAlreadyFunded,
+		/// The loan has not been funded yet.
+		LoanNotFunded,
+		/// Loan terms need a positive principal and duration.
+		InvalidLoanTerms,
+		/// The principal exceeds the loan-to-value fraction of the
+		/// kitty's floor valuation.
+		LoanExceedsValuation,
+		/// Borrowers cannot fund their own loans.
+		CannotFundOwnLoan,
+		/// The loan is not past due yet.
+		LoanNotDue,
+		/// The collateral is already being liquidated.
+		LoanInLiquidation,
+		/// A bundle needs between one and the maximum number of kitties.
+		BadBundleSize,
+		/// The same kitty appears twice in a bundle.
+		DuplicateBundleKitty,
+		/// No bundle exists under this id.
+		BundleNotFound,
+		/// The kitty already has an individual listing.
+		KittyAlreadyListed,
+		/// A swap between these two kitties is already proposed.
+		SwapAlreadyProposed,
+		/// No swap proposal exists for these kitties.
+		SwapNotFound,
+		/// Both kitties in a swap belong to the sender.
+		CannotSwapWithSelf,
+		/// No collection offer exists under this id.
+		CollectionOfferNotFound,
+		/// Only the buyer may cancel their collection offer.
+		NotCollectionOfferBuyer,
+		/// The kitty does not pass the collection offer's filter.
+		FilterMismatch,
+	}
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
+		fn deposit_event() = default;
+
+		// Call weights approximate proof-of-validity cost: every storage
+		// access is priced through `T::DbWeight`, with read/write counts
+		// sized to what the call can touch (owned kitties, revenue splits,
+		// table rows). `Weight` is one-dimensional in this Substrate
+		// version, so proof size is folded into the per-access price; the
+		// counts carry over unchanged if the chain moves to two-dimensional
+		// weights.
+
+		/// The deposit reserved from the owner for every kitty they hold.
+		const KittyDeposit: BalanceOf<T> = T::KittyDeposit::get();
+		/// The fee charged for breeding two kitties.
+		const BreedFee: BalanceOf<T> = T::BreedFee::get();
+		/// The number of blocks a kitty must rest between breedings.
+		const BreedCooldown: T::BlockNumber = T::BreedCooldown::get();
+		/// The maximum number of kitties that can ever exist.
+		const MaxKittySupply: u32 = T::MaxKittySupply::get();
+		/// The maximum number of kitties a single account may hold.
+		const MaxKittiesPerAccount: u32 = T::MaxKittiesPerAccount::get();
+		/// The maximum number of auctions settled per block.
+		const MaxAuctionSettlementsPerBlock: u32 = T::MaxAuctionSettlementsPerBlock::get();
+		/// The commission deducted from every sale.
+		const MarketFeePercent: Percent = T::MarketFeePercent::get();
+		/// The maximum number of provenance entries kept per kitty.
+		const MaxProvenanceEntries: u32 = T::MaxProvenanceEntries::get();
+
+		/// Settle the auctions that end in this block, up to the configured
+		/// per-block cap; the remainder carries over to the next block. Also
+		/// finalize the escrows whose dispute window ends here and reset the
+		/// per-block minting counter.
+		fn on_runtime_upgrade() -> Weight {
+			migration::IndexRepairMigration::<T>::run_once()
+		}
+
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			MintsThisBlock::kill();
+			Self::settle_due_auctions(now)
+				+ Self::settle_due_sealed_auctions(now)
+				+ Self::finalize_due_escrows(now)
+				+ Self::default_due_installments(now)
+				+ Self::deliver_due_timed_transfers(now)
+				+ Self::settle_due_name_auctions(now)
+				+ Self::run_tournaments(now)
+				+ Self::run_races(now)
+				+ Self::sweep_market_state(now)
+		}
+
+		/// Off-chain worker: render the trait JSON of every kitty born in
+		/// this block into the node's local off-chain storage, so operators
+		/// can serve kitty art without a separate rendering service.
+		/// Purely local; nothing is submitted back on-chain.
+		fn offchain_worker(now: T::BlockNumber) {
+			Self::render_new_kitties(now);
+			Self::submit_ocw_findings(now);
+		}
+
+		/// Record a kitty's phenotype hash, submitted unsigned by the
+		/// off-chain worker. Self-validating — the hash must match the
+		/// on-chain render — so no funded OCW key is needed; replay is
+		/// barred because each kitty's hash records exactly once.
+		#[weight = T::DbWeight::get().reads_writes(4, 1) + 10_000]
+		pub fn submit_phenotype_hash(origin, kitty_id: T::KittyIndex, hash: [u8; 32]) -> DispatchResult {
+			ensure_none(origin)?;
+			let render = Self::render_kitty(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(blake2_256(&render) == hash, Error::<T>::BadPhenotypeHash);
+			ensure!(Self::phenotype_hash(kitty_id).is_none(), Error::<T>::HashAlreadyRecorded);
+
+			<PhenotypeHashes<T>>::insert(kitty_id, hash);
+			Self::deposit_event(RawEvent::PhenotypeHashRecorded(kitty_id));
+			Ok(())
+		}
+
+		/// Record the off-chain worker's suggested floor price for a
+		/// kitty. The suggestion is a pure function of on-chain state, so
+		/// the submission is self-validating; re-submissions only pass
+		/// once the derivation's output actually changes.
+		#[weight = T::DbWeight::get().reads_writes(4, 1) + 10_000]
+		pub fn submit_price_suggestion(origin, kitty_id: T::KittyIndex, price: BalanceOf<T>) -> DispatchResult {
+			ensure_none(origin)?;
+			let expected = Self::suggest_price(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(price == expected, Error::<T>::BadPriceSuggestion);
+			if let Some((current, _)) = Self::suggested_price(kitty_id) {
+				ensure!(current != price, Error::<T>::StaleSuggestion);
+			}
+
+			<SuggestedPrices<T>>::insert(kitty_id, (price, <system::Module<T>>::block_number()));
+			Self::deposit_event(RawEvent::PriceSuggested(kitty_id, price));
+			Ok(())
+		}
+
+		/// Set the bonding curve pricing gen-0 mints. `AdminOrigin` only.
+		#[weight = T::DbWeight::get().reads_writes(0, 1) + 10_000]
+		pub fn set_bonding_curve(origin, base: BalanceOf<T>, slope: BalanceOf<T>) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			<BondingCurve<T>>::put((base, slope));
+			Self::deposit_event(RawEvent::BondingCurveSet(base, slope));
+			Ok(())
+		}
+
+		/// Schedule a gen-0 drop of `count` kitties at block `at`, or
+		/// reschedule one already planned there. `AdminOrigin` only. The
+		/// drop executes as an inherent, so block production itself
+		/// enforces the supply schedule.
+		#[weight = T::DbWeight::get().reads_writes(1, 1) + 10_000]
+		pub fn schedule_gen_zero_drop(origin, at: T::BlockNumber, count: u32) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(at > <system::Module<T>>::block_number(), Error::<T>::DropInPast);
+			ensure!(
+				count > 0 && count <= T::MaxMintsPerBlock::get(),
+				Error::<T>::BadDropCount
+			);
+
+			<GenZeroDrops<T>>::insert(at, count);
+			Self::deposit_event(RawEvent::GenZeroDropScheduled(at, count));
+			Ok(())
+		}
+
+		/// Cancel a scheduled gen-0 drop. `AdminOrigin` only.
+		#[weight = T::DbWeight::get().reads_writes(1, 1) + 10_000]
+		pub fn cancel_gen_zero_drop(origin, at: T::BlockNumber) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(Self::gen_zero_drop(at).is_some(), Error::<T>::NoScheduledDrop);
+
+			<GenZeroDrops<T>>::remove(at);
+			Self::deposit_event(RawEvent::GenZeroDropCancelled(at));
+			Ok(())
+		}
+
+		/// Execute the gen-0 drop scheduled for this block, minting into
+		/// the distribution pool. Dispatched only as an inherent by the
+		/// block author; the pool account holds no funds and pays no
+		/// deposits — claimants post the deposit on the way out.
+		#[weight = (T::DbWeight::get().reads_writes(4, 8) + 10_000) * 8]
+		pub fn apply_gen_zero_drop(origin, count: u32) -> DispatchResult {
+			ensure_none(origin)?;
+			let now = <system::Module<T>>::block_number();
+			ensure!(Self::gen_zero_drop(now) == Some(count), Error::<T>::NoScheduledDrop);
+
+			<GenZeroDrops<T>>::remove(now);
+			let pool = Self::pool_account();
+			let mut minted = 0u32;
+			for index in 0..count {
+				// Salting the seed with the index keeps the drop's kitties
+				// from burning through `unique_dna`'s re-roll chain.
+				let base = (Self::random_value(&pool), index).using_encoded(blake2_128);
+				let dna = match Self::unique_dna(base) {
+					Ok(dna) => dna,
+					Err(_) => continue,
+				};
+				let kitty_id = match Self::kitty_id_for(&dna) {
+					Ok(kitty_id) => kitty_id,
+					Err(_) => break,
+				};
+				Self::insert_kitty(&pool, kitty_id, Kitty(dna));
+				Self::note_provenance(kitty_id, &pool, TransferKind::Mint);
+				DistributionPool::<T>::mutate(|ids| ids.push(kitty_id));
+				minted += 1;
+			}
+
+			Self::deposit_event(RawEvent::GenZeroDropped(now, minted));
+			Ok(())
+		}
+
+		/// Claim the oldest kitty in the distribution pool, posting its
+		/// deposit. First come, first served.
+		#[weight = T::DbWeight::get().reads_writes(6, 9) + 10_000]
+		pub fn claim_drop(origin) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let mut pool_ids = Self::distribution_pool();
+			ensure!(!pool_ids.is_empty(), Error::<T>::DropPoolEmpty);
+			Self::ensure_can_hold_one_more(&sender)?;
+
+			T::Currency::reserve(&sender, T::KittyDeposit::get())?;
+			let kitty_id = pool_ids.remove(0);
+			DistributionPool::<T>::put(pool_ids);
+			let pool = Self::pool_account();
+			Self::do_transfer(&pool, &sender, kitty_id);
+			Self::note_provenance(kitty_id, &sender, TransferKind::Transfer);
+
+			Self::deposit_event(RawEvent::DropClaimed(sender, kitty_id));
+			Ok(())
+		}
+
+		/// Request a loan against one of the sender's kitties. The kitty is
+		/// locked as collateral; the principal may not exceed the
+		/// loan-to-value fraction of its floor valuation. The request is
+		/// open until someone funds it or the sender cancels.
+		#[weight = T::DbWeight::get().reads_writes(12, 2) + 10_000]
+		pub fn request_loan(
+			origin,
+			kitty_id: T::KittyIndex,
+			principal: BalanceOf<T>,
+			interest_per_block: BalanceOf<T>,
+			duration: T::BlockNumber,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(Self::kitty_owner(kitty_id) == Some(sender.clone()), Error::<T>::NotKittyOwner);
+			ensure!(Self::loans(kitty_id).is_none(), Error::<T>::LoanAlreadyExists);
+			ensure!(Self::kitty_lock(kitty_id).is_none(), Error::<T>::KittyLocked);
+			ensure!(Self::escrows(kitty_id).is_none(), Error::<T>::KittyInEscrow);
+			ensure!(Self::fraction_shares(kitty_id).is_none(), Error::<T>::KittyFractionalized);
+			ensure!(Self::bridged_out(kitty_id).is_none(), Error::<T>::KittyBridgedOut);
+			ensure!(!Self::is_departed(kitty_id), Error::<T>::KittyDeparted);
+			ensure!(Self::auctions(kitty_id).is_none(), Error::<T>::KittyAlreadyOnAuction);
+			ensure!(Self::sealed_auctions(kitty_id).is_none(), Error::<T>::KittyAlreadyOnAuction);
+			Self::ensure_not_soulbound(kitty_id)?;
+			ensure!(!principal.is_zero() && !duration.is_zero(), Error::<T>::InvalidLoanTerms);
+			let valuation = Self::kitty_valuation(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(
+				principal <= T::LoanToValue::get() * valuation,
+				Error::<T>::LoanExceedsValuation
+			);
+
+			<KittyLocks<T>>::insert(kitty_id, Self::loan_account());
+			<Loans<T>>::insert(kitty_id, Loan {
+				borrower: sender.clone(),
+				lender: None,
+				principal,
+				interest_per_block,
+				duration,
+				funded_at: None,
+				in_liquidation: false,
+			});
+
+			Self::deposit_event(RawEvent::LoanRequested(sender, kitty_id, principal));
+			Ok(())
+		}
+
+		/// Cancel an unfunded loan request, releasing the collateral lock.
+		#[weight = T::DbWeight::get().reads_writes(2, 2) + 10_000]
+		pub fn cancel_loan_request(origin, kitty_id: T::KittyIndex) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let loan = Self::loans(kitty_id).ok_or(Error::<T>::LoanNotFound)?;
+			ensure!(loan.borrower == sender, Error::<T>::NotKittyOwner);
+			ensure!(loan.lender.is_none(), Error::<T>::LoanAlreadyFunded);
+
+			<Loans<T>>::remove(kitty_id);
+			<KittyLocks<T>>::remove(kitty_id);
+			Self::deposit_event(RawEvent::LoanRequestCancelled(sender, kitty_id));
+			Ok(())
+		}
+
+		/// Fund an open loan request, paying the principal to the borrower.
+		/// Interest accrues per block from this point; past the duration the
+		/// collateral can be liquidated.
+		#[weight = T::DbWeight::get().reads_writes(3, 2) + 10_000]
+		pub fn fund_loan(origin, kitty_id: T::KittyIndex) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let mut loan = Self::loans(kitty_id).ok_or(Error::<T>::LoanNotFound)?;
+			ensure!(loan.lender.is_none(), Error::<T>::LoanAlreadyFunded);
+			ensure!(loan.borrower != sender, Error::<T>::CannotFundOwnLoan);
+
+			T::Currency::transfer(
+				&sender,
+				&loan.borrower,
+				loan.principal,
+				ExistenceRequirement::KeepAlive,
+			)?;
+			loan.lender = Some(sender.clone());
+			loan.funded_at = Some(<system::Module<T>>::block_number());
+			<Loans<T>>::insert(kitty_id, loan);
+
+			Self::deposit_event(RawEvent::LoanFunded(sender, kitty_id));
+			Ok(())
+		}
+
+		/// Repay a funded loan in full — the principal plus the per-block
+		/// interest accrued so far — releasing the collateral lock.
+		#[weight = T::DbWeight::get().reads_writes(4, 3) + 10_000]
+		pub fn repay_loan(origin, kitty_id: T::KittyIndex) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let loan = Self::loans(kitty_id).ok_or(Error::<T>::LoanNotFound)?;
+			ensure!(loan.borrower == sender, Error::<T>::NotKittyOwner);
+			ensure!(!loan.in_liquidation, Error::<T>::LoanInLiquidation);
+			let lender = loan.lender.clone().ok_or(Error::<T>::LoanNotFunded)?;
+
+			let debt = Self::loan_debt(&loan, <system::Module<T>>::block_number());
+			T::Currency::transfer(&sender, &lender, debt, ExistenceRequirement::KeepAlive)?;
+			<Loans<T>>::remove(kitty_id);
+			<KittyLocks<T>>::remove(kitty_id);
+
+			Self::deposit_event(RawEvent::LoanRepaid(sender, kitty_id, debt));
+			Ok(())
+		}
+
+		/// Send overdue loan collateral to a liquidation auction. Anyone may
+		/// call this once the loan is past due: the kitty moves to the loan
+		/// pool account and is auctioned with no reserve; settlement repays
+		/// the lender up to the outstanding debt and returns any surplus to
+		/// the borrower. If the auction passes without bids, calling this
+		/// again starts a fresh one.
+		#[weight = T::DbWeight::get().reads_writes(12, 12) + 10_000]
+		pub fn liquidate_loan(origin, kitty_id: T::KittyIndex) -> DispatchResult {
+			let _ = ensure_signed(origin)?;
+			let mut loan = Self::loans(kitty_id).ok_or(Error::<T>::LoanNotFound)?;
+			let funded_at = loan.funded_at.ok_or(Error::<T>::LoanNotFunded)?;
+			let now = <system::Module<T>>::block_number();
+			ensure!(now > funded_at + loan.duration, Error::<T>::LoanNotDue);
+			ensure!(Self::auctions(kitty_id).is_none(), Error::<T>::KittyAlreadyOnAuction);
+			let end = now + T::LiquidationAuctionLength::get();
+			ensure!(
+				(Self::auctions_by_end(end).len() as u32) < T::MaxSameBlockEndings::get(),
+				Error::<T>::TooManyEndingAtBlock
+			);
+
+			let pool = Self::loan_account();
+			if !loan.in_liquidation {
+				let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+				// The pool holds the kitty deposit-free, like the drop pool;
+				// the winner posts a fresh deposit at settlement.
+				T::Currency::unreserve(&owner, T::KittyDeposit::get());
+				<KittyLocks<T>>::remove(kitty_id);
+				Self::do_transfer(&owner, &pool, kitty_id);
+				Self::note_provenance(kitty_id, &pool, TransferKind::Seizure);
+				loan.in_liquidation = true;
+				<Loans<T>>::insert(kitty_id, loan);
+			}
+
+			<Auctions<T>>::insert(kitty_id, Auction {
+				seller: pool,
+				reserve_price: Zero::zero(),
+				end,
+				top_bidder: None,
+				top_bid: Zero::zero(),
+				asset: None,
+			});
+			<KittyLocks<T>>::insert(kitty_id, Self::market_lock_account());
+			<AuctionsByEnd<T>>::mutate(end, |ids| ids.push(kitty_id));
+
+			Self::deposit_event(RawEvent::LoanLiquidationStarted(kitty_id));
+			Ok(())
+		}
+
+		/// Freeze the current per-account kitty counts under a fresh
+		/// snapshot id, so airdrops and rewards can run against a fixed
+		/// historical state. Admin-only: the walk visits every holder, so
+		/// this is an occasional baseline, not a routine call.
+		#[weight = FunctionOf(
+			|_: ()| T::DbWeight::get().reads_writes(2, 2) + 1_000_000,
+			DispatchClass::Operational,
+			Pays::Yes,
+		)]
+		pub fn take_snapshot(origin) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			let id = Self::next_snapshot_id();
+			NextSnapshotId::put(id + 1);
+			let mut holders = 0u32;
+			for (who, count) in <OwnedKittiesCount<T>>::iter() {
+				if count > 0 {
+					<Snapshots<T>>::insert(id, &who, count);
+					holders += 1;
+				}
+			}
+			<SnapshotMeta<T>>::insert(id, (<system::Module<T>>::block_number(), holders));
+
+			Self::deposit_event(RawEvent::SnapshotTaken(id, holders));
+			Ok(())
+		}
+
+		/// List a bundle of the sender's kitties at a single price, with
+		/// optional revenue splits as in `sell`. Every kitty in the bundle
+		/// is locked until the bundle sells or is cancelled.
+		#[weight = T::DbWeight::get().reads_writes(4, 3)
+			+ T::DbWeight::get().reads_writes(8, 2) * T::MaxBundleSize::get() as Weight + 10_000]
+		pub fn list_bundle(
+			origin,
+			kitties: Vec<T::KittyIndex>,
+			price: BalanceOf<T>,
+			splits: Vec<(T::AccountId, Percent)>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_not_blacklisted(&sender)?;
+			ensure!(
+				!kitties.is_empty() && kitties.len() <= T::MaxBundleSize::get() as usize,
+				Error::<T>::BadBundleSize
+			);
+			for (position, kitty_id) in kitties.iter().enumerate() {
+				ensure!(
+					!kitties[..position].contains(kitty_id),
+					Error::<T>::DuplicateBundleKitty
+				);
+				ensure!(
+					Self::kitty_owner(*kitty_id) == Some(sender.clone()),
+					Error::<T>::NotKittyOwner
+				);
+				ensure!(Self::kitty_lock(*kitty_id).is_none(), Error::<T>::KittyLocked);
+				ensure!(Self::escrows(*kitty_id).is_none(), Error::<T>::KittyInEscrow);
+				ensure!(Self::fraction_shares(*kitty_id).is_none(), Error::<T>::KittyFractionalized);
+				ensure!(Self::bridged_out(*kitty_id).is_none(), Error::<T>::KittyBridgedOut);
+				ensure!(!Self::is_departed(*kitty_id), Error::<T>::KittyDeparted);
+				ensure!(Self::listings(*kitty_id).is_none(), Error::<T>::KittyAlreadyListed);
+				ensure!(Self::auctions(*kitty_id).is_none(), Error::<T>::KittyAlreadyOnAuction);
+				ensure!(Self::sealed_auctions(*kitty_id).is_none(), Error::<T>::KittyAlreadyOnAuction);
+				Self::ensure_not_soulbound(*kitty_id)?;
+			}
+			ensure!(
+				splits.len() <= T::MaxSaleSplits::get() as usize,
+				Error::<T>::TooManySaleSplits
+			);
+			let total: u32 = splits.iter().map(|(_, share)| share.deconstruct() as u32).sum();
+			ensure!(total <= 100, Error::<T>::InvalidSaleSplit);
+
+			let bundle_id = Self::next_bundle_id();
+			NextBundleId::put(bundle_id + 1);
+			let lock_holder = Self::bundle_lock_account();
+			for kitty_id in &kitties {
+				<KittyLocks<T>>::insert(*kitty_id, &lock_holder);
+				BundleOf::<T>::insert(*kitty_id, bundle_id);
+			}
+			<Bundles<T>>::insert(bundle_id, Bundle {
+				seller: sender.clone(),
+				price,
+				kitties,
+				splits,
+			});
+
+			Self::deposit_event(RawEvent::BundleListed(sender, bundle_id, price));
+			Ok(())
+		}
+
+		/// Cancel a bundle listed by the sender, unlocking its kitties.
+		#[weight = T::DbWeight::get().reads_writes(2, 1)
+			+ T::DbWeight::get().writes(2) * T::MaxBundleSize::get() as Weight + 10_000]
+		pub fn cancel_bundle(origin, bundle_id: u32) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let bundle = Self::bundles(bundle_id).ok_or(Error::<T>::BundleNotFound)?;
+			ensure!(bundle.seller == sender, Error::<T>::NotKittyOwner);
+
+			for kitty_id in &bundle.kitties {
+				<KittyLocks<T>>::remove(*kitty_id);
+				BundleOf::<T>::remove(*kitty_id);
+			}
+			<Bundles<T>>::remove(bundle_id);
+
+			Self::deposit_event(RawEvent::BundleCancelled(sender, bundle_id));
+			Ok(())
+		}
+
+		/// Buy a bundle at its asking price. Settlement is atomic: the
+		/// payment clears through the usual fee and split logic once, then
+		/// every kitty transfers to the buyer together.
+		#[weight = T::DbWeight::get().reads_writes(6, 4)
+			+ T::DbWeight::get().reads_writes(4, 8) * T::MaxBundleSize::get() as Weight + 10_000]
+		pub fn buy_bundle(origin, bundle_id: u32) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let bundle = Self::bundles(bundle_id).ok_or(Error::<T>::BundleNotFound)?;
+			ensure!(bundle.seller != sender, Error::<T>::OwnKittyMarketAction);
+			Self::ensure_not_blacklisted(&sender)?;
+			let count = bundle.kitties.len() as u32;
+			ensure!(
+				Self::owned_kitties_count(&sender) + count <= T::MaxKittiesPerAccount::get(),
+				Error::<T>::TooManyKittiesPerAccount
+			);
+
+			let deposits = T::KittyDeposit::get().saturating_mul(count.into());
+			T::Currency::reserve(&sender, deposits)?;
+			let fee = match Self::settle_payment(&sender, &bundle.seller, bundle.price, &bundle.splits) {
+				Ok(fee) => fee,
+				Err(e) => {
+					T::Currency::unreserve(&sender, deposits);
+					return Err(e);
+				}
+			};
+			T::Currency::unreserve(&bundle.seller, deposits);
+			for kitty_id in &bundle.kitties {
+				<KittyLocks<T>>::remove(*kitty_id);
+				BundleOf::<T>::remove(*kitty_id);
+				Self::do_transfer(&bundle.seller, &sender, *kitty_id);
+				Self::note_provenance(*kitty_id, &sender, TransferKind::Sale);
+			}
+			<Bundles<T>>::remove(bundle_id);
+
+			Self::deposit_event(RawEvent::BundleSold(
+				bundle.seller, sender, bundle_id, bundle.price, fee,
+			));
+			Ok(())
+		}
+
+		/// Propose swapping the sender's `offered` kitty for someone
+		/// else's `wanted` kitty. `sweetener` is reserved from the sender
+		/// now and handed over at settlement; `requested_sweetener` is
+		/// what the other owner must add when accepting. Either side may
+		/// be zero.
+		#[weight = T::DbWeight::get().reads_writes(12, 2) + 10_000]
+		pub fn propose_swap(
+			origin,
+			offered: T::KittyIndex,
+			wanted: T::KittyIndex,
+			sweetener: BalanceOf<T>,
+			requested_sweetener: BalanceOf<T>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(Self::kitty_owner(offered) == Some(sender.clone()), Error::<T>::NotKittyOwner);
+			let other = Self::kitty_owner(wanted).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(other != sender, Error::<T>::CannotSwapWithSelf);
+			ensure!(Self::swap_proposal(offered, wanted).is_none(), Error::<T>::SwapAlreadyProposed);
+			Self::ensure_swappable(offered)?;
+
+			T::Currency::reserve(&sender, sweetener)?;
+			<SwapProposals<T>>::insert(offered, wanted, (&sender, sweetener, requested_sweetener));
+
+			Self::deposit_event(RawEvent::SwapProposed(
+				sender, offered, wanted, sweetener, requested_sweetener,
+			));
+			Ok(())
+		}
+
+		/// Cancel a swap proposed by the sender, releasing the sweetener.
+		#[weight = T::DbWeight::get().reads_writes(2, 2) + 10_000]
+		pub fn cancel_swap(origin, offered: T::KittyIndex, wanted: T::KittyIndex) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let (proposer, sweetener, _) =
+				Self::swap_proposal(offered, wanted).ok_or(Error::<T>::SwapNotFound)?;
+			ensure!(proposer == sender, Error::<T>::NotKittyOwner);
+
+			T::Currency::unreserve(&sender, sweetener);
+			<SwapProposals<T>>::remove(offered, wanted);
+			Self::deposit_event(RawEvent::SwapCancelled(sender, offered, wanted));
+			Ok(())
+		}
+
+		/// Accept a swap against the sender's kitty. The requested
+		/// sweetener is paid to the proposer, the proposer's reserved
+		/// sweetener is handed over, and the kitties change owners — all
+		/// in one atomic settlement. Deposits stay put: each side ends the
+		/// swap holding the same number of kitties.
+		#[weight = T::DbWeight::get().reads_writes(14, 10) + 10_000]
+		pub fn accept_swap(origin, offered: T::KittyIndex, wanted: T::KittyIndex) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let (proposer, sweetener, requested) =
+				Self::swap_proposal(offered, wanted).ok_or(Error::<T>::SwapNotFound)?;
+			ensure!(Self::kitty_owner(wanted) == Some(sender.clone()), Error::<T>::NotKittyOwner);
+			// The proposal may have gone stale since it was made.
+			ensure!(Self::kitty_owner(offered) == Some(proposer.clone()), Error::<T>::SwapNotFound);
+			Self::ensure_swappable(offered)?;
+			Self::ensure_swappable(wanted)?;
+			Self::ensure_not_blacklisted(&sender)?;
+			Self::ensure_not_blacklisted(&proposer)?;
+
+			// The acceptor's side first: it is the only fallible step.
+			T::Currency::transfer(&sender, &proposer, requested, ExistenceRequirement::KeepAlive)?;
+			let _ = T::Currency::repatriate_reserved(&proposer, &sender, sweetener);
+			<SwapProposals<T>>::remove(offered, wanted);
+			Self::do_transfer(&proposer, &sender, offered);
+			Self::do_transfer(&sender, &proposer, wanted);
+			Self::note_provenance(offered, &sender, TransferKind::Swap);
+			Self::note_provenance(wanted, &proposer, TransferKind::Swap);
+
+			Self::deposit_event(RawEvent::SwapAccepted(proposer, sender, offered, wanted));
+			Ok(())
+		}
+
+		/// Lock a kitty owned by the sender behind `hash` for `timeout`
+		/// blocks, claimable only by `recipient` with the preimage. The
+		/// counterparty on the other chain locks their asset behind the
+		/// same hash; whoever reveals the secret first unlocks both sides.
+		#[weight = T::DbWeight::get().reads_writes(10, 2) + 10_000]
+		pub fn htlc_lock(origin, kitty_id: T::KittyIndex, hash: [u8; 32], timeout: T::BlockNumber, recipient: T::AccountId) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(owner == sender, Error::<T>::NotKittyOwner);
+			ensure!(sender != recipient, Error::<T>::HtlcToSelf);
+			ensure!(Self::kitty_lock(kitty_id).is_none(), Error::<T>::KittyLocked);
+			ensure!(Self::escrows(kitty_id).is_none(), Error::<T>::KittyInEscrow);
+			ensure!(Self::fraction_shares(kitty_id).is_none(), Error::<T>::KittyFractionalized);
+			ensure!(Self::bridged_out(kitty_id).is_none(), Error::<T>::KittyBridgedOut);
+			ensure!(!Self::is_departed(kitty_id), Error::<T>::KittyDeparted);
+			ensure!(Self::hibernating(kitty_id).is_none(), Error::<T>::KittyHibernating);
+			Self::ensure_not_soulbound(kitty_id)?;
+			ensure!(!timeout.is_zero(), Error::<T>::InvalidHtlcTimeout);
+
+			let timeout_at = <system::Module<T>>::block_number() + timeout;
+			<HtlcSwaps<T>>::insert(kitty_id, HtlcSwap {
+				owner: sender.clone(),
+				recipient: recipient.clone(),
+				hash,
+				timeout_at,
+			});
+			<KittyLocks<T>>::insert(kitty_id, Self::htlc_lock_account());
+
+			Self::deposit_event(RawEvent::HtlcLocked(sender, recipient, kitty_id, hash, timeout_at));
+			Ok(())
+		}
+
+		/// Claim a hash-timelocked kitty by revealing the preimage. Only
+		/// the named recipient can claim, and only before the timeout;
+		/// revealing the secret here is what lets the counterparty
+		/// collect the other chain's side.
+		#[weight = T::DbWeight::get().reads_writes(9, 8) + 10_000]
+		pub fn htlc_claim(origin, kitty_id: T::KittyIndex, preimage: Vec<u8>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let swap = Self::htlc_swap(kitty_id).ok_or(Error::<T>::HtlcNotFound)?;
+			ensure!(sender == swap.recipient, Error::<T>::NotHtlcRecipient);
+			ensure!(
+				<system::Module<T>>::block_number() < swap.timeout_at,
+				Error::<T>::HtlcExpired
+			);
+			ensure!(sha2_256(&preimage) == swap.hash, Error::<T>::HtlcPreimageMismatch);
+			Self::ensure_can_hold_one_more(&sender)?;
+			Self::ensure_accepts_transfer(&sender)?;
+
+			T::Currency::reserve(&sender, T::KittyDeposit::get())?;
+			T::Currency::unreserve(&swap.owner, T::KittyDeposit::get());
+			<HtlcSwaps<T>>::remove(kitty_id);
+			<KittyLocks<T>>::remove(kitty_id);
+			Self::do_transfer(&swap.owner, &sender, kitty_id);
+			Self::note_provenance(kitty_id, &sender, TransferKind::Htlc);
+
+			Self::deposit_event(RawEvent::HtlcClaimed(sender, kitty_id, preimage));
+			Ok(())
+		}
+
+		/// Reclaim a hash-timelocked kitty whose timeout has passed
+		/// unclaimed, releasing the lock back to the owner.
+		#[weight = T::DbWeight::get().reads_writes(2, 2) + 10_000]
+		pub fn htlc_refund(origin, kitty_id: T::KittyIndex) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let swap = Self::htlc_swap(kitty_id).ok_or(Error::<T>::HtlcNotFound)?;
+			ensure!(sender == swap.owner, Error::<T>::NotKittyOwner);
+			ensure!(
+				<system::Module<T>>::block_number() >= swap.timeout_at,
+				Error::<T>::HtlcNotExpired
+			);
+
+			<HtlcSwaps<T>>::remove(kitty_id);
+			<KittyLocks<T>>::remove(kitty_id);
+			Self::deposit_event(RawEvent::HtlcRefunded(sender, kitty_id));
+			Ok(())
+		}
+
+		/// Schedule a kitty to be delivered to `to` at `unlock_block`.
+		/// The kitty stays with the sender under a trade lock until then;
+		/// the sender can cancel only until the configured buffer before
+		/// the unlock. Useful for prizes and prescheduled gifts.
+		#[weight = T::DbWeight::get().reads_writes(10, 3) + 10_000]
+		pub fn transfer_at(origin, kitty_id: T::KittyIndex, to: T::AccountId, unlock_block: T::BlockNumber) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(owner == sender, Error::<T>::NotKittyOwner);
+			ensure!(sender != to, Error::<T>::TimedTransferToSelf);
+			ensure!(Self::kitty_lock(kitty_id).is_none(), Error::<T>::KittyLocked);
+			ensure!(Self::escrows(kitty_id).is_none(), Error::<T>::KittyInEscrow);
+			ensure!(Self::fraction_shares(kitty_id).is_none(), Error::<T>::KittyFractionalized);
+			ensure!(Self::bridged_out(kitty_id).is_none(), Error::<T>::KittyBridgedOut);
+			ensure!(!Self::is_departed(kitty_id), Error::<T>::KittyDeparted);
+			Self::ensure_not_soulbound(kitty_id)?;
+			ensure!(
+				unlock_block > <system::Module<T>>::block_number(),
+				Error::<T>::UnlockBlockInPast
+			);
+			ensure!(
+				(Self::timed_transfers_by_unlock(unlock_block).len() as u32)
+					< T::MaxSameBlockEndings::get(),
+				Error::<T>::TooManyEndingAtBlock
+			);
+
+			<TimedTransfers<T>>::insert(kitty_id, TimedTransfer {
+				from: sender.clone(),
+				to: to.clone(),
+				unlock_at: unlock_block,
+			});
+			<TimedTransfersByUnlock<T>>::mutate(unlock_block, |ids| ids.push(kitty_id));
+			<KittyLocks<T>>::insert(kitty_id, Self::timed_lock_account());
+
+			Self::deposit_event(RawEvent::TimedTransferScheduled(sender, to, kitty_id, unlock_block));
+			Ok(())
+		}
+
+		/// Cancel a scheduled transfer made by the sender. Allowed only
+		/// until the cancel buffer before the unlock block, and again
+		/// after the unlock if automatic delivery could not land the
+		/// kitty with the recipient.
+		#[weight = T::DbWeight::get().reads_writes(2, 3) + 10_000]
+		pub fn cancel_timed_transfer(origin, kitty_id: T::KittyIndex) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let scheduled = Self::timed_transfer(kitty_id).ok_or(Error::<T>::TimedTransferNotFound)?;
+			ensure!(scheduled.from == sender, Error::<T>::NotKittyOwner);
+			let now = <system::Module<T>>::block_number();
+			let cutoff = scheduled.unlock_at.saturating_sub(T::TimedTransferCancelBuffer::get());
+			ensure!(
+				now < cutoff || now >= scheduled.unlock_at,
+				Error::<T>::TimedTransferCutoffPassed
+			);
+
+			<TimedTransfers<T>>::remove(kitty_id);
+			<TimedTransfersByUnlock<T>>::mutate(scheduled.unlock_at, |ids| {
+				ids.retain(|id| *id != kitty_id)
+			});
+			<KittyLocks<T>>::remove(kitty_id);
+			Self::deposit_event(RawEvent::TimedTransferCancelled(sender, kitty_id));
+			Ok(())
+		}
+
+		/// Claim a scheduled transfer whose unlock block has passed but
+		/// whose automatic delivery did not land — typically because the
+		/// recipient could not post the deposit at the time.
+		#[weight = T::DbWeight::get().reads_writes(8, 8) + 10_000]
+		pub fn claim_timed_transfer(origin, kitty_id: T::KittyIndex) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let scheduled = Self::timed_transfer(kitty_id).ok_or(Error::<T>::TimedTransferNotFound)?;
+			ensure!(scheduled.to == sender, Error::<T>::NotTimedTransferRecipient);
+			ensure!(
+				<system::Module<T>>::block_number() >= scheduled.unlock_at,
+				Error::<T>::TimedTransferStillLocked
+			);
+
+			Self::deliver_timed_transfer(kitty_id, scheduled)
+		}
+
+		/// Place an open offer for any kitty passing `filter` at `price`.
+		/// The price is reserved until the offer fills or is cancelled;
+		/// the filter is validated against the kitty's stored attributes
+		/// at fill time, never trusted from the filler.
+		#[weight = T::DbWeight::get().reads_writes(3, 2) + 10_000]
+		pub fn make_collection_offer(
+			origin,
+			price: BalanceOf<T>,
+			filter: OfferFilter,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_not_blacklisted(&sender)?;
+			ensure!(!price.is_zero(), Error::<T>::BidTooLow);
+
+			T::Currency::reserve(&sender, price)?;
+			let offer_id = Self::next_collection_offer_id();
+			NextCollectionOfferId::put(offer_id + 1);
+			<CollectionOffers<T>>::insert(offer_id, (&sender, price, filter));
+
+			Self::deposit_event(RawEvent::CollectionOfferMade(sender, offer_id, price));
+			Ok(())
+		}
+
+		/// Cancel a collection offer placed by the sender, releasing the
+		/// reserved price.
+		#[weight = T::DbWeight::get().reads_writes(2, 2) + 10_000]
+		pub fn cancel_collection_offer(origin, offer_id: u32) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let (buyer, price, _) =
+				Self::collection_offer(offer_id).ok_or(Error::<T>::CollectionOfferNotFound)?;
+			ensure!(buyer == sender, Error::<T>::NotCollectionOfferBuyer);
+
+			T::Currency::unreserve(&sender, price);
+			<CollectionOffers<T>>::remove(offer_id);
+			Self::deposit_event(RawEvent::CollectionOfferCancelled(sender, offer_id));
+			Ok(())
+		}
+
+		/// Fill a collection offer with one of the sender's kitties. The
+		/// pallet checks the kitty against the offer's filter and settles
+		/// at the offered price through the usual fee logic.
+		#[weight = T::DbWeight::get().reads_writes(14, 10) + 10_000]
+		pub fn fill_collection_offer(origin, offer_id: u32, kitty_id: T::KittyIndex) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(Self::kitty_owner(kitty_id) == Some(sender.clone()), Error::<T>::NotKittyOwner);
+			let (buyer, price, filter) =
+				Self::collection_offer(offer_id).ok_or(Error::<T>::CollectionOfferNotFound)?;
+			ensure!(buyer != sender, Error::<T>::OwnKittyMarketAction);
+			Self::ensure_swappable(kitty_id)?;
+			ensure!(Self::offer_filter_matches(kitty_id, &filter), Error::<T>::FilterMismatch);
+			Self::ensure_can_hold_one_more(&buyer)?;
+
+			// Release the reserved price, then take deposit and payment
+			// from the now-free funds, rolling back if either step fails.
+			T::Currency::unreserve(&buyer, price);
+			if let Err(e) = T::Currency::reserve(&buyer, T::KittyDeposit::get()) {
+				let _ = T::Currency::reserve(&buyer, price);
+				return Err(e.into());
+			}
+			let fee = match Self::settle_payment(&buyer, &sender, price, &[]) {
+				Ok(fee) => fee,
+				Err(e) => {
+					T::Currency::unreserve(&buyer, T::KittyDeposit::get());
+					let _ = T::Currency::reserve(&buyer, price);
+					return Err(e);
+				}
+			};
+			T::Currency::unreserve(&sender, T::KittyDeposit::get());
+			<CollectionOffers<T>>::remove(offer_id);
+			Self::do_transfer(&sender, &buyer, kitty_id);
+			Self::note_provenance(kitty_id, &buyer, TransferKind::Offer);
+
+			Self::deposit_event(RawEvent::CollectionOfferFilled(
+				sender, buyer, offer_id, kitty_id, price, fee,
+			));
+			Ok(())
+		}
+
+		/// Create a new kitty with random DNA, reserving the kitty deposit.
+		/// Free creations are rate limited per account and, when PoW
+		/// minting is enabled, must carry a nonce satisfying the current
+		/// difficulty; see `create_expedited` for the paid bypass.
+		#[weight = T::DbWeight::get().reads_writes(10, 14) + 10_000]
+		pub fn create(origin, pow_nonce: u64) -> DispatchResultWithPostInfo {
+			let sender = Self::ensure_minter(origin)?;
+			Self::ensure_pow_nonce(&sender, pow_nonce)?;
+			Self::ensure_create_interval(&sender)?;
+			let free_mint = Self::mint_price().is_zero();
+			Self::do_create(&sender)?;
+			if free_mint {
+				// Refund the bonding-curve settlement that never ran.
+				return Ok(Some(T::DbWeight::get().reads_writes(8, 12) + 10_000).into());
+			}
+			Ok(None.into())
+		}
+
+		/// Create an account's very first kitty with the transaction fee
+		/// waived, so onboarding needs no spendable balance beyond the
+		/// deposit. One per account, ever; the proof-of-work nonce (when
+		/// enabled) stays as the sybil cost, and every later mint pays
+		/// normally through `create`.
+		#[weight = FunctionOf(
+			|_: (&u64,)| T::DbWeight::get().reads_writes(11, 14) + 10_000,
+			DispatchClass::Normal,
+			Pays::No,
+		)]
+		pub fn create_first(origin, pow_nonce: u64) -> DispatchResult {
+			let sender = Self::ensure_minter(origin)?;
+			ensure!(
+				!<LastCreateAt<T>>::contains_key(&sender),
+				Error::<T>::NotFirstCreation
+			);
+			Self::ensure_pow_nonce(&sender, pow_nonce)?;
+			Self::do_create(&sender)?;
+			Ok(())
+		}
+
+		/// Clone a kitty's DNA `count` times into the sender's account,
+		/// for load testing and demos. Deposits are still reserved so the
+		/// accounting stays honest. Only compiled under the `dev`
+		/// feature; production builds reject the call outright, since
+		/// `decl_module` cannot leave a dispatchable out conditionally.
+		#[weight = FunctionOf(
+			|(_, count): (&T::KittyIndex, &u32)|
+				(T::DbWeight::get().reads_writes(8, 12) + 10_000)
+					.saturating_mul(*count as Weight + 1),
+			DispatchClass::Normal,
+			Pays::Yes,
+		)]
+		pub fn dev_clone(origin, kitty_id: T::KittyIndex, count: u32) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::do_dev_clone(&sender, kitty_id, count)
+		}
+
+		/// Wipe a kitty's breeding cooldown, its owner's creation
+		/// interval, and refill its energy, for demo environments. Only
+		/// compiled under the `dev` feature, like `dev_clone`.
+		#[weight = T::DbWeight::get().reads_writes(2, 3) + 10_000]
+		pub fn dev_skip_cooldowns(origin, kitty_id: T::KittyIndex) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::do_dev_skip_cooldowns(&sender, kitty_id)
+		}
+
+		/// Set or clear the proof-of-work minting difficulty. Requires the
+		/// admin origin.
+		#[weight = T::DbWeight::get().reads_writes(0, 1) + 10_000]
+		pub fn set_mint_difficulty(origin, target: Option<[u8; 32]>) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			match target {
+				Some(target) => MintDifficulty::put(target),
+				None => MintDifficulty::kill(),
+			}
+			Self::deposit_event(RawEvent::MintDifficultySet(target));
+			Ok(())
+		}
+
+		/// Create a new kitty immediately, burning a fee instead of waiting
+		/// out the per-account creation interval.
+		#[weight = T::DbWeight::get().reads_writes(9, 15) + 10_000]
+		pub fn create_expedited(origin) -> DispatchResult {
+			let sender = Self::ensure_minter(origin)?;
+			let fee = Self::current_create_fee();
+			let _ = T::Currency::withdraw(
+				&sender,
+				fee,
+				WithdrawReason::Fee.into(),
+				ExistenceRequirement::KeepAlive,
+			)?;
+			if let Err(e) = Self::do_create(&sender) {
+				let _ = T::Currency::deposit_into_existing(&sender, fee);
+				return Err(e);
+			}
+			Ok(())
+		}
+
+		/// Claim this era's free faucet kitty. Only open while
+		/// `FaucetEnabled`; the transaction fee is waived and the kitty
+		/// deposit is minted into the claimer's account, so a brand-new
+		/// account needs no tokens at all.
+		#[weight = FunctionOf(
+			|_: ()| T::DbWeight::get().reads_writes(10, 16) + 10_000,
+			DispatchClass::Normal,
+			Pays::No,
+		)]
+		pub fn claim_faucet_kitty(origin) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(T::FaucetEnabled::get(), Error::<T>::FaucetClosed);
+			Self::ensure_not_blacklisted(&sender)?;
+			let era: u32 =
+				(<system::Module<T>>::block_number() / T::FaucetEra::get()).saturated_into();
+			ensure!(Self::last_faucet_era(&sender) != Some(era), Error::<T>::FaucetAlreadyClaimed);
+
+			// Mint the deposit the claimer cannot supply; claw it back if
+			// the creation itself fails.
+			T::Currency::deposit_creating(&sender, T::KittyDeposit::get());
+			let kitty_id = match Self::do_create(&sender) {
+				Ok(kitty_id) => kitty_id,
+				Err(e) => {
+					let _ = T::Currency::slash(&sender, T::KittyDeposit::get());
+					return Err(e);
+				}
+			};
+			<LastFaucetEra<T>>::insert(&sender, era);
+
+			Self::deposit_event(RawEvent::FaucetKittyClaimed(sender, kitty_id, era));
+			Ok(())
+		}
+
+		/// Create a first kitty, crediting `referrer` with a breeding-fee
+		/// credit for the onboarding. Only brand-new accounts — no kitties
+		/// and no prior referral — qualify, and self-referral is rejected.
+		/// The proof-of-work nonce (when enabled) is checked exactly as in
+		/// `create_first`, so referrals carry the same sybil cost.
+		#[weight = T::DbWeight::get().reads_writes(11, 16) + 10_000]
+		pub fn create_with_referral(origin, referrer: T::AccountId, pow_nonce: u64) -> DispatchResult {
+			let sender = Self::ensure_minter(origin)?;
+			ensure!(referrer != sender, Error::<T>::SelfReferral);
+			ensure!(
+				Self::owned_kitties_count(&sender) == 0
+					&& Self::referred_by(&sender).is_none(),
+				Error::<T>::NotNewAccount
+			);
+			Self::ensure_pow_nonce(&sender, pow_nonce)?;
+
+			Self::ensure_create_interval(&sender)?;
+			Self::do_create(&sender)?;
+			let credit = T::ReferralCredit::get();
+			<ReferredBy<T>>::insert(&sender, &referrer);
+			<FeeCredits<T>>::mutate(&referrer, |total| *total = total.saturating_add(credit));
+
+			Self::deposit_event(RawEvent::ReferralCredited(referrer, sender, credit));
+			Ok(())
+		}
+
+		/// Buy `count` prepaid breeding passes at a discount on the current
+		/// breed fee. Each pass covers one breeding's fee in full and is
+		/// spent before referral credits; the purchase price is burned just
+		/// like per-breed fees.
+		#[weight = T::DbWeight::get().reads_writes(2, 1) + 10_000]
+		pub fn buy_breeding_pass(origin, count: u32) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(count > 0, Error::<T>::ZeroPassCount);
+
+			let fee = Self::current_breed_fee();
+			let per_pass = fee - T::BreedingPassDiscount::get() * fee;
+			let price = per_pass.saturating_mul(count.into());
+			if !price.is_zero() {
+				let _ = T::Currency::withdraw(
+					&sender,
+					price,
+					WithdrawReason::Fee.into(),
+					ExistenceRequirement::KeepAlive,
+				)?;
+			}
+			<BreedingPasses<T>>::mutate(&sender, |passes| *passes = passes.saturating_add(count));
+
+			Self::deposit_event(RawEvent::BreedingPassesBought(sender, count, price));
+			Ok(())
+		}
+
+		/// Transfer a kitty to another account. The deposit moves with the
+		/// kitty: it is reserved from the recipient and released to the sender.
+		#[weight = T::DbWeight::get().reads_writes(9, 8) + 10_000]
+		pub fn transfer(origin, to: T::AccountId, kitty_id: T::KittyIndex) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(owner == sender, Error::<T>::NotKittyOwner);
+			ensure!(Self::fraction_shares(kitty_id).is_none(), Error::<T>::KittyFractionalized);
+			ensure!(Self::bridged_out(kitty_id).is_none(), Error::<T>::KittyBridgedOut);
+			ensure!(Self::kitty_lock(kitty_id).is_none(), Error::<T>::KittyLocked);
+			ensure!(Self::escrows(kitty_id).is_none(), Error::<T>::KittyInEscrow);
+			ensure!(!Self::is_departed(kitty_id), Error::<T>::KittyDeparted);
+			Self::ensure_not_soulbound(kitty_id)?;
+			Self::ensure_can_hold_one_more(&to)?;
+			Self::ensure_accepts_transfer(&to)?;
+
+			T::Currency::reserve(&to, T::KittyDeposit::get())?;
+			T::Currency::unreserve(&sender, T::KittyDeposit::get());
+			Self::do_transfer(&sender, &to, kitty_id);
+			Self::note_provenance(kitty_id, &to, TransferKind::Transfer);
+
+			Self::deposit_event(RawEvent::Transferred(sender, to, kitty_id));
+			Ok(())
+		}
+
+		/// Open a knockout tournament. Anyone may create one; the entry fee
+		/// funds the prize pool. Entry closes at `start` and rounds resolve
+		/// one per block from there.
+		#[weight = T::DbWeight::get().reads_writes(2, 3) + 10_000]
+		pub fn create_tournament(origin, entry_fee: BalanceOf<T>, max_entrants: u32, start: T::BlockNumber, end: T::BlockNumber) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let now = <system::Module<T>>::block_number();
+			ensure!(start > now && end > start, Error::<T>::InvalidTournamentTimes);
+			ensure!(
+				max_entrants <= T::MaxContestEntrants::get(),
+				Error::<T>::ContestTooLarge
+			);
+			ensure!(
+				(ActiveTournaments::get().len() as u32) < T::MaxActiveContests::get(),
+				Error::<T>::TooManyActiveContests
+			);
+
+			let tournament_id = NextTournamentId::get();
+			<Tournaments<T>>::insert(tournament_id, Tournament {
+				entry_fee,
+				max_entrants,
+				start,
+				end,
+				prize_pool: Zero::zero(),
+			});
+			ActiveTournaments::mutate(|ids| ids.push(tournament_id));
+			NextTournamentId::put(tournament_id + 1);
+
+			Self::deposit_event(RawEvent::TournamentCreated(sender, tournament_id, start, end));
+			Ok(())
+		}
+
+		/// Enter a kitty the sender owns into a tournament, paying the
+		/// entry fee into the prize pool.
+		#[weight = T::DbWeight::get().reads_writes(5, 4) + 10_000]
+		pub fn enter(origin, tournament_id: u32, kitty_id: T::KittyIndex) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let mut tournament =
+				Self::tournaments(tournament_id).ok_or(Error::<T>::TournamentNotFound)?;
+			ensure!(Self::kitty_owner(kitty_id) == Some(sender.clone()), Error::<T>::NotKittyOwner);
+			ensure!(!Self::is_departed(kitty_id), Error::<T>::KittyDeparted);
+			ensure!(Self::hibernating(kitty_id).is_none(), Error::<T>::KittyHibernating);
+			ensure!(
+				<system::Module<T>>::block_number() < tournament.start,
+				Error::<T>::TournamentClosed
+			);
+			let mut entrants = Self::tournament_entrants(tournament_id);
+			ensure!(
+				(entrants.len() as u32) < tournament.max_entrants,
+				Error::<T>::TournamentFull
+			);
+			ensure!(
+				entrants.iter().all(|(entered, _)| *entered != kitty_id),
+				Error::<T>::AlreadyEntered
+			);
+
+			let _ = T::Currency::withdraw(
+				&sender,
+				tournament.entry_fee,
+				WithdrawReason::Fee.into(),
+				ExistenceRequirement::KeepAlive,
+			)?;
+			tournament.prize_pool = tournament.prize_pool.saturating_add(tournament.entry_fee);
+			entrants.push((kitty_id, sender.clone()));
+			<Tournaments<T>>::insert(tournament_id, tournament);
+			<TournamentEntrants<T>>::insert(tournament_id, entrants);
+			<Counters<T>>::mutate(kitty_id, |counters| {
+				counters.battles = counters.battles.saturating_add(1)
+			});
+
+			Self::deposit_event(RawEvent::TournamentEntered(sender, tournament_id, kitty_id));
+			Ok(())
+		}
+
+		/// Breed a kitty with a creature from the registered foreign
+		/// registry, producing a hybrid kitten in this pallet. The sender
+		/// must own both parents and pays the usual breeding fee and
+		/// deposit; the foreign parent is marked in the pedigree and the
+		/// provenance log.
+		#[weight = T::DbWeight::get().reads_writes(14, 18) + 10_000]
+		pub fn crossbreed(origin, kitty_id: T::KittyIndex, creature_id: T::ForeignCreatureId) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let kitty = Self::kitties(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(Self::kitty_owner(kitty_id) == Some(sender.clone()), Error::<T>::NotKittyOwner);
+			ensure!(!Self::is_departed(kitty_id), Error::<T>::KittyDeparted);
+			let foreign_owner = T::ForeignCreature::owner_of(&creature_id)
+				.ok_or(Error::<T>::ForeignCreatureNotFound)?;
+			ensure!(foreign_owner == sender, Error::<T>::NotForeignCreatureOwner);
+			let foreign_dna = T::ForeignCreature::dna_of(&creature_id)
+				.ok_or(Error::<T>::ForeignCreatureNotFound)?;
+			let now = <system::Module<T>>::block_number();
+			ensure!(
+				now >= Self::last_breed_at(kitty_id) + Self::current_breed_cooldown(),
+				Error::<T>::BreedCooldownActive
+			);
+			ensure!(Self::next_breeding_window(now) == now, Error::<T>::BreedingClosed);
+
+			let selector = Self::random_value(&sender);
+			let dna = Self::unique_dna(Self::combine_dna(&kitty.0, &foreign_dna, selector))?;
+			let new_kitty_id = Self::kitty_id_for(&dna)?;
+			Self::ensure_can_hold_one_more(&sender)?;
+
+			T::Currency::reserve(&sender, T::KittyDeposit::get())?;
+			if let Err(e) = T::Currency::withdraw(
+				&sender,
+				Self::current_breed_fee(),
+				WithdrawReason::Fee.into(),
+				ExistenceRequirement::KeepAlive,
+			) {
+				T::Currency::unreserve(&sender, T::KittyDeposit::get());
+				return Err(e);
+			}
+
+			Self::insert_kitty(&sender, new_kitty_id, Kitty(dna));
+			<LastBreedAt<T>>::insert(kitty_id, now);
+			<Counters<T>>::mutate(kitty_id, |counters| {
+				counters.breedings = counters.breedings.saturating_add(1)
+			});
+			Self::set_generation(new_kitty_id, Self::generation(kitty_id) + 1);
+			<Pedigrees<T>>::insert(new_kitty_id, vec![
+				ParentRef::Kitty(kitty_id),
+				ParentRef::Foreign(
+					<T::ForeignCreature as ForeignCreature<_, _>>::SPECIES,
+					creature_id.clone(),
+				),
+			]);
+			Self::note_provenance(new_kitty_id, &sender, TransferKind::Hybrid);
+
+			Self::deposit_event(RawEvent::HybridBorn(sender, new_kitty_id, kitty_id, creature_id));
+			Ok(())
+		}
+
+		/// Burn two kitties the sender owns and forge one upgraded kitty in
+		/// their place. The forged DNA takes the higher byte of each parent
+		/// pair, its tier is one above the higher-tier parent, and the
+		/// fusion fee is burned. Both parents' deposits are refunded and
+		/// one is re-reserved for the newborn, so supply shrinks by one.
+		#[weight = T::DbWeight::get().reads_writes(18, 48) + 10_000]
+		pub fn fuse(origin, kitty_id_1: T::KittyIndex, kitty_id_2: T::KittyIndex) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(kitty_id_1 != kitty_id_2, Error::<T>::RequireDifferentParent);
+			Self::ensure_not_blacklisted(&sender)?;
+			let kitty_1 = Self::kitties(kitty_id_1).ok_or(Error::<T>::InvalidKittyId)?;
+			let kitty_2 = Self::kitties(kitty_id_2).ok_or(Error::<T>::InvalidKittyId)?;
+			for kitty_id in &[kitty_id_1, kitty_id_2] {
+				ensure!(Self::kitty_owner(*kitty_id) == Some(sender.clone()), Error::<T>::NotKittyOwner);
+				ensure!(Self::kitty_lock(*kitty_id).is_none(), Error::<T>::KittyLocked);
+				ensure!(Self::escrows(*kitty_id).is_none(), Error::<T>::KittyInEscrow);
+				ensure!(Self::auctions(*kitty_id).is_none(), Error::<T>::KittyAlreadyOnAuction);
+				ensure!(Self::sealed_auctions(*kitty_id).is_none(), Error::<T>::KittyAlreadyOnAuction);
+				ensure!(!Self::is_departed(*kitty_id), Error::<T>::KittyDeparted);
+				Self::ensure_not_soulbound(*kitty_id)?;
+			}
+
+			let mut forged = [0u8; 16];
+			for i in 0..forged.len() {
+				forged[i] = kitty_1.0[i].max(kitty_2.0[i]);
+			}
+			let tier = Self::tier(kitty_id_1).max(Self::tier(kitty_id_2)) + 1;
+			let generation = Self::generation(kitty_id_1).max(Self::generation(kitty_id_2));
+
+			// All fallible steps run before the parents are burned; the
+			// forged kitty's deposit is reserved while the parents' two
+			// deposits are still held, exactly as a transfer reserves the
+			// recipient's deposit before releasing the sender's.
+			let dna = Self::unique_dna(forged)?;
+			let kitty_id = Self::kitty_id_for(&dna)?;
+			let _ = T::Currency::withdraw(
+				&sender,
+				T::FusionFee::get(),
+				WithdrawReason::Fee.into(),
+				ExistenceRequirement::KeepAlive,
+			)?;
+			T::Currency::reserve(&sender, T::KittyDeposit::get())?;
+			Self::remove_kitty(&sender, kitty_id_1);
+			Self::remove_kitty(&sender, kitty_id_2);
+			Self::insert_kitty(&sender, kitty_id, Kitty(dna));
+			<Tiers<T>>::insert(kitty_id, tier);
+			Self::set_generation(kitty_id, generation);
+			<Pedigrees<T>>::insert(kitty_id, vec![
+				ParentRef::Kitty(kitty_id_1),
+				ParentRef::Kitty(kitty_id_2),
+			]);
+			Self::note_provenance(kitty_id, &sender, TransferKind::Fusion);
+
+			Self::deposit_event(RawEvent::Fused(sender, kitty_id_1, kitty_id_2, kitty_id, tier));
+			Ok(())
+		}
+
+		/// Mint a soulbound kitty directly into `to`'s account, e.g. as an
+		/// achievement reward. Requires the admin origin. The kitty can
+		/// never be transferred, sold or traded; the deposit is reserved
+		/// from the recipient as usual.
+		#[weight = T::DbWeight::get().reads_writes(9, 15) + 10_000]
+		pub fn mint_soulbound(origin, to: T::AccountId) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			let dna = Self::unique_dna(Self::random_value(&to))?;
+			let kitty_id = Self::kitty_id_for(&dna)?;
+			Self::ensure_can_hold_one_more(&to)?;
+
+			T::Currency::reserve(&to, T::KittyDeposit::get())?;
+			Self::insert_kitty(&to, kitty_id, Kitty(dna));
+			Self::note_birth_record(kitty_id, &to);
+			<Soulbound<T>>::insert(kitty_id, true);
+			Self::note_provenance(kitty_id, &to, TransferKind::Mint);
+
+			Self::deposit_event(RawEvent::Created(to.clone(), kitty_id));
+			Self::deposit_event(RawEvent::SoulboundMinted(to, kitty_id));
+			Ok(())
+		}
+
+		/// Open a limited edition. Requires the admin origin. Kitties
+		/// minted from it carry `dna_prefix` as their first DNA byte and
+		/// count against `supply_cap`.
+		#[weight = T::DbWeight::get().reads_writes(2, 2) + 10_000]
+		pub fn open_edition(origin, name: Vec<u8>, supply_cap: u32, dna_prefix: u8, open: T::BlockNumber, close: T::BlockNumber) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(name.len() as u32 <= T::MaxNameLength::get(), Error::<T>::NameTooLong);
+			ensure!(open < close, Error::<T>::InvalidEditionWindow);
+
+			let edition_id = NextEditionId::get();
+			<Editions<T>>::insert(edition_id, Edition {
+				name,
+				supply_cap,
+				dna_prefix,
+				open,
+				close,
+				minted: 0,
+			});
+			NextEditionId::put(edition_id + 1);
+
+			Self::deposit_event(RawEvent::EditionOpened(edition_id, open, close));
+			Ok(())
+		}
+
+		/// Mint a kitty from an open edition, paying the usual deposit. The
+		/// newborn's first DNA byte is the edition marker; edition
+		/// membership is recorded per kitty for display and rarity.
+		#[weight = T::DbWeight::get().reads_writes(10, 16) + 10_000]
+		pub fn create_in_edition(origin, edition_id: u32) -> DispatchResult {
+			let sender = Self::ensure_minter(origin)?;
+			let mut edition = Self::editions(edition_id).ok_or(Error::<T>::EditionNotFound)?;
+			let now = <system::Module<T>>::block_number();
+			ensure!(now >= edition.open && now < edition.close, Error::<T>::EditionClosed);
+			ensure!(edition.minted < edition.supply_cap, Error::<T>::EditionSoldOut);
+
+			let dna = Self::unique_edition_dna(Self::random_value(&sender), edition.dna_prefix)?;
+			let kitty_id = Self::kitty_id_for(&dna)?;
+			Self::ensure_can_hold_one_more(&sender)?;
+
+			T::Currency::reserve(&sender, T::KittyDeposit::get())?;
+			edition.minted += 1;
+			<Editions<T>>::insert(edition_id, edition);
+			Self::insert_kitty(&sender, kitty_id, Kitty(dna));
+			Self::note_birth_record(kitty_id, &sender);
+			<KittyEdition<T>>::insert(kitty_id, edition_id);
+			Self::note_provenance(kitty_id, &sender, TransferKind::Mint);
+
+			Self::deposit_event(RawEvent::Created(sender.clone(), kitty_id));
+			Self::deposit_event(RawEvent::EditionMinted(sender, edition_id, kitty_id));
+			Ok(())
+		}
+
+		/// Open a race starting at `start`. Anyone may create one; the
+		/// entry fee funds the prize pool.
+		#[weight = T::DbWeight::get().reads_writes(2, 3) + 10_000]
+		pub fn create_race(origin, entry_fee: BalanceOf<T>, max_runners: u32, start: T::BlockNumber) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(start > <system::Module<T>>::block_number(), Error::<T>::InvalidRaceStart);
+			ensure!(
+				max_runners <= T::MaxContestEntrants::get(),
+				Error::<T>::ContestTooLarge
+			);
+			ensure!(
+				(ActiveRaces::get().len() as u32) < T::MaxActiveContests::get(),
+				Error::<T>::TooManyActiveContests
+			);
+
+			let race_id = NextRaceId::get();
+			<Races<T>>::insert(race_id, Race {
+				entry_fee,
+				max_runners,
+				start,
+				prize_pool: Zero::zero(),
+			});
+			ActiveRaces::mutate(|ids| ids.push(race_id));
+			NextRaceId::put(race_id + 1);
+
+			Self::deposit_event(RawEvent::RaceCreated(sender, race_id, start));
+			Ok(())
+		}
+
+		/// Enter a kitty the sender owns into a race, paying the entry fee
+		/// into the prize pool.
+		#[weight = T::DbWeight::get().reads_writes(5, 4) + 10_000]
+		pub fn enter_race(origin, race_id: u32, kitty_id: T::KittyIndex) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let mut race = Self::races(race_id).ok_or(Error::<T>::RaceNotFound)?;
+			ensure!(Self::kitty_owner(kitty_id) == Some(sender.clone()), Error::<T>::NotKittyOwner);
+			ensure!(!Self::is_departed(kitty_id), Error::<T>::KittyDeparted);
+			ensure!(Self::hibernating(kitty_id).is_none(), Error::<T>::KittyHibernating);
+			ensure!(
+				<system::Module<T>>::block_number() < race.start,
+				Error::<T>::RaceClosed
+			);
+			let mut runners = Self::race_runners(race_id);
+			ensure!((runners.len() as u32) < race.max_runners, Error::<T>::RaceFull);
+			ensure!(
+				runners.iter().all(|(entered, _)| *entered != kitty_id),
+				Error::<T>::AlreadyRacing
+			);
+
+			let _ = T::Currency::withdraw(
+				&sender,
+				race.entry_fee,
+				WithdrawReason::Fee.into(),
+				ExistenceRequirement::KeepAlive,
+			)?;
+			race.prize_pool = race.prize_pool.saturating_add(race.entry_fee);
+			runners.push((kitty_id, sender.clone()));
+			<Races<T>>::insert(race_id, race);
+			<RaceRunners<T>>::insert(race_id, runners);
+			<Counters<T>>::mutate(kitty_id, |counters| {
+				counters.battles = counters.battles.saturating_add(1)
+			});
+			<RaceRecords<T>>::mutate(kitty_id, |record| {
+				record.races = record.races.saturating_add(1)
+			});
+
+			Self::deposit_event(RawEvent::RaceEntered(sender, race_id, kitty_id));
+			Ok(())
+		}
+
+		/// Move every transferable kitty the sender owns to `to` in one
+		/// call, shuffling each deposit with its kitty. Locked, escrowed,
+		/// fractionalized, bridged-out and departed kitties stay behind.
+		/// Meant for account migration
+		/// and cold-wallet rotation. Like `take_snapshot`, the ownership
+		/// walk visits every kitty, so the declared weight carries a flat
+		/// surcharge for the scan; the per-kitty portion not used is
+		/// refunded.
+		#[weight = T::DbWeight::get().reads_writes(
+			4 * T::MaxKittiesPerAccount::get() as Weight,
+			10 * T::MaxKittiesPerAccount::get() as Weight,
+		) + 1_000_000]
+		pub fn transfer_all(origin, to: T::AccountId) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			let movable = Self::movable_kitties_of(&sender);
+			let count = movable.len() as u32;
+			Self::ensure_not_blacklisted(&to)?;
+			ensure!(
+				Self::owned_kitties_count(&to) + count <= T::MaxKittiesPerAccount::get(),
+				Error::<T>::TooManyKittiesPerAccount
+			);
+			let preferences = Self::preferences(&to);
+			ensure!(preferences.auto_accept, Error::<T>::RecipientRefusesTransfers);
+			if let Some(cap) = preferences.max_incoming {
+				ensure!(
+					Self::owned_kitties_count(&to) + count <= cap,
+					Error::<T>::RecipientAtCapacity
+				);
+			}
+
+			let deposits = T::KittyDeposit::get().saturating_mul(count.saturated_into());
+			T::Currency::reserve(&to, deposits)?;
+			T::Currency::unreserve(&sender, deposits);
+			for kitty_id in movable {
+				Self::do_transfer(&sender, &to, kitty_id);
+				Self::note_provenance(kitty_id, &to, TransferKind::Transfer);
+			}
+
+			Self::deposit_event(RawEvent::TransferredAll(sender, to, count));
+			let scanned = Self::kitties_count().saturated_into::<Weight>();
+			Ok(Some(
+				T::DbWeight::get().reads(scanned)
+					+ T::DbWeight::get().reads_writes(4, 10).saturating_mul(count as Weight)
+					+ 1_000_000,
+			).into())
+		}
+
+		/// Designate `heir` to inherit the sender's kitties after
+		/// `threshold` blocks of inactivity, or clear the designation with
+		/// `None`. Minting, breeding or moving a kitty counts as activity;
+		/// anything quieter can be covered with `heartbeat`.
+		#[weight = T::DbWeight::get().reads_writes(0, 2) + 10_000]
+		pub fn set_heir(origin, heir: Option<(T::AccountId, T::BlockNumber)>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			match heir {
+				Some((heir, threshold)) => {
+					ensure!(heir != sender, Error::<T>::HeirIsSelf);
+					ensure!(!threshold.is_zero(), Error::<T>::InvalidInactivityThreshold);
+					<Heirs<T>>::insert(&sender, (heir.clone(), threshold));
+					Self::deposit_event(RawEvent::HeirDesignated(sender.clone(), heir, threshold));
+				},
+				None => {
+					<Heirs<T>>::remove(&sender);
+					Self::deposit_event(RawEvent::HeirCleared(sender.clone()));
+				},
+			}
+			Self::note_activity(&sender);
+			Ok(())
+		}
+
+		/// Reset the sender's inactivity clock without touching any kitty.
+		/// Also withdraws any inheritance claim currently standing against
+		/// the sender, which is the owner's veto.
+		#[weight = T::DbWeight::get().reads_writes(1, 2) + 10_000]
+		pub fn heartbeat(origin) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::note_activity(&sender);
+			if let Some((claimant, _)) = <InheritanceClaims<T>>::take(&sender) {
+				Self::deposit_event(RawEvent::InheritanceVetoed(sender, claimant));
+			}
+			Ok(())
+		}
+
+		/// Open an inheritance claim against `dormant` as their designated
+		/// heir. The owner must have been inactive for at least the
+		/// designated threshold; the claim then sits through the challenge
+		/// window, during which any sign of life from the owner voids it.
+		#[weight = T::DbWeight::get().reads_writes(4, 1) + 10_000]
+		pub fn claim_inheritance(origin, dormant: T::AccountId) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let (heir, threshold) = Self::heir(&dormant).ok_or(Error::<T>::NoHeirDesignated)?;
+			ensure!(sender == heir, Error::<T>::NotDesignatedHeir);
+			ensure!(Self::inheritance_claim(&dormant).is_none(), Error::<T>::InheritanceClaimPending);
+			let now = <system::Module<T>>::block_number();
+			ensure!(
+				now >= Self::last_activity(&dormant) + threshold,
+				Error::<T>::OwnerStillActive
+			);
+
+			let finalize_at = now + T::InheritanceChallengeWindow::get();
+			<InheritanceClaims<T>>::insert(&dormant, (sender.clone(), finalize_at));
+			Self::deposit_event(RawEvent::InheritanceClaimed(sender, dormant, finalize_at));
+			Ok(())
+		}
+
+		/// Finalize a matured inheritance claim, moving every transferable
+		/// kitty of the dormant account to the heir with its deposit.
+		/// Locked, escrowed, fractionalized, bridged-out, departed and
+		/// soulbound kitties stay behind, and the ownership walk is
+		/// weighted and refunded, exactly as in
+		/// `transfer_all`.
+		#[weight = T::DbWeight::get().reads_writes(
+			4 * T::MaxKittiesPerAccount::get() as Weight,
+			10 * T::MaxKittiesPerAccount::get() as Weight,
+		) + 1_000_000]
+		pub fn finalize_inheritance(origin, dormant: T::AccountId) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			let (claimant, finalize_at) =
+				Self::inheritance_claim(&dormant).ok_or(Error::<T>::NoInheritanceClaim)?;
+			ensure!(sender == claimant, Error::<T>::NotDesignatedHeir);
+			let now = <system::Module<T>>::block_number();
+			ensure!(now >= finalize_at, Error::<T>::ChallengeWindowOpen);
+			// Any activity since the claim was opened is an implicit veto.
+			if Self::last_activity(&dormant)
+				> finalize_at.saturating_sub(T::InheritanceChallengeWindow::get())
+			{
+				<InheritanceClaims<T>>::remove(&dormant);
+				return Err(Error::<T>::OwnerStillActive.into());
+			}
+			Self::ensure_not_blacklisted(&sender)?;
+
+			let movable = Self::movable_kitties_of(&dormant);
+			let count = movable.len() as u32;
+			ensure!(
+				Self::owned_kitties_count(&sender) + count <= T::MaxKittiesPerAccount::get(),
+				Error::<T>::TooManyKittiesPerAccount
+			);
+
+			let deposits = T::KittyDeposit::get().saturating_mul(count.saturated_into());
+			T::Currency::reserve(&sender, deposits)?;
+			T::Currency::unreserve(&dormant, deposits);
+			<InheritanceClaims<T>>::remove(&dormant);
+			<Heirs<T>>::remove(&dormant);
+			for kitty_id in movable {
+				Self::do_transfer(&dormant, &sender, kitty_id);
+				Self::note_provenance(kitty_id, &sender, TransferKind::Inheritance);
+			}
+
+			Self::deposit_event(RawEvent::InheritanceFinalized(sender, dormant, count));
+			let scanned = Self::kitties_count().saturated_into::<Weight>();
+			Ok(Some(
+				T::DbWeight::get().reads(scanned)
+					+ T::DbWeight::get().reads_writes(4, 10).saturating_mul(count as Weight)
+					+ 1_000_000,
+			).into())
+		}
+
+		/// Offer a kitty to `to` as a two-phase transfer: nothing moves
+		/// until the recipient claims. The kitty remains usable by the
+		/// sender in the meantime; re-offering replaces any earlier offer.
+		#[weight = T::DbWeight::get().reads_writes(7, 1) + 10_000]
+		pub fn offer_transfer(origin, kitty_id: T::KittyIndex, to: T::AccountId, expiry: T::BlockNumber) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(owner == sender, Error::<T>::NotKittyOwner);
+			ensure!(Self::kitty_lock(kitty_id).is_none(), Error::<T>::KittyLocked);
+			ensure!(Self::escrows(kitty_id).is_none(), Error::<T>::KittyInEscrow);
+			ensure!(Self::fraction_shares(kitty_id).is_none(), Error::<T>::KittyFractionalized);
+			ensure!(Self::bridged_out(kitty_id).is_none(), Error::<T>::KittyBridgedOut);
+			ensure!(!Self::is_departed(kitty_id), Error::<T>::KittyDeparted);
+			Self::ensure_not_soulbound(kitty_id)?;
+			ensure!(
+				expiry > <system::Module<T>>::block_number(),
+				Error::<T>::InvalidTransferExpiry
+			);
+
+			<PendingTransfers<T>>::insert(kitty_id, PendingTransfer {
+				from: sender.clone(),
+				to: to.clone(),
+				expires_at: expiry,
+			});
+
+			Self::deposit_event(RawEvent::TransferOffered(sender, to, kitty_id, expiry));
+			Ok(())
+		}
+
+		/// Claim a kitty offered to the sender. Fails once the offer has
+		/// expired or if the kitty changed hands since it was made; the
+		/// deposit shuffle is the same as a direct transfer.
+		#[weight = T::DbWeight::get().reads_writes(7, 8) + 10_000]
+		pub fn claim_transfer(origin, kitty_id: T::KittyIndex) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let pending = Self::pending_transfer(kitty_id).ok_or(Error::<T>::NoPendingTransfer)?;
+			ensure!(pending.to == sender, Error::<T>::NotTransferRecipient);
+			// Expired offers are dead but left in place; the sender's next
+			// offer or cancellation overwrites them.
+			ensure!(
+				<system::Module<T>>::block_number() <= pending.expires_at,
+				Error::<T>::TransferOfferExpired
+			);
+			// The offer dies with any change of hands or state in between.
+			ensure!(Self::kitty_owner(kitty_id) == Some(pending.from.clone()), Error::<T>::NoPendingTransfer);
+			ensure!(Self::kitty_lock(kitty_id).is_none(), Error::<T>::KittyLocked);
+			ensure!(Self::escrows(kitty_id).is_none(), Error::<T>::KittyInEscrow);
+			ensure!(Self::fraction_shares(kitty_id).is_none(), Error::<T>::KittyFractionalized);
+			ensure!(Self::bridged_out(kitty_id).is_none(), Error::<T>::KittyBridgedOut);
+			ensure!(!Self::is_departed(kitty_id), Error::<T>::KittyDeparted);
+			Self::ensure_can_hold_one_more(&sender)?;
+
+			T::Currency::reserve(&sender, T::KittyDeposit::get())?;
+			T::Currency::unreserve(&pending.from, T::KittyDeposit::get());
+			<PendingTransfers<T>>::remove(kitty_id);
+			Self::do_transfer(&pending.from, &sender, kitty_id);
+			Self::note_provenance(kitty_id, &sender, TransferKind::Transfer);
+
+			Self::deposit_event(RawEvent::TransferClaimed(pending.from, sender, kitty_id));
+			Ok(())
+		}
+
+		/// Withdraw a pending transfer offer made by the sender.
+		#[weight = T::DbWeight::get().reads_writes(2, 1) + 10_000]
+		pub fn cancel_transfer_offer(origin, kitty_id: T::KittyIndex) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let pending = Self::pending_transfer(kitty_id).ok_or(Error::<T>::NoPendingTransfer)?;
+			ensure!(pending.from == sender, Error::<T>::NotKittyOwner);
+
+			<PendingTransfers<T>>::remove(kitty_id);
+			Self::deposit_event(RawEvent::TransferOfferCancelled(kitty_id));
+			Ok(())
+		}
+
+		/// Set the sender's transfer-acceptance preferences. Refusing
+		/// accounts cannot be sent kitties directly; a `max_incoming` cap
+		/// bounds holdings against incoming transfers.
+		#[weight = T::DbWeight::get().reads_writes(0, 1) + 10_000]
+		pub fn set_account_preferences(origin, auto_accept: bool, max_incoming: Option<u32>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			<Preferences<T>>::insert(&sender, AccountPreferences { auto_accept, max_incoming });
+			Self::deposit_event(RawEvent::PreferencesSet(sender, auto_accept, max_incoming));
+			Ok(())
+		}
+
+		/// Breed two kitties, producing a new kitty whose DNA mixes both
+		/// parents. The sender must own each parent, hold a valid breeding
+		/// delegation for it, or hold the breeder role in its cattery; the
+		/// child goes to the first parent's owner, who
+		/// pays the breeding fee and the newborn's deposit. A foreign second
+		/// parent's owner receives the fee share agreed in their siring
+		/// delegation.
+		#[weight = T::DbWeight::get().reads_writes(18, 20) + 10_000]
+		pub fn breed(origin, kitty_id_1: T::KittyIndex, kitty_id_2: T::KittyIndex) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let owner1 = Self::kitty_owner(kitty_id_1).ok_or(Error::<T>::InvalidKittyId)?;
+			let owner2 = Self::kitty_owner(kitty_id_2).ok_or(Error::<T>::InvalidKittyId)?;
+			let now = <system::Module<T>>::block_number();
+			ensure!(
+				owner1 == sender
+					|| Self::has_breeding_delegation(&sender, kitty_id_1, now)
+					|| Self::has_cattery_role(&sender, kitty_id_1, CatteryRole::Breeder),
+				Error::<T>::NoBreedingRights
+			);
+			ensure!(
+				owner2 == sender
+					|| Self::has_breeding_delegation(&sender, kitty_id_2, now)
+					|| Self::has_cattery_role(&sender, kitty_id_2, CatteryRole::Breeder),
+				Error::<T>::NoBreedingRights
+			);
+
+			// When the second parent belongs to someone else, its owner is
+			// paid the share agreed in the siring delegation.
+			let mut sire_shares: Vec<(T::AccountId, Percent)> = Vec::new();
+			if owner2 != owner1 {
+				if let Some((_, _, share)) = Self::breeding_delegations(kitty_id_2, &sender) {
+					sire_shares.push((owner2.clone(), share));
+				}
+			}
+			let kitty_id = Self::do_breed(&owner1, kitty_id_1, kitty_id_2, &sire_shares)?;
+			if owner1 != sender {
+				Self::consume_breeding_delegation(kitty_id_1, &sender);
+			}
+			if owner2 != sender {
+				Self::consume_breeding_delegation(kitty_id_2, &sender);
+			}
+
+			Self::deposit_event(RawEvent::Bred(
+				owner1.clone(), kitty_id, kitty_id_1, kitty_id_2, Self::breeder_id(&owner1),
+			));
+			Ok(())
+		}
+
+		/// Delegate breeding rights on a kitty to a manager account, limited
+		/// to `max_uses` breedings and expiring at `expiry`. The delegate can
+		/// breed on the owner's behalf but cannot transfer or sell the kitty.
+		/// Whenever the delegation covers a kitty the fee payer does not
+		/// own, the owner receives `fee_share` of the breed fee. The kitty
+		/// is trade-locked while any delegation stands, so the rights cannot
+		/// be sold out from under the delegate.
+		#[weight = T::DbWeight::get().reads_writes(2, 1) + 10_000]
+		pub fn delegate_breeding(
+			origin,
+			kitty_id: T::KittyIndex,
+			delegate: T::AccountId,
+			max_uses: u32,
+			expiry: T::BlockNumber,
+			fee_share: Percent,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(Self::kitty_owner(kitty_id) == Some(sender.clone()), Error::<T>::NotKittyOwner);
+			ensure!(max_uses > 0 && delegate != sender, Error::<T>::InvalidDelegation);
+			// A further delegation may join an existing delegation lock,
+			// but not a lock held for any other reason.
+			ensure!(
+				Self::kitty_lock(kitty_id).is_none()
+					|| <BreedingDelegations<T>>::iter_prefix(kitty_id).next().is_some(),
+				Error::<T>::KittyLocked
+			);
+
+			<BreedingDelegations<T>>::insert(kitty_id, &delegate, (max_uses, expiry, fee_share));
+			<KittyLocks<T>>::insert(kitty_id, Self::market_lock_account());
+			Self::deposit_event(RawEvent::BreedingDelegated(
+				sender, kitty_id, delegate, max_uses, expiry,
+			));
+			Ok(())
+		}
+
+		/// Revoke a breeding delegation previously granted by the sender.
+		#[weight = T::DbWeight::get().reads_writes(2, 1) + 10_000]
+		pub fn revoke_breeding_delegation(origin, kitty_id: T::KittyIndex, delegate: T::AccountId) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(Self::kitty_owner(kitty_id) == Some(sender.clone()), Error::<T>::NotKittyOwner);
+
+			let existed = <BreedingDelegations<T>>::contains_key(kitty_id, &delegate);
+			<BreedingDelegations<T>>::remove(kitty_id, &delegate);
+			if existed {
+				Self::release_delegation_lock(kitty_id);
+			}
+			Self::deposit_event(RawEvent::BreedingDelegationRevoked(sender, kitty_id, delegate));
+			Ok(())
+		}
+
+		/// Propose breeding the sender's `kitty_a` with someone else's
+		/// `kitty_b`, offering `fee` to the other owner and assigning the
+		/// child to `offspring_recipient`.
+		#[weight = T::DbWeight::get().reads_writes(5, 1) + 10_000]
+		pub fn propose_breeding(
+			origin,
+			kitty_a: T::KittyIndex,
+			kitty_b: T::KittyIndex,
+			fee: BalanceOf<T>,
+			offspring_recipient: T::AccountId,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(kitty_a != kitty_b, Error::<T>::RequireDifferentParent);
+			ensure!(Self::kitty_owner(kitty_a) == Some(sender.clone()), Error::<T>::NotKittyOwner);
+			let other = Self::kitty_owner(kitty_b).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(other != sender, Error::<T>::SameOwnerAgreement);
+
+			<BreedingAgreements<T>>::insert(kitty_a, kitty_b, BreedingAgreement {
+				proposer: sender.clone(),
+				fee,
+				offspring_recipient: offspring_recipient.clone(),
+			});
+			Self::deposit_event(RawEvent::BreedingProposed(
+				sender, kitty_a, kitty_b, fee, offspring_recipient,
+			));
+			Ok(())
+		}
+
+		/// Accept a breeding proposal on a kitty owned by the sender. Pays
+		/// the negotiated fee from the proposer to the sender and breeds the
+		/// pair atomically, assigning the child per the agreement.
+		#[weight = T::DbWeight::get().reads_writes(19, 21) + 10_000]
+		pub fn accept_breeding(origin, kitty_a: T::KittyIndex, kitty_b: T::KittyIndex) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let agreement = Self::breeding_agreements(kitty_a, kitty_b)
+				.ok_or(Error::<T>::NoBreedingAgreement)?;
+			ensure!(Self::kitty_owner(kitty_b) == Some(sender.clone()), Error::<T>::NotKittyOwner);
+			ensure!(
+				Self::kitty_owner(kitty_a) == Some(agreement.proposer.clone()),
+				Error::<T>::BreedingAgreementStale
+			);
+
+			if !agreement.fee.is_zero() {
+				T::Currency::transfer(
+					&agreement.proposer,
+					&sender,
+					agreement.fee,
+					ExistenceRequirement::KeepAlive,
+				)?;
+			}
+			let kitty_id = match Self::do_breed(&agreement.offspring_recipient, kitty_a, kitty_b, &[]) {
+				Ok(kitty_id) => kitty_id,
+				Err(e) => {
+					if !agreement.fee.is_zero() {
+						let _ = T::Currency::transfer(
+							&sender,
+							&agreement.proposer,
+							agreement.fee,
+							ExistenceRequirement::AllowDeath,
+						);
+					}
+					return Err(e);
+				}
+			};
+			<BreedingAgreements<T>>::remove(kitty_a, kitty_b);
+
+			Self::deposit_event(RawEvent::Bred(
+				agreement.offspring_recipient.clone(),
+				kitty_id,
+				kitty_a,
+				kitty_b,
+				Self::breeder_id(&agreement.offspring_recipient),
+			));
+			Ok(())
+		}
+
+		/// Cancel a breeding proposal made by the sender.
+		#[weight = T::DbWeight::get().reads_writes(2, 1) + 10_000]
+		pub fn cancel_breeding_proposal(origin, kitty_a: T::KittyIndex, kitty_b: T::KittyIndex) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let agreement = Self::breeding_agreements(kitty_a, kitty_b)
+				.ok_or(Error::<T>::NoBreedingAgreement)?;
+			ensure!(agreement.proposer == sender, Error::<T>::NotKittyOwner);
+
+			<BreedingAgreements<T>>::remove(kitty_a, kitty_b);
+			Self::deposit_event(RawEvent::BreedingProposalCancelled(sender, kitty_a, kitty_b));
+			Ok(())
+		}
+
+		/// List a kitty owned by the sender — or, for a cattery manager,
+		/// by the cattery's founder — at a fixed asking price, with an
+		/// optional revenue split applied to the net proceeds at settlement.
+		/// With `CustodialListings` enabled the kitty moves into the custody
+		/// account for the life of the listing; otherwise it stays with the
+		/// seller under a trade lock. The price may be denominated in a
+		/// registered asset, or in the oracle's reference unit to be
+		/// converted to the native currency at settlement.
+		#[weight = FunctionOf(
+			|(_, _, splits, _, _, _): (&T::KittyIndex, &BalanceOf<T>, &Vec<(T::AccountId, Percent)>, &Option<AssetIdOf<T>>, &bool, &Option<(T::AccountId, Percent)>)|
+				T::DbWeight::get().reads_writes(8, 1 + splits.len() as Weight) + 10_000,
+			DispatchClass::Normal,
+			Pays::Yes,
+		)]
+		pub fn sell(
+			origin,
+			kitty_id: T::KittyIndex,
+			price: BalanceOf<T>,
+			splits: Vec<(T::AccountId, Percent)>,
+			asset: Option<AssetIdOf<T>>,
+			reference_priced: bool,
+			donation: Option<(T::AccountId, Percent)>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_not_blacklisted(&sender)?;
+			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			// A cattery manager may list on the founder's behalf; the
+			// owner stays the seller of record and receives the proceeds.
+			ensure!(
+				owner == sender || Self::has_cattery_role(&sender, kitty_id, CatteryRole::Manager),
+				Error::<T>::NotKittyOwner
+			);
+			Self::ensure_not_blacklisted(&owner)?;
+			ensure!(Self::fraction_shares(kitty_id).is_none(), Error::<T>::KittyFractionalized);
+			ensure!(Self::bridged_out(kitty_id).is_none(), Error::<T>::KittyBridgedOut);
+			ensure!(Self::kitty_lock(kitty_id).is_none(), Error::<T>::KittyLocked);
+			ensure!(Self::escrows(kitty_id).is_none(), Error::<T>::KittyInEscrow);
+			ensure!(!Self::is_departed(kitty_id), Error::<T>::KittyDeparted);
+			ensure!(Self::hibernating(kitty_id).is_none(), Error::<T>::KittyHibernating);
+			Self::ensure_not_soulbound(kitty_id)?;
+			ensure!(
+				splits.len() <= T::MaxSaleSplits::get() as usize,
+				Error::<T>::TooManySaleSplits
+			);
+			let mut total: u32 = splits.iter().map(|(_, share)| share.deconstruct() as u32).sum();
+			if let Some((charity, share)) = &donation {
+				ensure!(Self::is_approved_charity(charity), Error::<T>::CharityNotApproved);
+				total += share.deconstruct() as u32;
+			}
+			ensure!(total <= 100, Error::<T>::InvalidSaleSplit);
+			if reference_priced {
+				ensure!(asset.is_none(), Error::<T>::ReferencePricingRequiresNative);
+				// A courtesy check; the quote that matters is the one at
+				// settlement.
+				ensure!(
+					T::PriceOracle::reference_to_native(price).is_some(),
+					Error::<T>::PriceUnavailable
+				);
+			}
+
+			if T::CustodialListings::get() {
+				// The custody account holds the kitty deposit-free, like
+				// the drop pool; the seller's deposit comes back now and
+				// the buyer posts a fresh one at settlement.
+				T::Currency::unreserve(&owner, T::KittyDeposit::get());
+				Self::do_transfer(&owner, &Self::listing_custody_account(), kitty_id);
+			} else {
+				<KittyLocks<T>>::insert(kitty_id, Self::market_lock_account());
+			}
+			Self::insert_listing(
+				kitty_id,
+				Listing { seller: owner.clone(), price, splits, asset, reference_priced, donation },
+			);
+			Self::deposit_event(RawEvent::Listed(
+				owner,
+				kitty_id,
+				price,
+				asset,
+				Self::is_verified(kitty_id),
+			));
+			Ok(())
+		}
+
+		/// Buy a listed kitty at its asking price.
+		#[weight = T::DbWeight::get().reads_writes(11, 12) + 10_000]
+		pub fn buy(origin, kitty_id: T::KittyIndex) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			// The holder is the seller for locked listings and the custody
+			// account for custodial ones; the price goes to the seller
+			// either way.
+			let holder = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			let listing = Self::listings(kitty_id).ok_or(Error::<T>::NotForSale)?;
+			ensure!(listing.seller != sender, Error::<T>::OwnKittyMarketAction);
+			Self::ensure_can_hold_one_more(&sender)?;
+			let price = Self::effective_listing_price(&listing)?;
+
+			T::Currency::reserve(&sender, T::KittyDeposit::get())?;
+			let mut splits = listing.splits.clone();
+			if let Some((charity, share)) = &listing.donation {
+				splits.push((charity.clone(), *share));
+			}
+			let settled = match listing.asset {
+				Some(asset) => Self::settle_asset_payment(
+					&asset, &sender, &listing.seller, price, &splits,
+				),
+				None => Self::settle_payment(&sender, &listing.seller, price, &splits),
+			};
+			let fee = match settled {
+				Ok(fee) => fee,
+				Err(e) => {
+					T::Currency::unreserve(&sender, T::KittyDeposit::get());
+					return Err(e);
+				}
+			};
+			if holder == listing.seller {
+				T::Currency::unreserve(&holder, T::KittyDeposit::get());
+				<KittyLocks<T>>::remove(kitty_id);
+			}
+			Self::take_listing(kitty_id);
+			Self::do_transfer(&holder, &sender, kitty_id);
+			Self::note_provenance(kitty_id, &sender, TransferKind::Sale);
+
+			if let Some((charity, share)) = &listing.donation {
+				Self::deposit_event(RawEvent::DonationRouted(
+					listing.seller.clone(),
+					charity.clone(),
+					kitty_id,
+					*share * (price - fee),
+				));
+			}
+			Self::deposit_event(RawEvent::Sold(
+				listing.seller.clone(),
+				sender,
+				kitty_id,
+				price,
+				fee,
+				Self::breeder_id(&listing.seller),
+			));
+			Ok(())
+		}
+
+		/// Delist a kitty listed by the sender, or by a manager of its
+		/// cattery. The kitty always returns to the seller of record.
+		#[weight = T::DbWeight::get().reads_writes(3, 1) + 10_000]
+		pub fn cancel_listing(origin, kitty_id: T::KittyIndex) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let holder = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			let listing = Self::listings(kitty_id).ok_or(Error::<T>::NotForSale)?;
+			ensure!(
+				listing.seller == sender
+					|| Self::has_cattery_role(&sender, kitty_id, CatteryRole::Manager),
+				Error::<T>::NotKittyOwner
+			);
+			let seller = listing.seller;
+
+			if holder == seller {
+				Self::take_listing(kitty_id);
+				<KittyLocks<T>>::remove(kitty_id);
+			} else {
+				// Reclaim from custody; the seller posts the deposit again
+				// before any state is touched.
+				Self::ensure_can_hold_one_more(&seller)?;
+				T::Currency::reserve(&seller, T::KittyDeposit::get())?;
+				Self::take_listing(kitty_id);
+				Self::do_transfer(&holder, &seller, kitty_id);
+			}
+			Self::deposit_event(RawEvent::ListingCancelled(seller, kitty_id));
+			Ok(())
+		}
+
+		/// Buy a listed kitty in escrow instead of settling immediately. The
+		/// payment is reserved and the kitty frozen until the dispute window
+		/// passes, at which point settlement finalizes automatically.
+		#[weight = T::DbWeight::get().reads_writes(8, 3) + 10_000]
+		pub fn buy_in_escrow(origin, kitty_id: T::KittyIndex) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(owner != sender, Error::<T>::OwnKittyMarketAction);
+			ensure!(Self::escrows(kitty_id).is_none(), Error::<T>::KittyInEscrow);
+			let listing = Self::listings(kitty_id).ok_or(Error::<T>::NotForSale)?;
+			// Escrow settlement moves the kitty straight from the seller;
+			// custodially held listings must be bought outright.
+			ensure!(owner == listing.seller, Error::<T>::ListingInCustody);
+			// The escrow reserve only speaks the native currency.
+			ensure!(listing.asset.is_none(), Error::<T>::EscrowRequiresNative);
+			Self::ensure_can_hold_one_more(&sender)?;
+			// A reference-priced listing is converted when the buyer
+			// commits; the escrow then holds that native amount.
+			let price = Self::effective_listing_price(&listing)?;
+
+			let release_at =
+				<system::Module<T>>::block_number() + T::EscrowDisputeWindow::get();
+			ensure!(
+				(Self::escrows_by_end(release_at).len() as u32) < T::MaxSameBlockEndings::get(),
+				Error::<T>::TooManyEndingAtBlock
+			);
+			T::Currency::reserve(&sender, price)?;
+			Self::take_listing(kitty_id);
+			// The escrow state itself freezes the kitty from here on.
+			<KittyLocks<T>>::remove(kitty_id);
+			<Escrows<T>>::insert(kitty_id, Escrow {
+				seller: owner.clone(),
+				buyer: sender.clone(),
+				price,
+				release_at,
+				disputed: false,
+			});
+			<EscrowsByEnd<T>>::mutate(release_at, |ids| ids.push(kitty_id));
+
+			Self::deposit_event(RawEvent::EscrowOpened(
+				owner, sender, kitty_id, price, release_at,
+			));
+			Ok(())
+		}
+
+		/// Raise a dispute on an escrowed sale. Only the buyer or seller may
+		/// dispute; a disputed escrow no longer finalizes automatically and
+		/// waits for the arbiter's decision.
+		#[weight = T::DbWeight::get().reads_writes(2, 1) + 10_000]
+		pub fn raise_escrow_dispute(origin, kitty_id: T::KittyIndex) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let mut escrow = Self::escrows(kitty_id).ok_or(Error::<T>::NoEscrow)?;
+			ensure!(
+				sender == escrow.buyer || sender == escrow.seller,
+				Error::<T>::NotEscrowParty
+			);
+
+			escrow.disputed = true;
+			<Escrows<T>>::insert(kitty_id, escrow);
+			Self::deposit_event(RawEvent::EscrowDisputed(sender, kitty_id));
+			Ok(())
+		}
+
+		/// Resolve a disputed escrow. Requires the arbiter origin. Releasing to
+		/// the buyer completes the sale; otherwise the buyer is refunded and
+		/// the kitty stays with the seller.
+		#[weight = T::DbWeight::get().reads_writes(10, 13) + 10_000]
+		pub fn resolve_escrow_dispute(origin, kitty_id: T::KittyIndex, release_to_buyer: bool) -> DispatchResult {
+			T::ArbiterOrigin::ensure_origin(origin)?;
+			let escrow = Self::escrows(kitty_id).ok_or(Error::<T>::NoEscrow)?;
+			ensure!(escrow.disputed, Error::<T>::EscrowNotDisputed);
+
+			<Escrows<T>>::remove(kitty_id);
+			<EscrowsByEnd<T>>::mutate(escrow.release_at, |ids| ids.retain(|id| *id != kitty_id));
+			Self::close_escrow(kitty_id, escrow, release_to_buyer);
+			Ok(())
+		}
+
+		/// Buy a listed kitty on installments: a down payment is reserved
+		/// now and the rest falls due in `installments` equal payments,
+		/// one every `InstallmentInterval` blocks. The kitty stays locked
+		/// with the seller until the last installment settles; a missed
+		/// due date forfeits the down payment.
+		#[weight = T::DbWeight::get().reads_writes(9, 4) + 10_000]
+		pub fn buy_in_installments(origin, kitty_id: T::KittyIndex, installments: u32) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(owner != sender, Error::<T>::OwnKittyMarketAction);
+			ensure!(Self::escrows(kitty_id).is_none(), Error::<T>::KittyInEscrow);
+			let listing = Self::listings(kitty_id).ok_or(Error::<T>::NotForSale)?;
+			ensure!(owner == listing.seller, Error::<T>::ListingInCustody);
+			// Like escrow, the schedule only speaks the reserve machinery
+			// of the native currency.
+			ensure!(listing.asset.is_none(), Error::<T>::EscrowRequiresNative);
+			Self::ensure_can_hold_one_more(&sender)?;
+			// A reference-priced listing is converted once, when the buyer
+			// commits to the schedule.
+			let price = Self::effective_listing_price(&listing)?;
+			let down_payment = T::InstallmentDownPayment::get() * price;
+			let per_installment = (price - down_payment)
+				/ installments.max(1).into();
+			ensure!(
+				installments >= 1 && installments <= T::MaxInstallments::get(),
+				Error::<T>::InvalidInstallmentCount
+			);
+			ensure!(!per_installment.is_zero(), Error::<T>::InvalidInstallmentCount);
+			let next_due = <system::Module<T>>::block_number() + T::InstallmentInterval::get();
+			ensure!(
+				(Self::installments_by_due(next_due).len() as u32) < T::MaxSameBlockEndings::get(),
+				Error::<T>::TooManyEndingAtBlock
+			);
+
+			T::Currency::reserve(&sender, down_payment)?;
+			Self::take_listing(kitty_id);
+			// The listing's trade lock stays in place for the life of the
+			// schedule.
+			<Installments<T>>::insert(kitty_id, InstallmentPlan {
+				seller: owner.clone(),
+				buyer: sender.clone(),
+				price,
+				down_payment,
+				per_installment,
+				outstanding: price - down_payment,
+				remaining: installments,
+				next_due,
+				splits: {
+					let mut splits = listing.splits;
+					if let Some((charity, share)) = listing.donation {
+						splits.push((charity, share));
+					}
+					splits
+				},
+			});
+			<InstallmentsByDue<T>>::mutate(next_due, |ids| ids.push(kitty_id));
+
+			Self::deposit_event(RawEvent::InstallmentPlanStarted(
+				owner, sender, kitty_id, price, down_payment,
+			));
+			Ok(())
+		}
+
+		/// Pay the next installment on a running purchase. Early payment is
+		/// fine and pushes the next due date out by a full interval. The
+		/// final installment settles the whole price and delivers the
+		/// kitty.
+		#[weight = T::DbWeight::get().reads_writes(9, 12) + 10_000]
+		pub fn pay_installment(origin, kitty_id: T::KittyIndex) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let mut plan = Self::installments(kitty_id).ok_or(Error::<T>::NoInstallmentPlan)?;
+			ensure!(plan.buyer == sender, Error::<T>::NotInstallmentBuyer);
+
+			if plan.remaining <= 1 {
+				// The last installment absorbs the rounding remainder and
+				// settles the full price out of the released reserve, with
+				// the usual rollback if payment or deposit fails.
+				Self::ensure_can_hold_one_more(&sender)?;
+				// Rolling back restores the pre-call reserve: everything
+				// paid so far, without the attempted final installment.
+				let paid_so_far = plan.price - plan.outstanding;
+				T::Currency::reserve(&sender, plan.outstanding)?;
+				T::Currency::unreserve(&sender, plan.price);
+				if let Err(e) = T::Currency::reserve(&sender, T::KittyDeposit::get()) {
+					let _ = T::Currency::reserve(&sender, paid_so_far);
+					return Err(e.into());
+				}
+				let fee = match Self::settle_payment(&sender, &plan.seller, plan.price, &plan.splits) {
+					Ok(fee) => fee,
+					Err(e) => {
+						T::Currency::unreserve(&sender, T::KittyDeposit::get());
+						let _ = T::Currency::reserve(&sender, paid_so_far);
+						return Err(e);
+					}
+				};
+				T::Currency::unreserve(&plan.seller, T::KittyDeposit::get());
+				<Installments<T>>::remove(kitty_id);
+				<InstallmentsByDue<T>>::mutate(plan.next_due, |ids| ids.retain(|id| *id != kitty_id));
+				<KittyLocks<T>>::remove(kitty_id);
+				Self::do_transfer(&plan.seller, &sender, kitty_id);
+				Self::note_provenance(kitty_id, &sender, TransferKind::Sale);
+				Self::deposit_event(RawEvent::InstallmentsCompleted(
+					plan.seller, sender, kitty_id, plan.price, fee,
+				));
+				return Ok(());
+			}
+
+			let next_due = <system::Module<T>>::block_number() + T::InstallmentInterval::get();
+			ensure!(
+				(Self::installments_by_due(next_due).len() as u32) < T::MaxSameBlockEndings::get(),
+				Error::<T>::TooManyEndingAtBlock
+			);
+			T::Currency::reserve(&sender, plan.per_installment)?;
+			<InstallmentsByDue<T>>::mutate(plan.next_due, |ids| ids.retain(|id| *id != kitty_id));
+			plan.outstanding -= plan.per_installment;
+			plan.remaining -= 1;
+			plan.next_due = next_due;
+			<InstallmentsByDue<T>>::mutate(next_due, |ids| ids.push(kitty_id));
+			let outstanding = plan.outstanding;
+			let amount = plan.per_installment;
+			<Installments<T>>::insert(kitty_id, plan);
+
+			Self::deposit_event(RawEvent::InstallmentPaid(sender, kitty_id, amount, outstanding));
+			Ok(())
+		}
+
+		/// Register the sender as a breeder. Requires a verified identity
+		/// through `T::Identity`; the assigned registration id is echoed
+		/// in breeding and sale events so UIs can show the judged
+		/// identity instead of a raw address.
+		#[weight = T::DbWeight::get().reads_writes(2, 2) + 10_000]
+		pub fn register_breeder(origin) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(T::Identity::is_verified(&sender), Error::<T>::NoVerifiedIdentity);
+			ensure!(Self::breeder_id(&sender).is_none(), Error::<T>::AlreadyRegisteredBreeder);
+
+			let id = Self::next_breeder_id();
+			NextBreederId::put(id + 1);
+			<BreederRegistry<T>>::insert(&sender, id);
+			Self::deposit_event(RawEvent::BreederRegistered(sender, id));
+			Ok(())
+		}
+
+		/// Remove the sender's breeder registration. The id is not reused.
+		#[weight = T::DbWeight::get().reads_writes(1, 1) + 10_000]
+		pub fn deregister_breeder(origin) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let id = Self::breeder_id(&sender).ok_or(Error::<T>::NotRegisteredBreeder)?;
+
+			<BreederRegistry<T>>::remove(&sender);
+			Self::deposit_event(RawEvent::BreederDeregistered(sender, id));
+			Ok(())
+		}
+
+		/// Make an offer on someone else's kitty, reserving the offered
+		/// amount. An open-ended offer stands until accepted or cancelled;
+		/// one with an expiry dies at that block and the sweep hands the
+		/// reserve back. An asset-denominated offer moves the amount into
+		/// the payment pool instead, since assets cannot be reserved.
+		#[weight = T::DbWeight::get().reads_writes(4, 2) + 10_000]
+		pub fn make_offer(origin, kitty_id: T::KittyIndex, amount: BalanceOf<T>, expires_at: Option<T::BlockNumber>, asset: Option<AssetIdOf<T>>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(owner != sender, Error::<T>::OwnKittyMarketAction);
+			// An escrowed kitty is already sold pending release; offers on
+			// it could only strand the offerer's reserve.
+			ensure!(Self::escrows(kitty_id).is_none(), Error::<T>::KittyInEscrow);
+			Self::ensure_not_soulbound(kitty_id)?;
+			ensure!(Self::offers(kitty_id, &sender).is_none(), Error::<T>::OfferAlreadyExists);
+			if let Some(expiry) = expires_at {
+				ensure!(
+					expiry > <system::Module<T>>::block_number(),
+					Error::<T>::InvalidOfferExpiry
+				);
+			}
+
+			match asset {
+				Some(asset) => T::Fungibles::transfer(
+					&asset,
+					&sender,
+					&Self::payment_pool_account(),
+					amount.saturated_into::<u128>(),
+				)?,
+				None => T::Currency::reserve(&sender, amount)?,
+			}
+			<Offers<T>>::insert(kitty_id, &sender, (amount, expires_at, asset));
+			Self::deposit_event(RawEvent::OfferMade(sender, kitty_id, amount, asset));
+			Ok(())
+		}
+
+		/// Accept a standing offer on a kitty owned by the sender.
+		#[weight = T::DbWeight::get().reads_writes(11, 13) + 10_000]
+		pub fn accept_offer(origin, kitty_id: T::KittyIndex, offerer: T::AccountId) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(owner == sender, Error::<T>::NotKittyOwner);
+			ensure!(Self::kitty_lock(kitty_id).is_none(), Error::<T>::KittyLocked);
+			ensure!(Self::escrows(kitty_id).is_none(), Error::<T>::KittyInEscrow);
+			ensure!(!Self::is_departed(kitty_id), Error::<T>::KittyDeparted);
+			let (amount, expires_at, asset) =
+				Self::offers(kitty_id, &offerer).ok_or(Error::<T>::OfferNotFound)?;
+			// Expired offers are dead but left in place for the sweep to
+			// release; they can no longer be accepted.
+			if let Some(expiry) = expires_at {
+				ensure!(
+					<system::Module<T>>::block_number() <= expiry,
+					Error::<T>::OfferExpired
+				);
+			}
+			Self::ensure_can_hold_one_more(&offerer)?;
+
+			// Release the reserved offer, then take payment and deposit from
+			// the now-free funds, rolling back if either step fails. Asset
+			// offers settle straight out of the payment pool; only the
+			// native kitty deposit can fail.
+			if asset.is_none() {
+				T::Currency::unreserve(&offerer, amount);
+			}
+			if let Err(e) = T::Currency::reserve(&offerer, T::KittyDeposit::get()) {
+				if asset.is_none() {
+					let _ = T::Currency::reserve(&offerer, amount);
+				}
+				return Err(e.into());
+			}
+			let settled = match asset {
+				Some(asset) => Self::settle_asset_payment(
+					&asset, &Self::payment_pool_account(), &sender, amount, &[],
+				),
+				None => Self::settle_payment(&offerer, &sender, amount, &[]),
+			};
+			let fee = match settled {
+				Ok(fee) => fee,
+				Err(e) => {
+					T::Currency::unreserve(&offerer, T::KittyDeposit::get());
+					if asset.is_none() {
+						let _ = T::Currency::reserve(&offerer, amount);
+					}
+					return Err(e);
+				}
+			};
+			T::Currency::unreserve(&sender, T::KittyDeposit::get());
+			<Offers<T>>::remove(kitty_id, &offerer);
+			Self::do_transfer(&sender, &offerer, kitty_id);
+			Self::note_provenance(kitty_id, &offerer, TransferKind::Offer);
+
+			Self::deposit_event(RawEvent::OfferAccepted(sender, offerer, kitty_id, amount, fee));
+			Ok(())
+		}
+
+		/// Withdraw the sender's offer on a kitty, releasing the reserved funds.
+		#[weight = T::DbWeight::get().reads_writes(2, 2) + 10_000]
+		pub fn cancel_offer(origin, kitty_id: T::KittyIndex) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let (amount, _, asset) =
+				Self::offers(kitty_id, &sender).ok_or(Error::<T>::OfferNotFound)?;
+
+			Self::release_offer_funds(&sender, amount, asset);
+			<Offers<T>>::remove(kitty_id, &sender);
+			Self::deposit_event(RawEvent::OfferCancelled(sender, kitty_id));
+			Ok(())
+		}
+
+		/// Cancel an auction that has not yet received a bid.
+		#[weight = T::DbWeight::get().reads_writes(2, 2) + 10_000]
+		pub fn cancel_auction(origin, kitty_id: T::KittyIndex) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let auction = Self::auctions(kitty_id).ok_or(Error::<T>::AuctionNotFound)?;
+			ensure!(auction.seller == sender, Error::<T>::NotKittyOwner);
+			ensure!(auction.top_bidder.is_none(), Error::<T>::AuctionHasBids);
+
+			<Auctions<T>>::remove(kitty_id);
+			<AuctionsByEnd<T>>::mutate(auction.end, |ids| ids.retain(|id| *id != kitty_id));
+			<KittyLocks<T>>::remove(kitty_id);
+			Self::deposit_event(RawEvent::AuctionCancelled(sender, kitty_id));
+			Ok(())
+		}
+
+		/// Put a kitty owned by the sender up for auction. The auction ends
+		/// `duration` blocks from now and is settled automatically. Bids are
+		/// taken in the native currency, or in a registered asset if one is
+		/// named.
+		#[weight = T::DbWeight::get().reads_writes(8, 2) + 10_000]
+		pub fn start_auction(origin, kitty_id: T::KittyIndex, reserve_price: BalanceOf<T>, duration: T::BlockNumber, asset: Option<AssetIdOf<T>>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(owner == sender, Error::<T>::NotKittyOwner);
+			ensure!(Self::kitty_lock(kitty_id).is_none(), Error::<T>::KittyLocked);
+			ensure!(Self::escrows(kitty_id).is_none(), Error::<T>::KittyInEscrow);
+			ensure!(Self::fraction_shares(kitty_id).is_none(), Error::<T>::KittyFractionalized);
+			ensure!(Self::bridged_out(kitty_id).is_none(), Error::<T>::KittyBridgedOut);
+			ensure!(!Self::is_departed(kitty_id), Error::<T>::KittyDeparted);
+			ensure!(Self::auctions(kitty_id).is_none(), Error::<T>::KittyAlreadyOnAuction);
+			ensure!(Self::sealed_auctions(kitty_id).is_none(), Error::<T>::KittyAlreadyOnAuction);
+			ensure!(Self::hibernating(kitty_id).is_none(), Error::<T>::KittyHibernating);
+			Self::ensure_not_soulbound(kitty_id)?;
+			ensure!(!duration.is_zero(), Error::<T>::InvalidAuctionDuration);
+
+			let end = <system::Module<T>>::block_number() + duration;
+			ensure!(
+				(Self::auctions_by_end(end).len() as u32) < T::MaxSameBlockEndings::get(),
+				Error::<T>::TooManyEndingAtBlock
+			);
+			<Auctions<T>>::insert(kitty_id, Auction {
+				seller: sender.clone(),
+				reserve_price,
+				end,
+				top_bidder: None,
+				top_bid: Zero::zero(),
+				asset,
+			});
+			<AuctionsByEnd<T>>::mutate(end, |ids| ids.push(kitty_id));
+			<KittyLocks<T>>::insert(kitty_id, Self::market_lock_account());
+
+			Self::deposit_event(RawEvent::AuctionStarted(sender, kitty_id, reserve_price, end, asset));
+			Ok(())
+		}
+
+		/// Bid on a running auction. The bid amount is reserved; the previous
+		/// top bidder is refunded immediately.
+		#[weight = T::DbWeight::get().reads_writes(3, 2) + 10_000]
+		pub fn bid(origin, kitty_id: T::KittyIndex, amount: BalanceOf<T>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let mut auction = Self::auctions(kitty_id).ok_or(Error::<T>::AuctionNotFound)?;
+			ensure!(sender != auction.seller, Error::<T>::BidOnOwnAuction);
+			ensure!(<system::Module<T>>::block_number() < auction.end, Error::<T>::AuctionEnded);
+			ensure!(
+				amount >= auction.reserve_price && amount > auction.top_bid,
+				Error::<T>::BidTooLow
+			);
+
+			match auction.asset {
+				Some(asset) => T::Fungibles::transfer(
+					&asset,
+					&sender,
+					&Self::payment_pool_account(),
+					amount.saturated_into::<u128>(),
+				)?,
+				None => T::Currency::reserve(&sender, amount)?,
+			}
+			if let Some(prev) = auction.top_bidder.take() {
+				Self::release_offer_funds(&prev, auction.top_bid, auction.asset);
+			}
+			auction.top_bidder = Some(sender.clone());
+			auction.top_bid = amount;
+			<Auctions<T>>::insert(kitty_id, auction);
+
+			Self::deposit_event(RawEvent::BidPlaced(sender, kitty_id, amount));
+			Ok(())
+		}
+
+		/// Put a kitty up for sealed-bid auction. Bids are committed as
+		/// hashes for `commit_duration` blocks, then revealed for
+		/// `reveal_duration` blocks; settlement runs automatically at the
+		/// end of the reveal phase and the winner pays the second price.
+		/// `bid_deposit` is reserved with every commitment and slashed if
+		/// the commitment is never revealed.
+		#[weight = T::DbWeight::get().reads_writes(9, 2) + 10_000]
+		pub fn start_sealed_auction(origin, kitty_id: T::KittyIndex, reserve_price: BalanceOf<T>, bid_deposit: BalanceOf<T>, commit_duration: T::BlockNumber, reveal_duration: T::BlockNumber) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(owner == sender, Error::<T>::NotKittyOwner);
+			ensure!(Self::kitty_lock(kitty_id).is_none(), Error::<T>::KittyLocked);
+			ensure!(Self::escrows(kitty_id).is_none(), Error::<T>::KittyInEscrow);
+			ensure!(Self::fraction_shares(kitty_id).is_none(), Error::<T>::KittyFractionalized);
+			ensure!(Self::bridged_out(kitty_id).is_none(), Error::<T>::KittyBridgedOut);
+			ensure!(!Self::is_departed(kitty_id), Error::<T>::KittyDeparted);
+			ensure!(Self::auctions(kitty_id).is_none(), Error::<T>::KittyAlreadyOnAuction);
+			ensure!(Self::sealed_auctions(kitty_id).is_none(), Error::<T>::KittyAlreadyOnAuction);
+			ensure!(Self::hibernating(kitty_id).is_none(), Error::<T>::KittyHibernating);
+			Self::ensure_not_soulbound(kitty_id)?;
+			ensure!(
+				!commit_duration.is_zero() && !reveal_duration.is_zero(),
+				Error::<T>::InvalidAuctionDuration
+			);
+
+			let now = <system::Module<T>>::block_number();
+			let commit_end = now + commit_duration;
+			let reveal_end = commit_end + reveal_duration;
+			ensure!(
+				(Self::sealed_auctions_by_end(reveal_end).len() as u32)
+					< T::MaxSameBlockEndings::get(),
+				Error::<T>::TooManyEndingAtBlock
+			);
+			<SealedAuctions<T>>::insert(kitty_id, SealedAuction {
+				seller: sender.clone(),
+				reserve_price,
+				bid_deposit,
+				commit_end,
+				reveal_end,
+			});
+			<SealedAuctionsByEnd<T>>::mutate(reveal_end, |ids| ids.push(kitty_id));
+			<KittyLocks<T>>::insert(kitty_id, Self::market_lock_account());
+
+			Self::deposit_event(RawEvent::SealedAuctionStarted(
+				sender, kitty_id, reserve_price, commit_end, reveal_end,
+			));
+			Ok(())
+		}
+
+		/// Commit to a bid on a sealed auction. `commitment` must be
+		/// `blake2_256(encode(bidder, kitty_id, amount, salt))`; the bid
+		/// deposit is reserved and returned on an honest reveal. One
+		/// commitment per bidder per auction.
+		#[weight = T::DbWeight::get().reads_writes(3, 2) + 10_000]
+		pub fn commit_bid(origin, kitty_id: T::KittyIndex, commitment: [u8; 32]) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let auction = Self::sealed_auctions(kitty_id).ok_or(Error::<T>::SealedAuctionNotFound)?;
+			ensure!(sender != auction.seller, Error::<T>::BidOnOwnAuction);
+			ensure!(
+				<system::Module<T>>::block_number() < auction.commit_end,
+				Error::<T>::CommitPhaseClosed
+			);
+			ensure!(
+				Self::sealed_bids(kitty_id, &sender).is_none(),
+				Error::<T>::BidAlreadyCommitted
+			);
+
+			T::Currency::reserve(&sender, auction.bid_deposit)?;
+			<SealedBids<T>>::insert(kitty_id, &sender, (commitment, Option::<BalanceOf<T>>::None));
+
+			Self::deposit_event(RawEvent::BidCommitted(sender, kitty_id));
+			Ok(())
+		}
+
+		/// Reveal a committed bid during the reveal phase. The amount is
+		/// reserved until settlement and the bid deposit is returned; a
+		/// reveal that does not match the commitment is rejected.
+		#[weight = T::DbWeight::get().reads_writes(3, 2) + 10_000]
+		pub fn reveal_bid(origin, kitty_id: T::KittyIndex, amount: BalanceOf<T>, salt: [u8; 32]) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let auction = Self::sealed_auctions(kitty_id).ok_or(Error::<T>::SealedAuctionNotFound)?;
+			let now = <system::Module<T>>::block_number();
+			ensure!(
+				now >= auction.commit_end && now < auction.reveal_end,
+				Error::<T>::NotInRevealPhase
+			);
+			let (commitment, revealed) =
+				Self::sealed_bids(kitty_id, &sender).ok_or(Error::<T>::NoBidCommitment)?;
+			ensure!(revealed.is_none(), Error::<T>::BidAlreadyRevealed);
+			ensure!(
+				(&sender, kitty_id, amount, salt).using_encoded(blake2_256) == commitment,
+				Error::<T>::BadBidReveal
+			);
+
+			T::Currency::reserve(&sender, amount)?;
+			T::Currency::unreserve(&sender, auction.bid_deposit);
+			<SealedBids<T>>::insert(kitty_id, &sender, (commitment, Some(amount)));
+
+			Self::deposit_event(RawEvent::BidRevealed(sender, kitty_id, amount));
+			Ok(())
+		}
+
+		/// Re-roll a newborn kitty's DNA for a burned fee. Allowed once per
+		/// kitty within the re-roll window after birth; generation and
+		/// pedigree are untouched.
+		#[weight = T::DbWeight::get().reads_writes(7, 5) + 10_000]
+		pub fn reroll(origin, kitty_id: T::KittyIndex) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let kitty = Self::kitties(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(Self::kitty_owner(kitty_id) == Some(sender.clone()), Error::<T>::NotKittyOwner);
+			ensure!(!Self::rerolled(kitty_id), Error::<T>::AlreadyRerolled);
+			ensure!(
+				<system::Module<T>>::block_number()
+					<= Self::born_at(kitty_id) + T::RerollWindow::get(),
+				Error::<T>::RerollWindowClosed
+			);
+
+			let dna = Self::unique_dna(Self::random_value(&sender))?;
+			let _ = T::Currency::withdraw(
+				&sender,
+				T::RerollFee::get(),
+				WithdrawReason::Fee.into(),
+				ExistenceRequirement::KeepAlive,
+			)?;
+			<DnaIndex<T>>::remove(kitty.0);
+			<DnaIndex<T>>::insert(dna, kitty_id);
+			<Kitties<T>>::insert(kitty_id, Kitty(dna));
+			Self::refresh_stat_sheet(kitty_id);
+			Rerolled::<T>::insert(kitty_id, true);
+			Self::update_leaderboard(kitty_id);
+
+			Self::deposit_event(RawEvent::Rerolled(sender, kitty_id));
+			Ok(())
+		}
+
+		/// Add an account to or remove it from the blacklist. Admin-only.
+		/// Blacklisted accounts cannot mint, breed, list or receive
+		/// kitties; their existing holdings are untouched.
+		#[weight = T::DbWeight::get().reads_writes(1, 1) + 10_000]
+		pub fn set_blacklist(origin, who: T::AccountId, blacklisted: bool) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			if blacklisted {
+				<Blacklist<T>>::insert(&who, true);
+			} else {
+				<Blacklist<T>>::remove(&who);
+			}
+			Self::deposit_event(RawEvent::BlacklistUpdated(who, blacklisted));
+			Ok(())
+		}
+
+		/// Approve or revoke a charity that listings may route donations
+		/// to. Requires the admin origin.
+		#[weight = T::DbWeight::get().reads_writes(0, 1) + 10_000]
+		pub fn set_charity(origin, who: T::AccountId, approved: bool) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			if approved {
+				<Charities<T>>::insert(&who, true);
+			} else {
+				<Charities<T>>::remove(&who);
+			}
+			Self::deposit_event(RawEvent::CharityUpdated(who, approved));
+			Ok(())
+		}
+
+		/// Mark an id range or edition as a verified official drop.
+		/// Requires the admin origin — in practice the council curating
+		/// the registry.
+		#[weight = FunctionOf(
+			|(name, _): (&Vec<u8>, &VerifiedScope<T::KittyIndex>)|
+				T::DbWeight::get().reads_writes(1, 2) + 1_000 * (name.len() as Weight + 10),
+			DispatchClass::Normal,
+			Pays::Yes,
+		)]
+		pub fn verify_collection(origin, name: Vec<u8>, scope: VerifiedScope<T::KittyIndex>) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			if let VerifiedScope::Range(from, to) = &scope {
+				ensure!(from <= to, Error::<T>::InvalidVerifiedRange);
+			}
+
+			let collection_id = Self::next_verified_collection_id();
+			NextVerifiedCollectionId::put(collection_id + 1);
+			<VerifiedCollections<T>>::insert(collection_id, (name, scope.clone()));
+
+			Self::deposit_event(RawEvent::CollectionVerified(collection_id, scope));
+			Ok(())
+		}
+
+		/// Strike a collection from the verified registry. Requires the
+		/// admin origin.
+		#[weight = T::DbWeight::get().reads_writes(1, 1) + 10_000]
+		pub fn revoke_collection(origin, collection_id: u32) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(
+				<VerifiedCollections<T>>::contains_key(collection_id),
+				Error::<T>::VerifiedCollectionNotFound
+			);
+			<VerifiedCollections<T>>::remove(collection_id);
+			Self::deposit_event(RawEvent::CollectionRevoked(collection_id));
+			Ok(())
+		}
+
+		/// Found a cattery under the sender's account. The name is a free
+		/// label for the breeder's brand; unlike kitty names it does not
+		/// have to be unique.
+		#[weight = FunctionOf(
+			|(name,): (&Vec<u8>,)|
+				T::DbWeight::get().reads_writes(1, 2) + 1_000 * (name.len() as Weight + 10),
+			DispatchClass::Normal,
+			Pays::Yes,
+		)]
+		pub fn create_cattery(origin, name: Vec<u8>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(
+				name.len() <= T::MaxNameLength::get() as usize,
+				Error::<T>::NameTooLong
+			);
+
+			let cattery_id = Self::next_cattery_id();
+			NextCatteryId::put(cattery_id + 1);
+			<Catteries<T>>::insert(
+				cattery_id,
+				Cattery { owner: sender.clone(), name, members: 0 },
+			);
+
+			Self::deposit_event(RawEvent::CatteryCreated(sender, cattery_id));
+			Ok(())
+		}
+
+		/// Assign one of the sender's kitties to one of the sender's
+		/// catteries, leaving its previous cattery if it had one.
+		#[weight = T::DbWeight::get().reads_writes(3, 3) + 25_000]
+		pub fn assign_to_cattery(origin, kitty_id: T::KittyIndex, cattery_id: u32) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(Self::kitty_owner(kitty_id) == Some(sender.clone()), Error::<T>::NotKittyOwner);
+			let cattery = Self::cattery(cattery_id).ok_or(Error::<T>::CatteryNotFound)?;
+			ensure!(cattery.owner == sender, Error::<T>::NotCatteryOwner);
+			let previous = Self::kitty_cattery(kitty_id);
+			ensure!(previous != Some(cattery_id), Error::<T>::AlreadyInCattery);
+
+			if let Some(previous) = previous {
+				Self::leave_cattery(kitty_id, previous);
+			}
+			<KittyCattery<T>>::insert(kitty_id, cattery_id);
+			<Catteries<T>>::mutate(cattery_id, |cattery| {
+				if let Some(cattery) = cattery {
+					cattery.members += 1;
+				}
+			});
+
+			Self::deposit_event(RawEvent::AssignedToCattery(kitty_id, cattery_id));
+			Ok(())
+		}
+
+		/// Take one of the sender's kitties out of its cattery.
+		#[weight = T::DbWeight::get().reads_writes(2, 2) + 20_000]
+		pub fn remove_from_cattery(origin, kitty_id: T::KittyIndex) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(Self::kitty_owner(kitty_id) == Some(sender.clone()), Error::<T>::NotKittyOwner);
+			let cattery_id = Self::kitty_cattery(kitty_id).ok_or(Error::<T>::NotInCattery)?;
+
+			Self::leave_cattery(kitty_id, cattery_id);
+			Ok(())
+		}
+
+		/// Grant a role in the sender's cattery, or clear it with `None`.
+		/// A manager may list and delist the cattery's kitties, a breeder
+		/// may breed them, and a viewer holds no powers. Only the founder
+		/// may set roles.
+		#[weight = T::DbWeight::get().reads_writes(1, 1) + 15_000]
+		pub fn set_cattery_role(origin, cattery_id: u32, who: T::AccountId, role: Option<CatteryRole>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let cattery = Self::cattery(cattery_id).ok_or(Error::<T>::CatteryNotFound)?;
+			ensure!(cattery.owner == sender, Error::<T>::NotCatteryOwner);
+
+			match role {
+				Some(role) => <CatteryRoles<T>>::insert(cattery_id, &who, role),
+				None => <CatteryRoles<T>>::remove(cattery_id, &who),
+			}
+			Self::deposit_event(RawEvent::CatteryRoleSet(cattery_id, who, role));
+			Ok(())
+		}
+
+		/// Open, retune or close the buyback program. Requires the admin
+		/// origin. The schedule is `(base, per_rarity_point)`; `None`
+		/// closes the program and leaves any pool balance in place for a
+		/// later reopening.
+		#[weight = T::DbWeight::get().reads_writes(0, 1) + 10_000]
+		pub fn set_buyback_schedule(origin, schedule: Option<(BalanceOf<T>, BalanceOf<T>)>) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			match schedule {
+				Some(schedule) => <BuybackSchedule<T>>::put(schedule),
+				None => <BuybackSchedule<T>>::kill(),
+			}
+			Self::deposit_event(RawEvent::BuybackScheduleSet(schedule));
+			Ok(())
+		}
+
+		/// Move funds from the sender into the buyback pool. Anyone may
+		/// top it up; in practice this is where a treasury spend lands.
+		#[weight = T::DbWeight::get().reads_writes(2, 2) + 10_000]
+		pub fn fund_buyback(origin, amount: BalanceOf<T>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			T::Currency::transfer(
+				&sender,
+				&Self::buyback_pool_account(),
+				amount,
+				ExistenceRequirement::KeepAlive,
+			)?;
+			Self::deposit_event(RawEvent::BuybackFunded(sender, amount));
+			Ok(())
+		}
+
+		/// Burn one of the sender's kitties into the buyback program,
+		/// receiving the scheduled payout from the pool. A price floor
+		/// and supply sink in one: the kitty is gone for good, and the
+		/// payout scales with rarity and shrinks with generation.
+		#[weight = T::DbWeight::get().reads_writes(14, 28) + 10_000]
+		pub fn redeem_burn(origin, kitty_id: T::KittyIndex) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(owner == sender, Error::<T>::NotKittyOwner);
+			ensure!(Self::bridged_out(kitty_id).is_none(), Error::<T>::KittyBridgedOut);
+			ensure!(Self::kitty_lock(kitty_id).is_none(), Error::<T>::KittyLocked);
+			ensure!(Self::escrows(kitty_id).is_none(), Error::<T>::KittyInEscrow);
+			ensure!(Self::fraction_shares(kitty_id).is_none(), Error::<T>::KittyFractionalized);
+			ensure!(Self::hibernating(kitty_id).is_none(), Error::<T>::KittyHibernating);
+			ensure!(!Self::is_departed(kitty_id), Error::<T>::KittyDeparted);
+			ensure!(Self::auctions(kitty_id).is_none(), Error::<T>::KittyAlreadyOnAuction);
+			ensure!(Self::sealed_auctions(kitty_id).is_none(), Error::<T>::KittyAlreadyOnAuction);
+			Self::ensure_not_soulbound(kitty_id)?;
+			let payout = Self::buyback_quote(kitty_id).ok_or(Error::<T>::BuybackClosed)?;
+
+			// The payout leaves the pool before any state is touched; a
+			// dry pool fails here and the kitty stays put.
+			T::Currency::transfer(
+				&Self::buyback_pool_account(),
+				&sender,
+				payout,
+				ExistenceRequirement::AllowDeath,
+			).map_err(|_| Error::<T>::BuybackPoolDry)?;
+			Self::remove_kitty(&sender, kitty_id);
+
+			Self::deposit_event(RawEvent::BuybackRedeemed(sender, kitty_id, payout));
+			Ok(())
+		}
+
+		/// Replace the breeding difficulty table. Admin-only. Rows are
+		/// `(supply_at_least, fee_percent, cooldown_percent)` and must have
+		/// strictly ascending thresholds; the effective fee and cooldown are
+		/// recomputed lazily whenever a breeding runs, so no migration of
+		/// in-flight state is needed.
+		#[weight = FunctionOf(
+			|(table,): (&Vec<(u32, u32, u32)>,)|
+				T::DbWeight::get().writes(1) + 5_000 * (table.len() as Weight + 1),
+			DispatchClass::Operational,
+			Pays::Yes,
+		)]
+		pub fn set_difficulty_table(origin, table: Vec<(u32, u32, u32)>) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(
+				table.len() as u32 <= T::MaxDifficultyRows::get()
+					&& table.windows(2).all(|pair| pair[0].0 < pair[1].0),
+				Error::<T>::InvalidDifficultyTable
+			);
+			DifficultyTable::put(&table);
+			Self::deposit_event(RawEvent::DifficultyTableSet(table));
+			Ok(())
+		}
+
+		/// Set or clear the governed base breeding fee. `AdminOrigin`
+		/// only; `None` returns to the compiled default.
+		#[weight = T::DbWeight::get().writes(1) + 10_000]
+		pub fn set_breed_fee(origin, fee: Option<BalanceOf<T>>) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			match fee {
+				Some(fee) => <BreedFeeOverride<T>>::put(fee),
+				None => <BreedFeeOverride<T>>::kill(),
+			}
+			Self::deposit_event(RawEvent::BreedFeeSet(fee));
+			Ok(())
+		}
+
+		/// Set or clear the governed expedited-creation fee. `AdminOrigin`
+		/// only; `None` returns to the compiled default.
+		#[weight = T::DbWeight::get().writes(1) + 10_000]
+		pub fn set_create_fee(origin, fee: Option<BalanceOf<T>>) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			match fee {
+				Some(fee) => <CreateFeeOverride<T>>::put(fee),
+				None => <CreateFeeOverride<T>>::kill(),
+			}
+			Self::deposit_event(RawEvent::CreateFeeSet(fee));
+			Ok(())
+		}
+
+		/// Set or clear the governed base breeding cooldown. `AdminOrigin`
+		/// only; `None` returns to the compiled default.
+		#[weight = T::DbWeight::get().writes(1) + 10_000]
+		pub fn set_breed_cooldown(origin, cooldown: Option<T::BlockNumber>) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			match cooldown {
+				Some(cooldown) => <BreedCooldownOverride<T>>::put(cooldown),
+				None => <BreedCooldownOverride<T>>::kill(),
+			}
+			Self::deposit_event(RawEvent::BreedCooldownSet(cooldown));
+			Ok(())
+		}
+
+		/// Set the chance that a bred kitten mutates one gene byte.
+		/// `AdminOrigin` only.
+		#[weight = T::DbWeight::get().writes(1) + 10_000]
+		pub fn set_mutation_rate(origin, rate: Percent) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			MutationRate::put(rate);
+			Self::deposit_event(RawEvent::MutationRateSet(rate));
+			Ok(())
+		}
+
+		/// Set or clear the governed market commission, capped at half the
+		/// sale price. `AdminOrigin` only; `None` returns to the compiled
+		/// default.
+		#[weight = T::DbWeight::get().writes(1) + 10_000]
+		pub fn set_market_fee(origin, fee: Option<Percent>) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			if let Some(fee) = fee {
+				ensure!(fee <= Percent::from_percent(50), Error::<T>::MarketFeeTooHigh);
+				MarketFeeOverride::put(fee);
+			} else {
+				MarketFeeOverride::kill();
+			}
+			Self::deposit_event(RawEvent::MarketFeeSet(fee));
+			Ok(())
+		}
+
+		/// Rewrite a kitty's DNA outright. Requires the force origin; meant
+		/// for fixing genetics bugs or running special events. Refreshes the
+		/// DNA index and re-evaluates rarity milestones for the current
+		/// owner; stats and attributes are derived from DNA and need no
+		/// separate refresh.
+		#[weight = T::DbWeight::get().reads_writes(4, 5) + 10_000]
+		pub fn force_set_dna(origin, kitty_id: T::KittyIndex, new_dna: [u8; 16]) -> DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+			let kitty = Self::kitties(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(
+				Self::dna_index(new_dna).map_or(true, |holder| holder == kitty_id),
+				Error::<T>::DuplicateDna
+			);
+
+			<DnaIndex<T>>::remove(kitty.0);
+			<DnaIndex<T>>::insert(new_dna, kitty_id);
+			<Kitties<T>>::insert(kitty_id, Kitty(new_dna));
+			if let Some(owner) = Self::kitty_owner(kitty_id) {
+				Self::note_ownership_milestones(&owner, kitty_id);
+			}
+			Self::update_leaderboard(kitty_id);
+
+			Self::deposit_event(RawEvent::DnaForceSet(kitty_id, kitty.0, new_dna));
+			Ok(())
+		}
+
+		/// Name a kitty owned by the sender. Names are exclusive; reserved
+		/// names require an unredeemed auction claim, which is consumed.
+		#[weight = T::DbWeight::get().reads_writes(5, 3) + 10_000]
+		pub fn set_name(origin, kitty_id: T::KittyIndex, name: Vec<u8>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(Self::kitty_owner(kitty_id) == Some(sender.clone()), Error::<T>::NotKittyOwner);
+			ensure!(
+				name.len() <= T::MaxNameLength::get() as usize,
+				Error::<T>::NameTooLong
+			);
+			ensure!(Self::name_taken(&name).is_none(), Error::<T>::NameAlreadyTaken);
+			if Self::reserved_names(&name) {
+				ensure!(
+					Self::name_claims(&name) == Some(sender.clone()),
+					Error::<T>::NameIsReserved
+				);
+				<NameClaims<T>>::remove(&name);
+			}
+
+			if let Some(old) = <Names<T>>::take(kitty_id) {
+				<NameTaken<T>>::remove(old);
+			}
+			<NameTaken<T>>::insert(&name, kitty_id);
+			<Names<T>>::insert(kitty_id, &name);
+			Self::deposit_event(RawEvent::NameSet(sender, kitty_id, name));
+			Ok(())
+		}
+
+		/// Add a name to the reserved list. Requires the admin origin.
+		/// Already-assigned names are unaffected; reservation only governs
+		/// future naming.
+		#[weight = T::DbWeight::get().reads_writes(1, 1) + 10_000]
+		pub fn reserve_name(origin, name: Vec<u8>) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(
+				name.len() <= T::MaxNameLength::get() as usize,
+				Error::<T>::NameTooLong
+			);
+			ReservedNames::insert(&name, true);
+			Self::deposit_event(RawEvent::NameReserved(name));
+			Ok(())
+		}
+
+		/// Open an auction for a reserved name. Requires the admin origin.
+		/// The winner
+		/// receives a claim redeemable via `set_name`; the proceeds go to
+		/// the market-fee beneficiary or are burned.
+		#[weight = T::DbWeight::get().reads_writes(3, 2) + 10_000]
+		pub fn start_name_auction(origin, name: Vec<u8>, reserve_price: BalanceOf<T>, duration: T::BlockNumber) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(Self::reserved_names(&name), Error::<T>::NameIsReserved);
+			ensure!(Self::name_auctions(&name).is_none(), Error::<T>::NameAuctionExists);
+			ensure!(!duration.is_zero(), Error::<T>::InvalidAuctionDuration);
+
+			let end = <system::Module<T>>::block_number() + duration;
+			ensure!(
+				(Self::name_auctions_by_end(end).len() as u32) < T::MaxSameBlockEndings::get(),
+				Error::<T>::TooManyEndingAtBlock
+			);
+			<NameAuctions<T>>::insert(&name, NameAuction {
+				reserve_price,
+				end,
+				top_bidder: None,
+				top_bid: Zero::zero(),
+			});
+			<NameAuctionsByEnd<T>>::mutate(end, |names| names.push(name.clone()));
+
+			Self::deposit_event(RawEvent::NameAuctionStarted(name, reserve_price, end));
+			Ok(())
+		}
+
+		/// Bid on a running name auction. The bid is reserved; the previous
+		/// top bidder is refunded immediately.
+		#[weight = T::DbWeight::get().reads_writes(2, 2) + 10_000]
+		pub fn bid_name(origin, name: Vec<u8>, amount: BalanceOf<T>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let mut auction = Self::name_auctions(&name).ok_or(Error::<T>::NameAuctionNotFound)?;
+			ensure!(<system::Module<T>>::block_number() < auction.end, Error::<T>::AuctionEnded);
+			ensure!(
+				amount >= auction.reserve_price && amount > auction.top_bid,
+				Error::<T>::BidTooLow
+			);
+
+			T::Currency::reserve(&sender, amount)?;
+			if let Some(prev) = auction.top_bidder.take() {
+				T::Currency::unreserve(&prev, auction.top_bid);
+			}
+			auction.top_bidder = Some(sender.clone());
+			auction.top_bid = amount;
+			<NameAuctions<T>>::insert(&name, auction);
+
+			Self::deposit_event(RawEvent::NameBidPlaced(sender, name, amount));
+			Ok(())
+		}
+
+		/// Tip the current owner of a kitty. The amount goes straight to the
+		/// owner; the kitty's lifetime-tips counter feeds popularity rankings.
+		#[weight = T::DbWeight::get().reads_writes(3, 2) + 10_000]
+		pub fn tip_kitty(origin, kitty_id: T::KittyIndex, amount: BalanceOf<T>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(owner != sender, Error::<T>::OwnKittyMarketAction);
+			ensure!(!amount.is_zero(), Error::<T>::ZeroTip);
+
+			T::Currency::transfer(&sender, &owner, amount, ExistenceRequirement::KeepAlive)?;
+			<LifetimeTips<T>>::mutate(kitty_id, |total| *total = total.saturating_add(amount));
+
+			Self::deposit_event(RawEvent::Tipped(sender, kitty_id, amount));
+			Ok(())
+		}
+
+		/// Set or clear the breeding season. Requires the admin origin.
+		/// With a season of
+		/// `(open_length, period)`, breeding is only allowed during the
+		/// first `open_length` blocks of every `period`-block cycle.
+		#[weight = T::DbWeight::get().reads_writes(0, 1) + 10_000]
+		pub fn set_breeding_season(origin, season: Option<(T::BlockNumber, T::BlockNumber)>) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			if let Some((open, period)) = season {
+				ensure!(
+					!period.is_zero() && !open.is_zero() && open <= period,
+					Error::<T>::InvalidBreedingSeason
+				);
+			}
+
+			match season {
+				Some(season) => <BreedingSeason<T>>::put(season),
+				None => <BreedingSeason<T>>::kill(),
+			}
+			Self::deposit_event(RawEvent::BreedingSeasonSet(season));
+			Ok(())
+		}
+
+		/// Register an equipment item and its stat bonuses. Requires the
+		/// admin origin. Re-registering an id does not retroactively
+		/// refresh cached stat sheets; wearers pick the new bonuses up on
+		/// their next equipment change.
+		#[weight = T::DbWeight::get().reads_writes(1, 1) + 10_000]
+		pub fn register_item(origin, item_id: u32, bonuses: KittyStats) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			Items::insert(item_id, bonuses);
+			Self::deposit_event(RawEvent::ItemRegistered(item_id));
+			Ok(())
+		}
+
+		/// Equip a registered item on a kitty owned by the sender.
+		#[weight = T::DbWeight::get().reads_writes(4, 1) + 10_000]
+		pub fn equip(origin, kitty_id: T::KittyIndex, item_id: u32) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(Self::kitty_owner(kitty_id) == Some(sender.clone()), Error::<T>::NotKittyOwner);
+			ensure!(Items::contains_key(item_id), Error::<T>::UnknownItem);
+			let mut worn = Self::equipped(kitty_id);
+			ensure!(!worn.contains(&item_id), Error::<T>::ItemAlreadyEquipped);
+			ensure!(
+				worn.len() < T::MaxEquippedItems::get() as usize,
+				Error::<T>::TooManyEquippedItems
+			);
+
+			worn.push(item_id);
+			<Equipped<T>>::insert(kitty_id, worn);
+			Self::refresh_stat_sheet(kitty_id);
+			Self::deposit_event(RawEvent::ItemEquipped(sender, kitty_id, item_id));
+			Ok(())
+		}
+
+		/// Take an item off a kitty owned by the sender.
+		#[weight = T::DbWeight::get().reads_writes(2, 1) + 10_000]
+		pub fn unequip(origin, kitty_id: T::KittyIndex, item_id: u32) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(Self::kitty_owner(kitty_id) == Some(sender.clone()), Error::<T>::NotKittyOwner);
+			let mut worn = Self::equipped(kitty_id);
+			ensure!(worn.contains(&item_id), Error::<T>::ItemNotEquipped);
+
+			worn.retain(|id| *id != item_id);
+			<Equipped<T>>::insert(kitty_id, worn);
+			Self::refresh_stat_sheet(kitty_id);
+			Self::deposit_event(RawEvent::ItemUnequipped(sender, kitty_id, item_id));
+			Ok(())
+		}
+
+		/// Reap a departed kitty, refunding its deposit to the owner. Anyone
+		/// may call this; it only removes per-kitty state, the historical
+		/// mint count is unaffected.
+		#[weight = T::DbWeight::get().reads_writes(8, 24) + 10_000]
+		pub fn bury_departed(origin, kitty_id: T::KittyIndex) -> DispatchResult {
+			let _ = ensure_signed(origin)?;
+			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(Self::is_departed(kitty_id), Error::<T>::KittyNotDeparted);
+			ensure!(Self::kitty_lock(kitty_id).is_none(), Error::<T>::KittyLocked);
+			ensure!(Self::escrows(kitty_id).is_none(), Error::<T>::KittyInEscrow);
+			ensure!(Self::auctions(kitty_id).is_none(), Error::<T>::KittyAlreadyOnAuction);
+			ensure!(Self::sealed_auctions(kitty_id).is_none(), Error::<T>::KittyAlreadyOnAuction);
+
+			Self::remove_kitty(&owner, kitty_id);
+
+			Self::deposit_event(RawEvent::Departed(owner, kitty_id));
+			Ok(())
+		}
+
+		/// Renew a kitty's state rent for `periods` periods, burning the
+		/// fee. Anyone may pay anyone's rent; lapsed rent resumes from now
+		/// instead of stacking onto the past.
+		#[weight = T::DbWeight::get().reads_writes(3, 1) + 10_000]
+		pub fn pay_rent(origin, kitty_id: T::KittyIndex, periods: u32) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(<Kitties<T>>::contains_key(kitty_id), Error::<T>::InvalidKittyId);
+			ensure!(periods > 0, Error::<T>::ZeroRentPeriods);
+
+			let _ = T::Currency::withdraw(
+				&sender,
+				T::RentPerPeriod::get().saturating_mul(periods.into()),
+				WithdrawReason::Fee.into(),
+				ExistenceRequirement::KeepAlive,
+			)?;
+			let now = <system::Module<T>>::block_number();
+			let base = Self::rent_paid_until(kitty_id).max(now);
+			let until = base + T::RentPeriod::get().saturating_mul(periods.into());
+			<RentPaidUntil<T>>::insert(kitty_id, until);
+
+			Self::deposit_event(RawEvent::RentPaid(sender, kitty_id, until));
+			Ok(())
+		}
+
+		/// Archive a kitty whose rent and grace period have both lapsed.
+		/// Anyone may call this: the reaper takes the finder's fee out of
+		/// the kitty deposit, the owner keeps the rest, and a tombstone
+		/// with the DNA and final owner stays behind for explorers.
+		#[weight = T::DbWeight::get().reads_writes(10, 26) + 10_000]
+		pub fn reap_kitty(origin, kitty_id: T::KittyIndex) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let kitty = Self::kitties(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			let now = <system::Module<T>>::block_number();
+			ensure!(
+				now > Self::rent_paid_until(kitty_id) + T::RentGracePeriod::get(),
+				Error::<T>::RentNotLapsed
+			);
+			ensure!(Self::kitty_lock(kitty_id).is_none(), Error::<T>::KittyLocked);
+			ensure!(Self::escrows(kitty_id).is_none(), Error::<T>::KittyInEscrow);
+			ensure!(Self::auctions(kitty_id).is_none(), Error::<T>::KittyAlreadyOnAuction);
+			ensure!(Self::sealed_auctions(kitty_id).is_none(), Error::<T>::KittyAlreadyOnAuction);
+			ensure!(Self::hibernating(kitty_id).is_none(), Error::<T>::KittyHibernating);
+
+			// The finder's fee comes straight out of the reserved deposit;
+			// `remove_kitty` hands the owner whatever is left.
+			let fee = T::ReapFinderFee::get();
+			let _ = T::Currency::repatriate_reserved(&owner, &sender, fee)?;
+			<ArchivedKitties<T>>::insert(kitty_id, (kitty.0, owner.clone()));
+			Self::remove_kitty(&owner, kitty_id);
+
+			Self::deposit_event(RawEvent::KittyReaped(sender, kitty_id, fee));
+			Ok(())
+		}
+
+		/// Put a kitty the sender owns into hibernation: vitals stop
+		/// decaying and the rent clock pauses, but the kitty cannot
+		/// breed, battle or be listed until woken, and not before
+		/// `MinHibernationPeriod` blocks have passed.
+		#[weight = T::DbWeight::get().reads_writes(8, 2) + 10_000]
+		pub fn hibernate(origin, kitty_id: T::KittyIndex) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(owner == sender, Error::<T>::NotKittyOwner);
+			ensure!(Self::hibernating(kitty_id).is_none(), Error::<T>::KittyHibernating);
+			ensure!(Self::kitty_lock(kitty_id).is_none(), Error::<T>::KittyLocked);
+			ensure!(Self::escrows(kitty_id).is_none(), Error::<T>::KittyInEscrow);
+			ensure!(Self::fraction_shares(kitty_id).is_none(), Error::<T>::KittyFractionalized);
+			ensure!(Self::bridged_out(kitty_id).is_none(), Error::<T>::KittyBridgedOut);
+			ensure!(!Self::is_departed(kitty_id), Error::<T>::KittyDeparted);
+
+			let now = <system::Module<T>>::block_number();
+			// Snapshot the decayed energy so it freezes at today's value.
+			<Vitals<T>>::insert(kitty_id, KittyVitals {
+				energy: Self::current_energy(kitty_id),
+				updated_at: now,
+			});
+			<Hibernating<T>>::insert(kitty_id, now);
+			Self::deposit_event(RawEvent::Hibernated(sender, kitty_id));
+			Ok(())
+		}
+
+		/// Wake a hibernating kitty. The rent already paid is pushed out
+		/// by the time slept, so hibernation costs no rent.
+		#[weight = T::DbWeight::get().reads_writes(4, 3) + 10_000]
+		pub fn wake(origin, kitty_id: T::KittyIndex) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(owner == sender, Error::<T>::NotKittyOwner);
+			let slept_at = Self::hibernating(kitty_id).ok_or(Error::<T>::NotHibernating)?;
+			let now = <system::Module<T>>::block_number();
+			ensure!(
+				now >= slept_at + T::MinHibernationPeriod::get(),
+				Error::<T>::HibernationTooShort
+			);
+
+			<Hibernating<T>>::remove(kitty_id);
+			// Decay resumes from the wake block; the frozen energy value
+			// carries over unchanged.
+			<Vitals<T>>::mutate(kitty_id, |vitals| vitals.updated_at = now);
+			<RentPaidUntil<T>>::mutate(kitty_id, |until| *until = *until + (now - slept_at));
+			Self::deposit_event(RawEvent::Awakened(sender, kitty_id));
+			Ok(())
+		}
+
+		/// Fractionalize a kitty into `total_shares` fungible shares, all
+		/// credited to the owner, so an expensive kitty can be co-invested.
+		/// The kitty stays in the owner's custody but cannot move while
+		/// the shares exist.
+		#[weight = T::DbWeight::get().reads_writes(9, 3) + 10_000]
+		pub fn fractionalize(origin, kitty_id: T::KittyIndex, total_shares: u128) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(owner == sender, Error::<T>::NotKittyOwner);
+			ensure!(total_shares >= 2, Error::<T>::TooFewShares);
+			ensure!(Self::fraction_shares(kitty_id).is_none(), Error::<T>::KittyFractionalized);
+			ensure!(Self::bridged_out(kitty_id).is_none(), Error::<T>::KittyBridgedOut);
+			ensure!(Self::kitty_lock(kitty_id).is_none(), Error::<T>::KittyLocked);
+			ensure!(Self::escrows(kitty_id).is_none(), Error::<T>::KittyInEscrow);
+			ensure!(!Self::is_departed(kitty_id), Error::<T>::KittyDeparted);
+			ensure!(Self::auctions(kitty_id).is_none(), Error::<T>::KittyAlreadyOnAuction);
+			ensure!(Self::sealed_auctions(kitty_id).is_none(), Error::<T>::KittyAlreadyOnAuction);
+			Self::ensure_not_soulbound(kitty_id)?;
+
+			let asset = T::Fungibles::issue(&sender, total_shares)?;
+			Self::take_listing(kitty_id);
+			<FractionShares<T>>::insert(kitty_id, (asset, total_shares));
+			// The lock stands until `redeem`, so transfer and burn paths
+			// that only check locks cannot pull the kitty out from under
+			// its shareholders.
+			<KittyLocks<T>>::insert(kitty_id, Self::fraction_lock_account());
+
+			Self::deposit_event(RawEvent::Fractionalized(sender, kitty_id, total_shares));
+			Ok(())
+		}
+
+		/// Redeem a fractionalized kitty by presenting its full share
+		/// supply. The shares are retired and the kitty moves to the
+		/// redeemer, deposit and all.
+		#[weight = T::DbWeight::get().reads_writes(9, 10) + 10_000]
+		pub fn redeem(origin, kitty_id: T::KittyIndex) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let (asset, total) =
+				Self::fraction_shares(kitty_id).ok_or(Error::<T>::NotFractionalized)?;
+			ensure!(
+				T::Fungibles::balance_of(&asset, &sender) == total,
+				Error::<T>::NotAllShares
+			);
+			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			if owner != sender {
+				Self::ensure_can_hold_one_more(&sender)?;
+				T::Currency::reserve(&sender, T::KittyDeposit::get())?;
+			}
+
+			T::Fungibles::retire(&asset, &sender)?;
+			<FractionShares<T>>::remove(kitty_id);
+			<KittyLocks<T>>::remove(kitty_id);
+			if owner != sender {
+				T::Currency::unreserve(&owner, T::KittyDeposit::get());
+				Self::do_transfer(&owner, &sender, kitty_id);
+				Self::note_provenance(kitty_id, &sender, TransferKind::Transfer);
+			}
+
+			Self::deposit_event(RawEvent::Redeemed(sender, kitty_id));
+			Ok(())
+		}
+
+		/// Register a bridge relayer. `AdminOrigin` only.
+		#[weight = T::DbWeight::get().reads_writes(1, 1) + 10_000]
+		pub fn add_relayer(origin, who: T::AccountId) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(!Self::is_relayer(&who), Error::<T>::AlreadyRelayer);
+
+			<Relayers<T>>::insert(&who, true);
+			Self::deposit_event(RawEvent::RelayerAdded(who));
+			Ok(())
+		}
+
+		/// Retire a bridge relayer. `AdminOrigin` only. Pending approvals
+		/// by the retired relayer stay counted; shrinking the set does not
+		/// retroactively invalidate votes already cast.
+		#[weight = T::DbWeight::get().reads_writes(1, 1) + 10_000]
+		pub fn remove_relayer(origin, who: T::AccountId) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(Self::is_relayer(&who), Error::<T>::NotRelayer);
+
+			<Relayers<T>>::remove(&who);
+			Self::deposit_event(RawEvent::RelayerRemoved(who));
+			Ok(())
+		}
+
+		/// Lock a kitty for the Ethereum bridge. The emitted event carries
+		/// the target address; the relayer set consumes it and mints the
+		/// wrapped ERC-721 token there. Until the kitty returns it is inert
+		/// locally: it cannot move, trade or breed.
+		#[weight = T::DbWeight::get().reads_writes(10, 3) + 10_000]
+		pub fn bridge_out(origin, kitty_id: T::KittyIndex, eth_address: [u8; 20]) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(owner == sender, Error::<T>::NotKittyOwner);
+			ensure!(Self::bridged_out(kitty_id).is_none(), Error::<T>::KittyBridgedOut);
+			ensure!(Self::kitty_lock(kitty_id).is_none(), Error::<T>::KittyLocked);
+			ensure!(Self::escrows(kitty_id).is_none(), Error::<T>::KittyInEscrow);
+			ensure!(Self::fraction_shares(kitty_id).is_none(), Error::<T>::KittyFractionalized);
+			ensure!(!Self::is_departed(kitty_id), Error::<T>::KittyDeparted);
+			ensure!(Self::auctions(kitty_id).is_none(), Error::<T>::KittyAlreadyOnAuction);
+			ensure!(Self::sealed_auctions(kitty_id).is_none(), Error::<T>::KittyAlreadyOnAuction);
+			Self::ensure_not_soulbound(kitty_id)?;
+
+			Self::take_listing(kitty_id);
+			<BridgedKitties<T>>::insert(kitty_id, (sender.clone(), eth_address));
+			// The lock stands until the relayers release the kitty, so
+			// rent lapse, reaping and every other lock-checking path
+			// leaves the Ethereum side's backing untouched.
+			<KittyLocks<T>>::insert(kitty_id, Self::bridge_lock_account());
+
+			Self::deposit_event(RawEvent::BridgedOut(sender, kitty_id, eth_address));
+			Ok(())
+		}
+
+		/// Exit a kitty to a successor chain: the kitty is burned here and
+		/// a commitment to its final owner, DNA and pedigree is folded
+		/// into the exits root, against which the successor chain can
+		/// verify a trust-minimized migration claim. Irreversible.
+		#[weight = T::DbWeight::get().reads_writes(12, 28) + 10_000]
+		pub fn generate_exit_proof(origin, kitty_id: T::KittyIndex) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let kitty = Self::kitties(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(owner == sender, Error::<T>::NotKittyOwner);
+			ensure!(Self::bridged_out(kitty_id).is_none(), Error::<T>::KittyBridgedOut);
+			ensure!(Self::kitty_lock(kitty_id).is_none(), Error::<T>::KittyLocked);
+			ensure!(Self::escrows(kitty_id).is_none(), Error::<T>::KittyInEscrow);
+			ensure!(Self::fraction_shares(kitty_id).is_none(), Error::<T>::KittyFractionalized);
+			ensure!(Self::hibernating(kitty_id).is_none(), Error::<T>::KittyHibernating);
+			ensure!(!Self::is_departed(kitty_id), Error::<T>::KittyDeparted);
+			ensure!(Self::auctions(kitty_id).is_none(), Error::<T>::KittyAlreadyOnAuction);
+			ensure!(Self::sealed_auctions(kitty_id).is_none(), Error::<T>::KittyAlreadyOnAuction);
+			Self::ensure_not_soulbound(kitty_id)?;
+
+			let commitment = ExitCommitment {
+				owner: sender.clone(),
+				dna: kitty.0,
+				pedigree_hash: blake2_256(&Self::pedigree(kitty_id).encode()),
+				exited_at: <system::Module<T>>::block_number(),
+			};
+			let leaf = blake2_256(&(kitty_id, &commitment).encode());
+			let root = blake2_256(&(Self::exits_root(), leaf).encode());
+			Self::remove_kitty(&sender, kitty_id);
+			<Exits<T>>::insert(kitty_id, commitment);
+			ExitsRoot::put(root);
+			ExitCount::mutate(|count| *count += 1);
+
+			Self::deposit_event(RawEvent::ExitProofGenerated(sender, kitty_id, leaf, root));
+			Ok(())
+		}
+
+		/// Approve releasing a bridged kitty, as a relayer attesting that
+		/// the wrapped token was burned on Ethereum. Each relayer's signed
+		/// extrinsic is one vote; at `RelayerThreshold` votes the kitty
+		/// returns to the owner recorded at bridge-out.
+		#[weight = T::DbWeight::get().reads_writes(4, 3) + 10_000]
+		pub fn approve_unlock(origin, kitty_id: T::KittyIndex) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(Self::is_relayer(&sender), Error::<T>::NotRelayer);
+			let (owner, _) = Self::bridged_out(kitty_id).ok_or(Error::<T>::NotBridgedOut)?;
+			let mut votes = Self::unlock_votes(kitty_id);
+			ensure!(!votes.contains(&sender), Error::<T>::AlreadyApproved);
+
+			votes.push(sender.clone());
+			let count = votes.len() as u32;
+			Self::deposit_event(RawEvent::UnlockApproved(sender, kitty_id, count));
+			if count >= T::RelayerThreshold::get() {
+				<UnlockVotes<T>>::remove(kitty_id);
+				<BridgedKitties<T>>::remove(kitty_id);
+				<KittyLocks<T>>::remove(kitty_id);
+				Self::deposit_event(RawEvent::BridgedIn(owner, kitty_id));
+			} else {
+				<UnlockVotes<T>>::insert(kitty_id, votes);
+			}
+			Ok(())
+		}
+
+		/// Answer a sibling parachain's ownership query. This Substrate
+		/// version carries no XCM types, so the runtime's message handler
+		/// owns the envelope: it maps the sibling's sovereign origin
+		/// through `ForceOrigin`, dispatches this, and wraps the emitted
+		/// verdict in a `QueryResponse` back to the asker. The attestation
+		/// is advisory — it carries no lock, so it only speaks for the
+		/// block it was made in.
+		#[weight = T::DbWeight::get().reads_writes(3, 1) + 10_000]
+		pub fn answer_ownership_query(origin, query_id: u64, kitty_id: T::KittyIndex, claimed_owner: T::AccountId) -> DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+
+			let verdict = Self::attest_ownership(kitty_id, &claimed_owner);
+			Self::deposit_event(RawEvent::OwnershipQueryAnswered(
+				query_id, kitty_id, claimed_owner, verdict,
+			));
+			Ok(())
+		}
+
+		/// Issue an ownership query to a sibling parachain, asking whether
+		/// `claimed_owner` holds the wrapped form of `kitty_id` there. The
+		/// emitted event is the runtime message handler's cue to send the
+		/// XCM; the response lands through `resolve_ownership_query`.
+		#[weight = T::DbWeight::get().reads_writes(2, 2) + 10_000]
+		pub fn open_ownership_query(origin, para_id: u32, kitty_id: T::KittyIndex, claimed_owner: T::AccountId) -> DispatchResult {
+			let _ = ensure_signed(origin)?;
+			ensure!(<Kitties<T>>::contains_key(kitty_id), Error::<T>::InvalidKittyId);
+
+			let query_id = Self::next_query_id();
+			NextQueryId::put(query_id + 1);
+			<OwnershipQueries<T>>::insert(query_id, (para_id, kitty_id, claimed_owner.clone()));
+
+			Self::deposit_event(RawEvent::OwnershipQueryIssued(
+				query_id, para_id, kitty_id, claimed_owner,
+			));
+			Ok(())
+		}
+
+		/// Land a sibling parachain's `QueryResponse` to one of our
+		/// ownership queries. As with `answer_ownership_query`, the runtime
+		/// message handler dispatches this through `ForceOrigin` after
+		/// checking the response really came from the queried para.
+		#[weight = T::DbWeight::get().reads_writes(1, 1) + 10_000]
+		pub fn resolve_ownership_query(origin, query_id: u64, verdict: bool) -> DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+			ensure!(
+				<OwnershipQueries<T>>::contains_key(query_id),
+				Error::<T>::QueryNotFound
+			);
+
+			<OwnershipQueries<T>>::remove(query_id);
+			Self::deposit_event(RawEvent::OwnershipQueryResolved(query_id, verdict));
+			Ok(())
+		}
+
+		/// Feed a kitty, burning the spent amount and restoring energy at the
+		/// configured rate, up to the energy ceiling. Anyone may feed any
+		/// kitty.
+		#[weight = T::DbWeight::get().reads_writes(3, 2) + 10_000]
+		pub fn feed(origin, kitty_id: T::KittyIndex, amount: BalanceOf<T>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(<Kitties<T>>::contains_key(kitty_id), Error::<T>::InvalidKittyId);
+			let gained: u32 = (amount / T::FeedCostPerEnergy::get()).saturated_into();
+			ensure!(gained > 0, Error::<T>::FeedTooSmall);
+
+			let _ = T::Currency::withdraw(
+				&sender,
+				amount,
+				WithdrawReason::Fee.into(),
+				ExistenceRequirement::KeepAlive,
+			)?;
+			let energy = Self::current_energy(kitty_id)
+				.saturating_add(gained)
+				.min(T::MaxEnergy::get());
+			<Vitals<T>>::insert(kitty_id, KittyVitals {
+				energy,
+				updated_at: <system::Module<T>>::block_number(),
+			});
+
+			Self::deposit_event(RawEvent::Fed(sender, kitty_id, amount, energy));
+			Ok(())
+		}
+
+		/// Register an account (usually another pallet's module account) as a
+		/// collateral taker. Requires the admin origin.
+		#[weight = T::DbWeight::get().reads_writes(1, 1) + 10_000]
+		pub fn register_collateral_taker(origin, taker: T::AccountId) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			<CollateralTakers<T>>::insert(&taker, true);
+			Self::deposit_event(RawEvent::CollateralTakerRegistered(taker));
+			Ok(())
+		}
+
+		/// Remove a collateral taker registration. Requires the admin
+		/// origin. Existing locks held by the taker remain in force until
+		/// released or seized.
+		#[weight = T::DbWeight::get().reads_writes(1, 1) + 10_000]
+		pub fn unregister_collateral_taker(origin, taker: T::AccountId) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			<CollateralTakers<T>>::remove(&taker);
+			Self::deposit_event(RawEvent::CollateralTakerUnregistered(taker));
+			Ok(())
+		}
+	}
+}
+
+impl<T: Trait> NonFungibleCollateral<T::AccountId, T::KittyIndex> for Module<T> {
+	fn lock_collateral(kitty_id: T::KittyIndex, locker: &T::AccountId) -> DispatchResult {
+		ensure!(Self::is_collateral_taker(locker), Error::<T>::NotRegisteredCollateralTaker);
+		ensure!(<Kitties<T>>::contains_key(kitty_id), Error::<T>::InvalidKittyId);
+		ensure!(Self::kitty_lock(kitty_id).is_none(), Error::<T>::KittyLocked);
+		Self::ensure_not_soulbound(kitty_id)?;
+
+		<KittyLocks<T>>::insert(kitty_id, locker);
+		Self::deposit_event(RawEvent::CollateralLocked(kitty_id, locker.clone()));
+		Ok(())
+	}
+
+	fn release_collateral(kitty_id: T::KittyIndex, locker: &T::AccountId) -> DispatchResult {
+		let holder = Self::kitty_lock(kitty_id).ok_or(Error::<T>::KittyNotLocked)?;
+		ensure!(holder == *locker, Error::<T>::NotCollateralLocker);
+
+		<KittyLocks<T>>::remove(kitty_id);
+		Self::deposit_event(RawEvent::CollateralReleased(kitty_id, locker.clone()));
+		Ok(())
+	}
+
+	fn seize_collateral(
+		kitty_id: T::KittyIndex,
+		locker: &T::AccountId,
+		new_owner: &T::AccountId,
+	) -> DispatchResult {
+		let holder = Self::kitty_lock(kitty_id).ok_or(Error::<T>::KittyNotLocked)?;
+		ensure!(holder == *locker, Error::<T>::NotCollateralLocker);
+		let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+		Self::ensure_can_hold_one_more(new_owner)?;
+
+		T::Currency::reserve(new_owner, T::KittyDeposit::get())?;
+		T::Currency::unreserve(&owner, T::KittyDeposit::get());
+		<KittyLocks<T>>::remove(kitty_id);
+		Self::do_transfer(&owner, new_owner, kitty_id);
+		Self::note_provenance(kitty_id, new_owner, TransferKind::Seizure);
+
+		Self::deposit_event(RawEvent::CollateralSeized(kitty_id, locker.clone(), new_owner.clone()));
+		Ok(())
+	}
+}
+
+impl<T: Trait> KittyProvider<T::AccountId, T::KittyIndex> for Module<T> {
+	fn owner_of(kitty_id: T::KittyIndex) -> Option<T::AccountId> {
+		Self::kitty_owner(kitty_id)
+	}
+
+	fn exists(kitty_id: T::KittyIndex) -> bool {
+		<Kitties<T>>::contains_key(kitty_id)
+	}
+
+	fn dna_of(kitty_id: T::KittyIndex) -> Option<[u8; 16]> {
+		Self::kitties(kitty_id).map(|kitty| kitty.0)
+	}
+
+	fn can_transfer(kitty_id: T::KittyIndex) -> bool {
+		<Kitties<T>>::contains_key(kitty_id)
+			&& Self::kitty_lock(kitty_id).is_none()
+			&& Self::escrows(kitty_id).is_none()
+			&& Self::fraction_shares(kitty_id).is_none()
+			&& Self::bridged_out(kitty_id).is_none()
+			&& !Self::is_departed(kitty_id)
+			&& !Self::is_soulbound(kitty_id)
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// Return the id to use for a new kitty with the given DNA, checking the
+	/// supply limit and, depending on the configured mode, either allocating
+	/// sequentially or deriving the id from the DNA.
+	fn kitty_id_for(dna: &[u8; 16]) -> sp_std::result::Result<T::KittyIndex, DispatchError> {
+		ensure!(
+			Self::kitties_count() < T::MaxKittySupply::get().into(),
+			Error::<T>::MaxKittySupplyReached
+		);
+		ensure!(
+			Self::mints_this_block() < T::MaxMintsPerBlock::get(),
+			Error::<T>::MintRateExceeded
+		);
+		if T::ContentAddressedIds::get() {
+			// Derive the id from the DNA; bump the disambiguator until an
+			// unused id is found.
+			for disambiguator in 0u8..=255 {
+				let hash = (dna, disambiguator).using_encoded(blake2_128);
+				let kitty_id: T::KittyIndex =
+					u32::from_le_bytes([hash[0], hash[1], hash[2], hash[3]]).into();
+				if !<Kitties<T>>::contains_key(kitty_id) {
+					return Ok(kitty_id);
+				}
+			}
+			Err(Error::<T>::KittyIdCollision.into())
+		} else {
+			Self::next_kitty_id()
+		}
+	}
+
+	/// Return the next free sequential kitty id.
+	fn next_kitty_id() -> sp_std::result::Result<T::KittyIndex, DispatchError> {
+		let kitty_id = Self::kitties_count();
+		if kitty_id == T::KittyIndex::max_value() {
+			return Err(Error::<T>::KittiesCountOverflow.into());
+		}
+		Ok(kitty_id)
+	}
+
+	/// Resolve the minting account from `origin`: any signed account
+	/// while minting is open, only origins admitted by `T::MintOrigin`
+	/// while `PermissionedMinting` is on.
+	fn ensure_minter(origin: T::Origin) -> sp_std::result::Result<T::AccountId, DispatchError> {
+		if T::PermissionedMinting::get() {
+			Ok(T::MintOrigin::ensure_origin(origin)?)
+		} else {
+			Ok(ensure_signed(origin)?)
+		}
+	}
+
+	/// When PoW minting is enabled and a difficulty is set, check that
+	/// `blake2_256(sender ++ parent_hash ++ nonce)` does not exceed the
+	/// target. The parent hash keeps nonces from being ground ahead of time.
+	fn ensure_pow_nonce(sender: &T::AccountId, nonce: u64) -> DispatchResult {
+		if !T::PowMintEnabled::get() {
+			return Ok(());
+		}
+		let target = match Self::mint_difficulty() {
+			Some(target) => target,
+			None => return Ok(()),
+		};
+		let work = (sender, <system::Module<T>>::parent_hash(), nonce)
+			.using_encoded(blake2_256);
+		ensure!(work <= target, Error::<T>::InvalidPowNonce);
+		Ok(())
+	}
+
+	/// Check the sender has waited out the free-creation interval since
+	/// their last creation.
+	fn ensure_create_interval(sender: &T::AccountId) -> DispatchResult {
+		let last = Self::last_create_at(sender);
+		ensure!(
+			last.is_zero()
+				|| <system::Module<T>>::block_number() >= last + T::CreateInterval::get(),
+			Error::<T>::CreateRateLimited
+		);
+		Ok(())
+	}
+
+	/// Derive a DNA not yet present in the index, re-hashing with an
+	/// incremented nonce on collision, up to a bounded number of attempts.
+	fn unique_dna(dna: [u8; 16]) -> sp_std::result::Result<[u8; 16], DispatchError> {
+		let mut candidate = dna;
+		for nonce in 0u8..16 {
+			if !<DnaIndex<T>>::contains_key(candidate) {
+				return Ok(candidate);
+			}
+			candidate = (dna, nonce).using_encoded(blake2_128);
+		}
+		Err(Error::<T>::DuplicateDna.into())
+	}
+
+	/// Like `unique_dna`, but every candidate keeps `prefix` as its first
+	/// byte so the edition marker survives collision re-rolls.
+	fn unique_edition_dna(
+		dna: [u8; 16],
+		prefix: u8,
+	) -> sp_std::result::Result<[u8; 16], DispatchError> {
+		let mut candidate = dna;
+		candidate[0] = prefix;
+		for nonce in 0u8..16 {
+			if !<DnaIndex<T>>::contains_key(candidate) {
+				return Ok(candidate);
+			}
+			candidate = (dna, nonce).using_encoded(blake2_128);
+			candidate[0] = prefix;
+		}
+		Err(Error::<T>::DuplicateDna.into())
+	}
+
+	/// Mint a fresh random kitty for `sender`, reserving the deposit.
+	fn do_create(sender: &T::AccountId) -> sp_std::result::Result<T::KittyIndex, DispatchError> {
+		let dna = Self::unique_dna(Self::random_value(sender))?;
+		let kitty_id = Self::kitty_id_for(&dna)?;
+		Self::ensure_can_hold_one_more(sender)?;
+		ensure!(
+			Self::gen_zero_minted()
+				< Self::gen_zero_allowance(<system::Module<T>>::block_number()),
+			Error::<T>::GenZeroAllowanceExhausted
+		);
+
+		T::Currency::reserve(sender, T::KittyDeposit::get())?;
+		if let Err(e) = Self::charge_mint_price(sender) {
+			T::Currency::unreserve(sender, T::KittyDeposit::get());
+			return Err(e);
+		}
+		<LastCreateAt<T>>::insert(sender, <system::Module<T>>::block_number());
+		Self::note_activity(sender);
+		GenZeroMinted::mutate(|minted| *minted += 1);
+		Self::insert_kitty(sender, kitty_id, Kitty(dna));
+		Self::note_birth_record(kitty_id, sender);
+		Self::note_provenance(kitty_id, sender, TransferKind::Mint);
+
+		Self::deposit_event(RawEvent::Created(sender.clone(), kitty_id));
+		Ok(kitty_id)
+	}
+
+	fn ensure_can_hold_one_more(owner: &T::AccountId) -> DispatchResult {
+		// Every path by which a kitty can land in an account runs through
+		// here, so this doubles as the blacklist's receive/mint/breed gate.
+		Self::ensure_not_blacklisted(owner)?;
+		ensure!(
+			Self::owned_kitties_count(owner) < T::MaxKittiesPerAccount::get(),
+			Error::<T>::TooManyKittiesPerAccount
+		);
+		Ok(())
+	}
+
+	fn ensure_not_blacklisted(who: &T::AccountId) -> DispatchResult {
+		ensure!(!Self::blacklisted(who), Error::<T>::Blacklisted);
+		Ok(())
+	}
+
+	fn ensure_not_soulbound(kitty_id: T::KittyIndex) -> DispatchResult {
+		ensure!(!Self::is_soulbound(kitty_id), Error::<T>::SoulboundKitty);
+		Ok(())
+	}
+
+	/// The ids of `who`'s kitties that can move right now: not locked,
+	/// escrowed, fractionalized, bridged out, departed or soulbound.
+	/// Enumerated from the ownership
+	/// map itself, so sequential and content-addressed ids are covered
+	/// alike; the walk visits every kitty, and callers account for that
+	/// in their weights.
+	fn movable_kitties_of(who: &T::AccountId) -> Vec<T::KittyIndex> {
+		<KittyOwners<T>>::iter()
+			.filter(|(kitty_id, owner)| {
+				owner == who
+					&& Self::kitty_lock(*kitty_id).is_none()
+					&& Self::escrows(*kitty_id).is_none()
+					&& Self::fraction_shares(*kitty_id).is_none()
+					&& Self::bridged_out(*kitty_id).is_none()
+					&& !Self::is_departed(*kitty_id)
+					&& !Self::is_soulbound(*kitty_id)
+			})
+			.map(|(kitty_id, _)| kitty_id)
+			.collect()
+	}
+
+	/// Remove a kitty and all its per-kitty state, refunding the deposit
+	/// to `owner`. The historical mint count is unaffected; `KittiesCount`
+	/// doubles as the id allocator and never decreases.
+	fn remove_kitty(owner: &T::AccountId, kitty_id: T::KittyIndex) {
+		T::Currency::unreserve(owner, T::KittyDeposit::get());
+		if let Some(kitty) = Self::kitties(kitty_id) {
+			<DnaIndex<T>>::remove(kitty.0);
+		}
+		if Self::generation(kitty_id) == 0 {
+			GenZeroSupply::mutate(|supply| *supply = supply.saturating_sub(1));
+		}
+		GenerationCounts::mutate(Self::generation(kitty_id), |count| {
+			*count = count.saturating_sub(1)
+		});
+		BurnedKitties::mutate(|count| *count += 1);
+		<Kitties<T>>::remove(kitty_id);
+		<KittyOwners<T>>::remove(kitty_id);
+		Self::debit_holding(owner);
+		<Vitals<T>>::remove(kitty_id);
+		<StatSheets<T>>::remove(kitty_id);
+		<KittiesByGeneration<T>>::remove(Self::generation(kitty_id), kitty_id);
+		<Generations<T>>::remove(kitty_id);
+		<BornAt<T>>::remove(kitty_id);
+		<RentPaidUntil<T>>::remove(kitty_id);
+		<Hibernating<T>>::remove(kitty_id);
+		<Rerolled<T>>::remove(kitty_id);
+		<PendingTransfers<T>>::remove(kitty_id);
+		<Soulbound<T>>::remove(kitty_id);
+		<KittyEdition<T>>::remove(kitty_id);
+		<Tiers<T>>::remove(kitty_id);
+		<RarityLeaderboard<T>>::mutate(|board| board.retain(|(id, _)| *id != kitty_id));
+		<Pedigrees<T>>::remove(kitty_id);
+		<BirthRecords<T>>::remove(kitty_id);
+		<Erc721Approvals<T>>::remove(kitty_id);
+		if let Some(name) = <Names<T>>::take(kitty_id) {
+			<NameTaken<T>>::remove(name);
+		}
+		<BridgedKitties<T>>::remove(kitty_id);
+		<UnlockVotes<T>>::remove(kitty_id);
+		<LastBreedAt<T>>::remove(kitty_id);
+		<Counters<T>>::remove(kitty_id);
+		if let Some(cattery_id) = Self::kitty_cattery(kitty_id) {
+			Self::leave_cattery(kitty_id, cattery_id);
+		}
+		Self::take_listing(kitty_id);
+		<Provenance<T>>::remove(kitty_id);
+		<LifetimeTips<T>>::remove(kitty_id);
+		<Equipped<T>>::remove(kitty_id);
+		<PhenotypeHashes<T>>::remove(kitty_id);
+		<SuggestedPrices<T>>::remove(kitty_id);
+		<RaceRecords<T>>::remove(kitty_id);
+	}
+
+	/// Check that `to` is willing to receive an unsolicited kitty. Only
+	/// direct transfers consult this; market actions the recipient started
+	/// themselves are always welcome.
+	fn ensure_accepts_transfer(to: &T::AccountId) -> DispatchResult {
+		let preferences = Self::preferences(to);
+		ensure!(preferences.auto_accept, Error::<T>::RecipientRefusesTransfers);
+		if let Some(cap) = preferences.max_incoming {
+			ensure!(Self::owned_kitties_count(to) < cap, Error::<T>::RecipientAtCapacity);
+		}
+		Ok(())
+	}
+
+	/// Generate 16 bytes of DNA from the randomness source, the sender and
+	/// the position of the current extrinsic.
+	fn random_value(sender: &T::AccountId) -> [u8; 16] {
+		let payload = (
+			T::Randomness::random_seed(),
+			&sender,
+			<system::Module<T>>::extrinsic_index(),
+		);
+		payload.using_encoded(blake2_128)
+	}
+
+	/// Record the inputs `random_value` drew on for `kitty_id`'s DNA.
+	/// Must run in the same extrinsic as the `random_value` call so the
+	/// seed and extrinsic index it captures are the ones actually used.
+	fn note_birth_record(kitty_id: T::KittyIndex, minter: &T::AccountId) {
+		<BirthRecords<T>>::insert(kitty_id, BirthRecord {
+			block: <system::Module<T>>::block_number(),
+			extrinsic_index: <system::Module<T>>::extrinsic_index(),
+			seed_hash: T::Randomness::random_seed(),
+			minter: minter.clone(),
+		});
+	}
+
+	/// Whether `who` owns a live kitty, as attested to sibling parachains.
+	/// Bridged-out kitties fail the check: their custody sits with the
+	/// bridge contract, not the recorded owner.
+	pub fn attest_ownership(kitty_id: T::KittyIndex, who: &T::AccountId) -> bool {
+		Self::kitty_owner(kitty_id).as_ref() == Some(who)
+			&& Self::bridged_out(kitty_id).is_none()
+	}
+
+	/// Recompute a minted kitty's DNA from its birth record and check it
+	/// against the registry, replaying the collision re-rolls of
+	/// `unique_dna` and the prefix pinning of edition mints. Returns
+	/// `None` for kitties without a record (bred, hybrid or forged ones)
+	/// and `Some(false)` when the DNA no longer matches its derivation,
+	/// as after a re-roll or a forced rewrite.
+	pub fn verify_dna(kitty_id: T::KittyIndex) -> Option<bool> {
+		let record = Self::birth_record(kitty_id)?;
+		let kitty = Self::kitties(kitty_id)?;
+		let base = (record.seed_hash, &record.minter, record.extrinsic_index)
+			.using_encoded(blake2_128);
+		let prefix = Self::kitty_edition(kitty_id)
+			.and_then(Self::editions)
+			.map(|edition| edition.dna_prefix);
+
+		let mut candidate = base;
+		if let Some(prefix) = prefix {
+			candidate[0] = prefix;
+		}
+		for nonce in 0u8..16 {
+			if candidate == kitty.0 {
+				return Some(true);
+			}
+			candidate = (base, nonce).using_encoded(blake2_128);
+			if let Some(prefix) = prefix {
+				candidate[0] = prefix;
+			}
+		}
+		Some(false)
+	}
+
+	/// Mix two parents' DNA, using `selector` to choose each bit.
+	fn combine_dna(dna1: &[u8; 16], dna2: &[u8; 16], selector: [u8; 16]) -> [u8; 16] {
+		let mut new_dna = [0u8; 16];
+		for i in 0..new_dna.len() {
+			new_dna[i] = (selector[i] & dna1[i]) | (!selector[i] & dna2[i]);
+		}
+		new_dna
+	}
+
+	/// Whether `delegate` holds an unexpired breeding delegation with uses
+	/// left for the kitty.
+	fn has_breeding_delegation(
+		delegate: &T::AccountId,
+		kitty_id: T::KittyIndex,
+		now: T::BlockNumber,
+	) -> bool {
+		Self::breeding_delegations(kitty_id, delegate)
+			.map(|(uses, expiry, _)| uses > 0 && expiry >= now)
+			.unwrap_or(false)
+	}
+
+	/// Use up one breeding from a delegation, removing it when exhausted.
+	fn consume_breeding_delegation(kitty_id: T::KittyIndex, delegate: &T::AccountId) {
+		if let Some((uses, expiry, fee_share)) = Self::breeding_delegations(kitty_id, delegate) {
+			if uses <= 1 {
+				<BreedingDelegations<T>>::remove(kitty_id, delegate);
+				Self::release_delegation_lock(kitty_id);
+			} else {
+				<BreedingDelegations<T>>::insert(kitty_id, delegate, (uses - 1, expiry, fee_share));
+			}
+		}
+	}
+
+	/// Drop the trade lock held for breeding delegations once the last
+	/// delegation on the kitty is gone. Callers must only invoke this after
+	/// removing a delegation, so the lock cannot belong to another state.
+	fn release_delegation_lock(kitty_id: T::KittyIndex) {
+		if <BreedingDelegations<T>>::iter_prefix(kitty_id).next().is_none() {
+			<KittyLocks<T>>::remove(kitty_id);
+		}
+	}
+
+	/// Breed two existing kitties, assigning the child to `recipient`, who
+	/// pays the breeding fee and the newborn's deposit. Ownership of the
+	/// parents is the caller's responsibility to check.
+	fn do_breed(
+		recipient: &T::AccountId,
+		kitty_id_1: T::KittyIndex,
+		kitty_id_2: T::KittyIndex,
+		sire_shares: &[(T::AccountId, Percent)],
+	) -> sp_std::result::Result<T::KittyIndex, DispatchError> {
+		ensure!(kitty_id_1 != kitty_id_2, Error::<T>::RequireDifferentParent);
+		let kitty1 = Self::kitties(kitty_id_1).ok_or(Error::<T>::InvalidKittyId)?;
+		let kitty2 = Self::kitties(kitty_id_2).ok_or(Error::<T>::InvalidKittyId)?;
+		ensure!(!Self::is_departed(kitty_id_1), Error::<T>::KittyDeparted);
+		ensure!(!Self::is_departed(kitty_id_2), Error::<T>::KittyDeparted);
+		ensure!(Self::bridged_out(kitty_id_1).is_none(), Error::<T>::KittyBridgedOut);
+		ensure!(Self::bridged_out(kitty_id_2).is_none(), Error::<T>::KittyBridgedOut);
+		ensure!(Self::hibernating(kitty_id_1).is_none(), Error::<T>::KittyHibernating);
+		ensure!(Self::hibernating(kitty_id_2).is_none(), Error::<T>::KittyHibernating);
+
+		let now = <system::Module<T>>::block_number();
+		ensure!(Self::next_breeding_window(now) == now, Error::<T>::BreedingClosed);
+		let cooldown = Self::current_breed_cooldown();
+		ensure!(
+			now >= Self::last_breed_at(kitty_id_1) + cooldown,
+			Error::<T>::BreedCooldownActive
+		);
+		ensure!(
+			now >= Self::last_breed_at(kitty_id_2) + cooldown,
+			Error::<T>::BreedCooldownActive
+		);
+
+		// Well-fed parents always conceive; if either is below the breeding
+		// energy cost, the attempt only succeeds half the time.
+		let energy1 = Self::current_energy(kitty_id_1);
+		let energy2 = Self::current_energy(kitty_id_2);
+		let cost = T::BreedEnergyCost::get();
+		if energy1 < cost || energy2 < cost {
+			let roll = Self::random_value(recipient)[0];
+			ensure!(roll < 128, Error::<T>::BreedingFailed);
+		}
+
+		let mut combined = Self::combine_dna(&kitty1.0, &kitty2.0, Self::random_value(recipient));
+		// With the governed mutation chance, one gene byte is rerolled to
+		// a value neither parent carries there.
+		let mutation_roll = Self::random_value(recipient);
+		if Percent::from_percent(mutation_roll[0] % 100) < Self::mutation_rate() {
+			combined[(mutation_roll[1] % 16) as usize] = mutation_roll[2];
+		}
+		let dna = Self::unique_dna(combined)?;
+		let kitty_id = Self::kitty_id_for(&dna)?;
+		Self::ensure_can_hold_one_more(recipient)?;
+
+		// A prepaid pass covers the whole fee; otherwise referral credits
+		// apply before any balance is withdrawn.
+		let fee = Self::current_breed_fee();
+		let use_pass = Self::breeding_passes(recipient) > 0;
+		let credit = if use_pass {
+			Zero::zero()
+		} else {
+			Self::fee_credits(recipient).min(fee)
+		};
+		let charged = if use_pass { Zero::zero() } else { fee - credit };
+		T::Currency::reserve(recipient, T::KittyDeposit::get())?;
+		if !charged.is_zero() {
+			match T::Currency::withdraw(
+				recipient,
+				charged,
+				WithdrawReason::Fee.into(),
+				ExistenceRequirement::KeepAlive,
+			) {
+				// Pay each external sire owner their agreed share of the
+				// configured fee (capped by what was actually withdrawn);
+				// the unallocated remainder takes the deflationary burn
+				// before the beneficiary sees it.
+				Ok(mut paid) => {
+					for (sire_owner, share) in sire_shares {
+						let (cut, remainder) = paid.split(*share * fee);
+						if !cut.peek().is_zero() {
+							Self::deposit_event(RawEvent::BreedFeeSplit(
+								sire_owner.clone(),
+								kitty_id,
+								cut.peek(),
+							));
+						}
+						T::Currency::resolve_creating(sire_owner, cut);
+						paid = remainder;
+					}
+					Self::route_fee(paid);
+				}
+				Err(e) => {
+					T::Currency::unreserve(recipient, T::KittyDeposit::get());
+					return Err(e);
+				}
+			}
+		}
+		if !credit.is_zero() {
+			<FeeCredits<T>>::mutate(recipient, |total| *total -= credit);
+		}
+		if use_pass {
+			<BreedingPasses<T>>::mutate(recipient, |passes| *passes -= 1);
+		}
+
+		Self::insert_kitty(recipient, kitty_id, Kitty(dna));
+		Self::note_provenance(kitty_id, recipient, TransferKind::Breed);
+		Self::note_activity(recipient);
+		<LastBreedAt<T>>::insert(kitty_id_1, now);
+		<LastBreedAt<T>>::insert(kitty_id_2, now);
+		<Counters<T>>::mutate(kitty_id_1, |c| c.breedings = c.breedings.saturating_add(1));
+		<Counters<T>>::mutate(kitty_id_2, |c| c.breedings = c.breedings.saturating_add(1));
+		<Vitals<T>>::insert(kitty_id_1, KittyVitals {
+			energy: energy1.saturating_sub(cost),
+			updated_at: now,
+		});
+		<Vitals<T>>::insert(kitty_id_2, KittyVitals {
+			energy: energy2.saturating_sub(cost),
+			updated_at: now,
+		});
+		let generation = Self::generation(kitty_id_1)
+			.max(Self::generation(kitty_id_2))
+			.saturating_add(1);
+		Self::set_generation(kitty_id, generation);
+		<Pedigrees<T>>::insert(kitty_id, vec![
+			ParentRef::Kitty(kitty_id_1),
+			ParentRef::Kitty(kitty_id_2),
+		]);
+		<BreederScore<T>>::mutate(recipient, |score| *score = score.saturating_add(1));
+		if Self::breeder_score(recipient) >= 10 {
+			Self::unlock_achievement(recipient, Achievement::TenBreedings);
+		}
+		if generation >= 5 {
+			Self::unlock_achievement(recipient, Achievement::BredGenFive);
+		}
+		Ok(kitty_id)
+	}
+
+	/// Decode the canonical phenotype attributes from a kitty's DNA.
+	pub fn attributes(kitty_id: T::KittyIndex) -> Option<KittyAttributes> {
+		Self::kitties(kitty_id).map(|kitty| KittyAttributes {
+			fur: kitty.0[0] % 8,
+			eyes: kitty.0[1] % 8,
+			pattern: kitty.0[2] % 16,
+		})
+	}
+
+	/// Move `kitty_id` to `generation`, keeping the generation index in
+	/// step with the `Generations` map.
+	fn set_generation(kitty_id: T::KittyIndex, generation: u32) {
+		let previous = Self::generation(kitty_id);
+		if previous == 0 && generation != 0 {
+			GenZeroSupply::mutate(|supply| *supply = supply.saturating_sub(1));
+		} else if previous != 0 && generation == 0 {
+			GenZeroSupply::mutate(|supply| *supply += 1);
+		}
+		<KittiesByGeneration<T>>::remove(previous, kitty_id);
+		<Generations<T>>::insert(kitty_id, generation);
+		<KittiesByGeneration<T>>::insert(generation, kitty_id, ());
+		GenerationCounts::mutate(previous, |count| *count = count.saturating_sub(1));
+		GenerationCounts::mutate(generation, |count| *count += 1);
+	}
+
+	/// The dashboard aggregates, assembled purely from counters.
+	pub fn aggregate_stats() -> AggregateStats<BalanceOf<T>> {
+		let mut generations: Vec<(u32, u32)> = GenerationCounts::iter()
+			.filter(|(_, count)| *count > 0)
+			.collect();
+		generations.sort();
+		AggregateStats {
+			total_supply: generations.iter().map(|(_, count)| count).sum(),
+			generations,
+			total_burned: Self::burned_kitties(),
+			trade_volume: Self::trade_volume(),
+			active_listings: Self::active_listings(),
+		}
+	}
+
+	/// A page of the kitties in `generation`: ascending ids starting at
+	/// `start`, at most `limit` entries long.
+	pub fn generation_page(
+		generation: u32,
+		start: T::KittyIndex,
+		limit: u32,
+	) -> Vec<T::KittyIndex> {
+		let mut ids: Vec<T::KittyIndex> =
+			<KittiesByGeneration<T>>::iter_prefix(generation).map(|(id, ())| id).collect();
+		ids.sort();
+		ids.into_iter().filter(|id| *id >= start).take(limit as usize).collect()
+	}
+
+	/// Every holder in a snapshot with their frozen kitty count. Serves
+	/// the runtime API; on-chain consumers should read `Snapshots`
+	/// directly instead of materialising the whole list.
+	pub fn snapshot_holders(snapshot_id: u32) -> Vec<(T::AccountId, u32)> {
+		<Snapshots<T>>::iter_prefix(snapshot_id).collect()
+	}
+
+	/// Run one round of every active tournament whose start has passed.
+	/// Tournaments with a single contender left, or past their deadline,
+	/// are settled. Weight scales with the number of contenders touched.
+	fn run_tournaments(now: T::BlockNumber) -> Weight {
+		let mut still_active = Vec::new();
+		let mut touched: Weight = 0;
+		for tournament_id in ActiveTournaments::get() {
+			let tournament = match Self::tournaments(tournament_id) {
+				Some(tournament) => tournament,
+				None => continue,
+			};
+			if now < tournament.start {
+				still_active.push(tournament_id);
+				continue;
+			}
+			let entrants = Self::tournament_entrants(tournament_id);
+			touched += entrants.len() as Weight;
+			if entrants.len() <= 1 || now >= tournament.end {
+				Self::finish_tournament(tournament_id, &tournament, entrants);
+				continue;
+			}
+			let survivors = Self::resolve_round(tournament_id, entrants);
+			if survivors.len() <= 1 {
+				Self::finish_tournament(tournament_id, &tournament, survivors);
+			} else {
+				<TournamentEntrants<T>>::insert(tournament_id, survivors);
+				still_active.push(tournament_id);
+			}
+		}
+		ActiveTournaments::put(still_active);
+		touched * 50_000
+	}
+
+	/// Run every race whose start block has arrived: rank the runners by
+	/// speed plus a random roll and pay the pool out 50/30/20 to the top
+	/// three, with the shares of missing finishers going to the winner.
+	fn run_races(now: T::BlockNumber) -> Weight {
+		let mut still_active = Vec::new();
+		let mut touched: Weight = 0;
+		for race_id in ActiveRaces::get() {
+			let race = match Self::races(race_id) {
+				Some(race) => race,
+				None => continue,
+			};
+			if now < race.start {
+				still_active.push(race_id);
+				continue;
+			}
+			let runners = Self::race_runners(race_id);
+			touched += runners.len() as Weight;
+			Self::finish_race(race_id, &race, runners);
+		}
+		ActiveRaces::put(still_active);
+		touched * 50_000
+	}
+
+	/// A kitty's speed on the track: agility weighted double over stamina,
+	/// a deliberately different blend from the 1v1 battle score.
+	fn race_speed(kitty_id: T::KittyIndex) -> u32 {
+		Self::effective_stats(kitty_id)
+			.map(|stats| stats.agility * 2 + stats.stamina)
+			.unwrap_or(0)
+	}
+
+	/// Rank the runners, pay the prize shares and update the lifetime
+	/// records. A race nobody entered just disappears.
+	fn finish_race(
+		race_id: u32,
+		race: &Race<BalanceOf<T>, T::BlockNumber>,
+		runners: Vec<(T::KittyIndex, T::AccountId)>,
+	) {
+		<Races<T>>::remove(race_id);
+		<RaceRunners<T>>::remove(race_id);
+		if runners.is_empty() {
+			return;
+		}
+
+		let seed = T::Randomness::random_seed();
+		let mut ranked: Vec<(u32, T::KittyIndex, T::AccountId)> = runners
+			.into_iter()
+			.map(|(kitty_id, entered_by)| {
+				let roll = (&seed, race_id, kitty_id).using_encoded(blake2_128);
+				(Self::race_speed(kitty_id) + (roll[0] % 32) as u32, kitty_id, entered_by)
+			})
+			.collect();
+		ranked.sort_by(|a, b| b.0.cmp(&a.0));
+
+		let second_prize = if ranked.len() > 1 {
+			Percent::from_percent(30) * race.prize_pool
+		} else {
+			Zero::zero()
+		};
+		let third_prize = if ranked.len() > 2 {
+			Percent::from_percent(20) * race.prize_pool
+		} else {
+			Zero::zero()
+		};
+		let first_prize = race.prize_pool - second_prize - third_prize;
+
+		for (position, (_, kitty_id, entered_by)) in ranked.into_iter().take(3).enumerate() {
+			let prize = match position {
+				0 => first_prize,
+				1 => second_prize,
+				_ => third_prize,
+			};
+			let owner = Self::kitty_owner(kitty_id).unwrap_or(entered_by);
+			let _ = T::Currency::deposit_into_existing(&owner, prize);
+			<RaceRecords<T>>::mutate(kitty_id, |record| {
+				record.podiums = record.podiums.saturating_add(1);
+				if position == 0 {
+					record.wins = record.wins.saturating_add(1);
+				}
+			});
+			if position == 0 {
+				Self::deposit_event(RawEvent::RaceFinished(race_id, kitty_id, owner, prize));
+			}
+		}
+	}
+
+	/// Pair off the contenders and keep the winner of each match: the
+	/// kitty with the higher effective stat total plus a bounded random
+	/// roll. An odd contender gets a bye.
+	fn resolve_round(
+		tournament_id: u32,
+		entrants: Vec<(T::KittyIndex, T::AccountId)>,
+	) -> Vec<(T::KittyIndex, T::AccountId)> {
+		let seed = T::Randomness::random_seed();
+		let mut survivors = Vec::new();
+		for pair in entrants.chunks(2) {
+			if pair.len() == 1 {
+				survivors.push(pair[0].clone());
+				continue;
+			}
+			let roll = (&seed, tournament_id, pair[0].0, pair[1].0).using_encoded(blake2_128);
+			let score_a = Self::battle_score(pair[0].0) + (roll[0] % 32) as u32;
+			let score_b = Self::battle_score(pair[1].0) + (roll[1] % 32) as u32;
+			survivors.push(if score_a >= score_b { pair[0].clone() } else { pair[1].clone() });
+		}
+		survivors
+	}
+
+	/// A kitty's strength in a tournament match: the sum of its effective
+	/// stats.
+	fn battle_score(kitty_id: T::KittyIndex) -> u32 {
+		Self::effective_stats(kitty_id)
+			.map(|stats| stats.strength + stats.agility + stats.stamina)
+			.unwrap_or(0)
+	}
+
+	/// Pay the prize pool to the strongest remaining contender and clear
+	/// the tournament. A tournament nobody entered just disappears. The
+	/// prize follows the kitty's current owner, not the account that
+	/// entered it.
+	fn finish_tournament(
+		tournament_id: u32,
+		tournament: &Tournament<BalanceOf<T>, T::BlockNumber>,
+		entrants: Vec<(T::KittyIndex, T::AccountId)>,
+	) {
+		<Tournaments<T>>::remove(tournament_id);
+		<TournamentEntrants<T>>::remove(tournament_id);
+		let champion = entrants
+			.into_iter()
+			.max_by_key(|(kitty_id, _)| Self::battle_score(*kitty_id));
+		if let Some((kitty_id, entered_by)) = champion {
+			let winner = Self::kitty_owner(kitty_id).unwrap_or(entered_by);
+			let _ = T::Currency::deposit_into_existing(&winner, tournament.prize_pool);
+			Self::deposit_event(RawEvent::TournamentWon(
+				tournament_id, kitty_id, winner, tournament.prize_pool,
+			));
+		}
+	}
+
+	/// Write a deterministic trait-JSON render of every kitty born in
+	/// `now` to off-chain local storage, keyed by kitty id. Ids are walked
+	/// backwards from the allocator, so this only covers sequentially
+	/// allocated ids; content-addressed deployments need an external
+	/// indexer anyway.
+	fn render_new_kitties(now: T::BlockNumber) {
+		if T::ContentAddressedIds::get() {
+			return;
+		}
+		let mut kitty_id = Self::kitties_count();
+		while kitty_id > Zero::zero() {
+			kitty_id -= One::one();
+			if Self::born_at(kitty_id) != now {
+				break;
+			}
+			if let Some(render) = Self::render_kitty(kitty_id) {
+				let mut key = b"kitties/render/".to_vec();
+				kitty_id.using_encoded(|id| key.extend_from_slice(id));
+				StorageValueRef::persistent(&key).set(&render);
+			}
+		}
+	}
+
+	/// Submit the off-chain worker's findings for kitties born this block
+	/// as unsigned transactions: the phenotype hash and the suggested
+	/// floor price. Failures are swallowed — another node's worker, or
+	/// the next run, will cover the gap.
+	fn submit_ocw_findings(now: T::BlockNumber) {
+		if T::ContentAddressedIds::get() {
+			return;
+		}
+		let mut kitty_id = Self::kitties_count();
+		while kitty_id > Zero::zero() {
+			kitty_id -= One::one();
+			if Self::born_at(kitty_id) != now {
+				break;
+			}
+			if let Some(render) = Self::render_kitty(kitty_id) {
+				let call = Call::submit_phenotype_hash(kitty_id, blake2_256(&render));
+				let _ = SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into());
+			}
+			if let Some(price) = Self::suggest_price(kitty_id) {
+				let call = Call::submit_price_suggestion(kitty_id, price);
+				let _ = SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into());
+			}
+		}
+	}
+
+	/// The current bonding-curve price of a gen-0 mint, also exposed
+	/// through the runtime API so wallets can quote it ahead of time.
+	pub fn mint_price() -> BalanceOf<T> {
+		let (base, slope) = Self::bonding_curve();
+		base + slope * Self::gen_zero_supply().into()
+	}
+
+	/// The cumulative gen-0 minting allowance released by `now`: one
+	/// tranche from genesis plus one per elapsed tranche period, never
+	/// exceeding the lifetime cap.
+	pub fn gen_zero_allowance(now: T::BlockNumber) -> u32 {
+		let period = T::GenZeroTranchePeriod::get();
+		let tranches = if period.is_zero() {
+			u32::max_value()
+		} else {
+			(now / period).saturated_into::<u32>().saturating_add(1)
+		};
+		T::GenZeroTrancheSize::get()
+			.saturating_mul(tranches)
+			.min(T::GenZeroCap::get())
+	}
+
+	/// Charge the bonding-curve mint price, crediting the proceeds to
+	/// the market fee beneficiary — the chain's treasury account — or
+	/// burning them when none is configured.
+	fn charge_mint_price(sender: &T::AccountId) -> DispatchResult {
+		let price = Self::mint_price();
+		if price.is_zero() {
+			return Ok(());
+		}
+		let withdrawn = T::Currency::withdraw(
+			sender,
+			price,
+			WithdrawReason::Fee.into(),
+			ExistenceRequirement::KeepAlive,
+		)?;
+		match T::MarketFeeBeneficiary::get() {
+			Some(beneficiary) => T::Currency::resolve_creating(&beneficiary, withdrawn),
+			None => drop(withdrawn),
+		}
+		Ok(())
+	}
+
+	/// The account holding the distribution pool's kitties. Derived from
+	/// the module id, so nobody holds its keys.
+	pub fn pool_account() -> T::AccountId {
+		DROP_POOL_ID.into_account()
+	}
+
+	/// The keyless account holding liquidated loan collateral and routing
+	/// its auction proceeds.
+	pub fn loan_account() -> T::AccountId {
+		LOAN_POOL_ID.into_account()
+	}
+
+	/// The keyless account recorded as the lock holder of bundled kitties.
+	pub fn bundle_lock_account() -> T::AccountId {
+		BUNDLE_LOCK_ID.into_account()
+	}
+
+	/// The keyless account recorded as the lock holder of kitties with
+	/// active market state.
+	pub fn market_lock_account() -> T::AccountId {
+		MARKET_LOCK_ID.into_account()
+	}
+
+	/// The keyless account named as locker of kitties sitting inside a
+	/// hash-timelocked swap.
+	pub fn htlc_lock_account() -> T::AccountId {
+		HTLC_LOCK_ID.into_account()
+	}
+
+	/// The keyless account named as locker of kitties awaiting a
+	/// timelocked transfer.
+	pub fn timed_lock_account() -> T::AccountId {
+		TIMED_LOCK_ID.into_account()
+	}
+
+	/// The exact storage keys a light client needs in a read proof to
+	/// verify ownership of `kitty_id`: the kitty's entry in `Kitties`
+	/// (existence) and in `KittyOwners` (the owner), plus its
+	/// `BridgedKitties` entry so a proof cannot pass off a kitty that has
+	/// left the chain. A node turns these into a compact Merkle proof
+	/// with the `state_getReadProof` RPC.
+	pub fn ownership_proof_keys(kitty_id: T::KittyIndex) -> Vec<Vec<u8>> {
+		vec![
+			<Kitties<T>>::hashed_key_for(kitty_id),
+			<KittyOwners<T>>::hashed_key_for(kitty_id),
+			<BridgedKitties<T>>::hashed_key_for(kitty_id),
+		]
+	}
+
+	/// The `dev_clone` body for dev builds: mint `count` near-copies of
+	/// the kitty to `sender`, nudging each DNA through `unique_dna`.
+	#[cfg(feature = "dev")]
+	fn do_dev_clone(
+		sender: &T::AccountId,
+		kitty_id: T::KittyIndex,
+		count: u32,
+	) -> DispatchResult {
+		let kitty = Self::kitties(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+		for index in 0..count {
+			Self::ensure_can_hold_one_more(sender)?;
+			let mut dna = kitty.0;
+			dna[15] = dna[15].wrapping_add(index as u8 + 1);
+			let dna = Self::unique_dna(dna)?;
+			let clone_id = Self::kitty_id_for(&dna)?;
+			T::Currency::reserve(sender, T::KittyDeposit::get())?;
+			Self::insert_kitty(sender, clone_id, Kitty(dna));
+			Self::note_provenance(clone_id, sender, TransferKind::Mint);
+			Self::deposit_event(RawEvent::Created(sender.clone(), clone_id));
+		}
+		Ok(())
+	}
+
+	#[cfg(not(feature = "dev"))]
+	fn do_dev_clone(
+		_sender: &T::AccountId,
+		_kitty_id: T::KittyIndex,
+		_count: u32,
+	) -> DispatchResult {
+		Err(Error::<T>::DevOnly.into())
+	}
+
+	/// The `dev_skip_cooldowns` body for dev builds.
+	#[cfg(feature = "dev")]
+	fn do_dev_skip_cooldowns(sender: &T::AccountId, kitty_id: T::KittyIndex) -> DispatchResult {
+		ensure!(Self::kitties(kitty_id).is_some(), Error::<T>::InvalidKittyId);
+		<LastBreedAt<T>>::remove(kitty_id);
+		<LastCreateAt<T>>::remove(sender);
+		<Vitals<T>>::insert(kitty_id, KittyVitals {
+			energy: T::MaxEnergy::get(),
+			updated_at: <system::Module<T>>::block_number(),
+		});
+		Ok(())
+	}
+
+	#[cfg(not(feature = "dev"))]
+	fn do_dev_skip_cooldowns(
+		_sender: &T::AccountId,
+		_kitty_id: T::KittyIndex,
+	) -> DispatchResult {
+		Err(Error::<T>::DevOnly.into())
+	}
+
+	/// Whether `kitty_id` falls inside any council-verified collection.
+	pub fn is_verified(kitty_id: T::KittyIndex) -> bool {
+		<VerifiedCollections<T>>::iter().any(|(_, (_, scope))| match scope {
+			VerifiedScope::Range(from, to) => from <= kitty_id && kitty_id <= to,
+			VerifiedScope::Edition(edition_id) =>
+				Self::kitty_edition(kitty_id) == Some(edition_id),
+		})
+	}
+
+	/// The keyless account holding custodially listed kitties.
+	pub fn listing_custody_account() -> T::AccountId {
+		LISTING_CUSTODY_ID.into_account()
+	}
+
+	/// The keyless account pooling asset-denominated offer and bid funds.
+	pub fn payment_pool_account() -> T::AccountId {
+		PAYMENT_POOL_ID.into_account()
+	}
+
+	/// The keyless account holding the buyback program's funds.
+	pub fn buyback_pool_account() -> T::AccountId {
+		BUYBACK_POOL_ID.into_account()
+	}
+
+	/// The keyless lock holder marking fractionalized kitties.
+	pub fn fraction_lock_account() -> T::AccountId {
+		FRACTION_LOCK_ID.into_account()
+	}
+
+	/// The keyless lock holder marking bridged-out kitties.
+	pub fn bridge_lock_account() -> T::AccountId {
+		BRIDGE_LOCK_ID.into_account()
+	}
+
+	/// What the buyback program pays for the kitty right now: the
+	/// schedule applied to its rarity score and generation. `None` when
+	/// the program is closed or the kitty does not exist.
+	pub fn buyback_quote(kitty_id: T::KittyIndex) -> Option<BalanceOf<T>> {
+		let (base, per_point) = Self::buyback_schedule()?;
+		let score = Self::rarity_score(kitty_id)?;
+		let generation = Self::generation(kitty_id);
+		Some((base + per_point * score.into()) / (generation + 1).into())
+	}
+
+	/// The transferability gates shared by both sides of a swap: the
+	/// kitty must be free of locks, escrow, fractions, bridging,
+	/// departure and soulbinding.
+	fn ensure_swappable(kitty_id: T::KittyIndex) -> DispatchResult {
+		ensure!(Self::kitty_lock(kitty_id).is_none(), Error::<T>::KittyLocked);
+		ensure!(Self::escrows(kitty_id).is_none(), Error::<T>::KittyInEscrow);
+		ensure!(Self::fraction_shares(kitty_id).is_none(), Error::<T>::KittyFractionalized);
+		ensure!(Self::bridged_out(kitty_id).is_none(), Error::<T>::KittyBridgedOut);
+		ensure!(!Self::is_departed(kitty_id), Error::<T>::KittyDeparted);
+		Self::ensure_not_soulbound(kitty_id)
+	}
+
+	/// Whether a kitty passes a collection offer's filter, judged against
+	/// its stored generation and decoded attributes.
+	fn offer_filter_matches(kitty_id: T::KittyIndex, filter: &OfferFilter) -> bool {
+		if Self::generation(kitty_id) > filter.max_generation {
+			return false;
+		}
+		let attributes = match Self::attributes(kitty_id) {
+			Some(attributes) => attributes,
+			None => return false,
+		};
+		filter.fur.map_or(true, |fur| fur == attributes.fur)
+			&& filter.eyes.map_or(true, |eyes| eyes == attributes.eyes)
+			&& filter.pattern.map_or(true, |pattern| pattern == attributes.pattern)
+	}
+
+	/// The floor valuation backing loan-to-value checks: the off-chain
+	/// worker's recorded suggestion when one exists, the canonical
+	/// derivation otherwise.
+	fn kitty_valuation(kitty_id: T::KittyIndex) -> Option<BalanceOf<T>> {
+		if let Some((price, _)) = Self::suggested_price(kitty_id) {
+			return Some(price);
+		}
+		Self::suggest_price(kitty_id)
+	}
+
+	/// The outstanding debt on a loan at `now`: the principal plus the
+	/// per-block interest since funding.
+	fn loan_debt(
+		loan: &Loan<T::AccountId, BalanceOf<T>, T::BlockNumber>,
+		now: T::BlockNumber,
+	) -> BalanceOf<T> {
+		let elapsed = loan
+			.funded_at
+			.map(|funded_at| now.saturating_sub(funded_at))
+			.unwrap_or_else(Zero::zero);
+		loan.principal.saturating_add(
+			loan.interest_per_block
+				.saturating_mul(elapsed.saturated_into::<u32>().into()),
+		)
+	}
+
+	/// Route a settled liquidation auction's proceeds out of the loan
+	/// pool: the lender is repaid up to the outstanding debt and any
+	/// surplus goes back to the borrower.
+	fn settle_loan_proceeds(kitty_id: T::KittyIndex, received: BalanceOf<T>) {
+		let loan = match Self::loans(kitty_id) {
+			Some(loan) if loan.in_liquidation => loan,
+			_ => return,
+		};
+		let lender = match loan.lender.clone() {
+			Some(lender) => lender,
+			None => return,
+		};
+		let pool = Self::loan_account();
+		let debt = Self::loan_debt(&loan, <system::Module<T>>::block_number());
+		let to_lender = received.min(debt);
+		let _ = T::Currency::transfer(&pool, &lender, to_lender, ExistenceRequirement::AllowDeath);
+		let surplus = received - to_lender;
+		if !surplus.is_zero() {
+			let _ = T::Currency::transfer(
+				&pool,
+				&loan.borrower,
+				surplus,
+				ExistenceRequirement::AllowDeath,
+			);
+		}
+		<Loans<T>>::remove(kitty_id);
+		Self::deposit_event(RawEvent::LoanLiquidated(loan.borrower, kitty_id, to_lender));
+	}
+
+	/// The canonical floor-price suggestion for a kitty: the deposit plus
+	/// a rarity premium of one percent of the deposit per rarity point.
+	/// Deliberately a pure function of on-chain state, so unsigned
+	/// submissions carrying it can be verified on chain.
+	pub fn suggest_price(kitty_id: T::KittyIndex) -> Option<BalanceOf<T>> {
+		let rarity = Self::rarity_score(kitty_id)?;
+		let deposit = T::KittyDeposit::get();
+		Some(deposit + deposit / 100u32.into() * rarity.into())
+	}
+
+	/// The deterministic trait-JSON render of a kitty: its phenotype
+	/// attributes, base stats and rarity score. Byte-identical on every
+	/// node for a given DNA.
+	pub fn render_kitty(kitty_id: T::KittyIndex) -> Option<Vec<u8>> {
+		let attributes = Self::attributes(kitty_id)?;
+		let stats = Self::base_stats(kitty_id)?;
+		let rarity = Self::rarity_score(kitty_id)?;
+		let mut json = Vec::new();
+		let fields: [(&[u8], u32); 8] = [
+			(b"fur", attributes.fur as u32),
+			(b"eyes", attributes.eyes as u32),
+			(b"pattern", attributes.pattern as u32),
+			(b"strength", stats.strength),
+			(b"agility", stats.agility),
+			(b"stamina", stats.stamina),
+			(b"rarity", rarity),
+			(b"soulbound", Self::is_soulbound(kitty_id) as u32),
+		];
+		json.push(b'{');
+		for (i, (name, value)) in fields.iter().enumerate() {
+			if i > 0 {
+				json.push(b',');
+			}
+			json.push(b'"');
+			json.extend_from_slice(name);
+			json.extend_from_slice(b"\":");
+			Self::push_decimal(&mut json, *value);
+		}
+		json.push(b'}');
+		Some(json)
+	}
+
+	/// Append the decimal representation of `value` to `out`.
+	fn push_decimal(out: &mut Vec<u8>, value: u32) {
+		if value >= 10 {
+			Self::push_decimal(out, value / 10);
+		}
+		out.push(b'0' + (value % 10) as u8);
+	}
+
+	/// The scalar rarity score of a kitty's phenotype; higher is rarer.
+	/// Pattern dominates, with fur and eyes as tie-breakers.
+	pub fn rarity_score(kitty_id: T::KittyIndex) -> Option<u32> {
+		Self::attributes(kitty_id)
+			.map(|a| (a.pattern as u32) * 64 + (a.fur as u32) * 8 + a.eyes as u32)
+	}
+
+	/// Re-rank `kitty_id` on the bounded rarity leaderboard after its DNA
+	/// was set or changed.
+	fn update_leaderboard(kitty_id: T::KittyIndex) {
+		let score = match Self::rarity_score(kitty_id) {
+			Some(score) => score,
+			None => return,
+		};
+		<RarityLeaderboard<T>>::mutate(|board| {
+			board.retain(|(id, _)| *id != kitty_id);
+			let position = board.iter().position(|(_, held)| *held < score).unwrap_or(board.len());
+			board.insert(position, (kitty_id, score));
+			board.truncate(T::MaxLeaderboardSize::get() as usize);
+		});
+	}
+
+	/// The first block at or after `at` in which breeding is open. Returns
+	/// `at` itself while breeding is open or no season is configured.
+	pub fn next_breeding_window(at: T::BlockNumber) -> T::BlockNumber {
+		match Self::breeding_season() {
+			None => at,
+			Some((open, period)) => {
+				let phase = at % period;
+				if phase < open {
+					at
+				} else {
+					at + (period - phase)
+				}
+			}
+		}
+	}
+
+	/// The difficulty percentages in force at the current total supply:
+	/// the row with the highest threshold the supply has crossed, or
+	/// `(100, 100)` when no row applies.
+	fn current_difficulty() -> (u32, u32) {
+		let supply: u32 = Self::kitties_count().saturated_into::<u32>();
+		Self::difficulty_table()
+			.iter()
+			.rev()
+			.find(|(at_least, _, _)| supply >= *at_least)
+			.map_or((100, 100), |(_, fee, cooldown)| (*fee, *cooldown))
+	}
+
+	/// The breeding fee at the current total supply: the governed (or
+	/// compiled) base fee scaled by the difficulty table's fee percentage.
+	pub fn current_breed_fee() -> BalanceOf<T> {
+		let (fee_percent, _) = Self::current_difficulty();
+		let base = Self::breed_fee_override().unwrap_or_else(T::BreedFee::get);
+		base.saturating_mul(fee_percent.into()) / 100u32.into()
+	}
+
+	/// The breeding cooldown at the current total supply: the governed
+	/// (or compiled) base cooldown scaled by the difficulty table's
+	/// cooldown percentage.
+	pub fn current_breed_cooldown() -> T::BlockNumber {
+		let (_, cooldown_percent) = Self::current_difficulty();
+		let base = Self::breed_cooldown_override().unwrap_or_else(T::BreedCooldown::get);
+		base.saturating_mul(cooldown_percent.into()) / 100u32.into()
+	}
+
+	/// The expedited-creation fee, honouring the governed override.
+	pub fn current_create_fee() -> BalanceOf<T> {
+		Self::create_fee_override().unwrap_or_else(T::ExpeditedCreateFee::get)
+	}
+
+	/// The market commission, honouring the governed override.
+	pub fn current_market_fee() -> Percent {
+		Self::market_fee_override().unwrap_or_else(T::MarketFeePercent::get)
+	}
+
+	/// The base stats decoded from a kitty's DNA, before equipment.
+	pub fn base_stats(kitty_id: T::KittyIndex) -> Option<KittyStats> {
+		Self::kitties(kitty_id).map(|kitty| KittyStats {
+			strength: (kitty.0[3] % 100) as u32,
+			agility: (kitty.0[4] % 100) as u32,
+			stamina: (kitty.0[5] % 100) as u32,
+			charm: (kitty.0[6] % 100) as u32,
+		})
+	}
+
+	/// The stats a kitty brings to battles and races: base DNA stats plus
+	/// the bonuses of every equipped item. This is the single source of
+	/// truth for stat calculations; it serves the cached sheet and only
+	/// falls back to the gene math when no sheet exists.
+	pub fn effective_stats(kitty_id: T::KittyIndex) -> Option<KittyStats> {
+		if let Some(stats) = Self::stat_sheet(kitty_id) {
+			return Some(stats);
+		}
+		Self::compute_stats(kitty_id)
+	}
+
+	/// Decode the DNA and fold in every equipped item's bonuses. Only the
+	/// cache maintenance below should call this; everyone else reads
+	/// `effective_stats`.
+	fn compute_stats(kitty_id: T::KittyIndex) -> Option<KittyStats> {
+		let mut stats = Self::base_stats(kitty_id)?;
+		for item_id in Self::equipped(kitty_id) {
+			if let Some(bonus) = Self::items(item_id) {
+				stats.strength = stats.strength.saturating_add(bonus.strength);
+				stats.agility = stats.agility.saturating_add(bonus.agility);
+				stats.stamina = stats.stamina.saturating_add(bonus.stamina);
+				stats.charm = stats.charm.saturating_add(bonus.charm);
+			}
+		}
+		Some(stats)
+	}
+
+	/// Recompute and store a kitty's stat sheet. Called whenever the inputs
+	/// change: mint, breed, fusion, reroll and equipment changes.
+	fn refresh_stat_sheet(kitty_id: T::KittyIndex) {
+		match Self::compute_stats(kitty_id) {
+			Some(stats) => <StatSheets<T>>::insert(kitty_id, stats),
+			None => <StatSheets<T>>::remove(kitty_id),
+		}
+	}
+
+	/// Append an entry to a kitty's provenance log, dropping the oldest entry
+	/// once the bound is reached.
+	fn note_provenance(kitty_id: T::KittyIndex, who: &T::AccountId, kind: TransferKind) {
+		let now = <system::Module<T>>::block_number();
+		<Provenance<T>>::mutate(kitty_id, |log| {
+			if log.len() as u32 >= T::MaxProvenanceEntries::get() {
+				log.remove(0);
+			}
+			log.push((who.clone(), now, kind));
+		});
+	}
+
+	fn insert_kitty(owner: &T::AccountId, kitty_id: T::KittyIndex, kitty: Kitty) {
+		<DnaIndex<T>>::insert(kitty.0, kitty_id);
+		<Kitties<T>>::insert(kitty_id, kitty);
+		Self::refresh_stat_sheet(kitty_id);
+		<KittiesCount<T>>::mutate(|count| *count += One::one());
+		<KittyOwners<T>>::insert(kitty_id, owner);
+		Self::credit_holding(owner);
+		<BornAt<T>>::insert(kitty_id, <system::Module<T>>::block_number());
+		// The mint includes the first period of state rent.
+		<RentPaidUntil<T>>::insert(
+			kitty_id,
+			<system::Module<T>>::block_number() + T::RentPeriod::get(),
+		);
+		// Minted kitties are generation zero; `do_breed` re-files kittens
+		// under their real generation.
+		<KittiesByGeneration<T>>::insert(0u32, kitty_id, ());
+		GenerationCounts::mutate(0u32, |count| *count += 1);
+		GenZeroSupply::mutate(|supply| *supply += 1);
+		MintsThisBlock::mutate(|count| *count += 1);
+		// Newborns start well-fed.
+		<Vitals<T>>::insert(kitty_id, KittyVitals {
+			energy: T::MaxEnergy::get(),
+			updated_at: <system::Module<T>>::block_number(),
+		});
+		Self::note_ownership_milestones(owner, kitty_id);
+		Self::update_leaderboard(kitty_id);
+	}
+
+	/// Unlock the achievements implied by `owner` now holding `kitty_id`.
+	fn note_ownership_milestones(owner: &T::AccountId, kitty_id: T::KittyIndex) {
+		if Self::owned_kitties_count(owner) == 1 {
+			Self::unlock_achievement(owner, Achievement::FirstKitty);
+		}
+		// Pattern 15 is the top of the phenotype range: maximum rarity.
+		if Self::attributes(kitty_id).map(|a| a.pattern == 15).unwrap_or(false) {
+			Self::unlock_achievement(owner, Achievement::MaxRarityOwner);
+		}
+	}
+
+	/// Record an achievement the first time it is earned.
+	fn unlock_achievement(who: &T::AccountId, achievement: Achievement) {
+		if Self::achievements(who, achievement).is_none() {
+			<Achievements<T>>::insert(who, achievement, <system::Module<T>>::block_number());
+			Self::deposit_event(RawEvent::AchievementUnlocked(who.clone(), achievement));
+		}
+	}
+
+	/// The kitty's energy right now, with the decay since the last stored
+	/// update applied. Hibernating kitties hold the energy they slept
+	/// with.
+	pub fn current_energy(kitty_id: T::KittyIndex) -> u32 {
+		let vitals = Self::vitals(kitty_id);
+		if Self::hibernating(kitty_id).is_some() {
+			return vitals.energy;
+		}
+		let elapsed: u32 = <system::Module<T>>::block_number()
+			.saturating_sub(vitals.updated_at)
+			.saturated_into();
+		vitals.energy.saturating_sub(elapsed.saturating_mul(T::EnergyDecayPerBlock::get()))
+	}
+
+	/// Whether the kitty has been at zero energy for longer than the grace
+	/// period. Always false while perma-death is disabled or energy does not
+	/// decay.
+	pub fn is_departed(kitty_id: T::KittyIndex) -> bool {
+		let decay = T::EnergyDecayPerBlock::get();
+		if !T::PermaDeathEnabled::get() || decay == 0 || Self::hibernating(kitty_id).is_some() {
+			return false;
+		}
+		let vitals = Self::vitals(kitty_id);
+		// The first block at which the energy has fully decayed away.
+		let zero_at = vitals.updated_at
+			+ T::BlockNumber::from((vitals.energy + decay - 1) / decay);
+		<system::Module<T>>::block_number() > zero_at + T::DepartureGracePeriod::get()
+	}
+
+	/// Settle the auctions due at `now`, observing the per-block cap and
+	/// carrying any overflow to the next block.
+	fn settle_due_auctions(now: T::BlockNumber) -> Weight {
+		let mut due = SettlementOverflow::<T>::take();
+		due.extend(<AuctionsByEnd<T>>::take(now));
+		if due.is_empty() {
+			return 0;
+		}
+
+		let cap = T::MaxAuctionSettlementsPerBlock::get() as usize;
+		let carried = if due.len() > cap { due.split_off(cap) } else { Vec::new() };
+		let settled = due.len() as Weight;
+		for kitty_id in due {
+			Self::settle_auction(kitty_id);
+		}
+		if !carried.is_empty() {
+			<SettlementOverflow<T>>::put(carried);
+		}
+		settled * 50_000
+	}
+
+	/// Route a collected fee: the configured burn share is destroyed and
+	/// tallied, and the remainder goes to the market fee beneficiary —
+	/// or is likewise destroyed and tallied when none is set.
+	fn route_fee(fee: NegativeImbalanceOf<T>) {
+		let burn = T::FeeBurnPercent::get() * fee.peek();
+		let (burned, rest) = fee.split(burn);
+		let mut amount = burned.peek();
+		drop(burned);
+		match T::MarketFeeBeneficiary::get() {
+			Some(beneficiary) => T::Currency::resolve_creating(&beneficiary, rest),
+			None => {
+				amount = amount.saturating_add(rest.peek());
+				drop(rest);
+			}
+		}
+		if !amount.is_zero() {
+			<TotalFeesBurned<T>>::mutate(|total| *total = total.saturating_add(amount));
+		}
+	}
+
+	/// Move a sale payment from `payer` to `seller`, deducting the market
+	/// commission (sent to the configured beneficiary or burned) and then any
+	/// revenue splits from the net proceeds; the remainder goes to the
+	/// seller. The whole amount is withdrawn up front so there is a single
+	/// point of failure and no partial settlement. Returns the fee taken.
+	fn settle_payment(
+		payer: &T::AccountId,
+		seller: &T::AccountId,
+		amount: BalanceOf<T>,
+		splits: &[(T::AccountId, Percent)],
+	) -> sp_std::result::Result<BalanceOf<T>, DispatchError> {
+		let fee = Self::current_market_fee() * amount;
+		let net = amount - fee;
+
+		let withdrawn = T::Currency::withdraw(
+			payer,
+			amount,
+			WithdrawReason::Transfer.into(),
+			ExistenceRequirement::KeepAlive,
+		)?;
+		let (fee_imbalance, mut rest) = withdrawn.split(fee);
+		Self::route_fee(fee_imbalance);
+		for (beneficiary, share) in splits {
+			let (cut, remainder) = rest.split(*share * net);
+			T::Currency::resolve_creating(beneficiary, cut);
+			rest = remainder;
+		}
+		T::Currency::resolve_creating(seller, rest);
+		<TradeVolume<T>>::mutate(|volume| *volume = volume.saturating_add(amount));
+		Ok(fee)
+	}
+
+	/// Open a listing, keeping the active-listings counter in step.
+	fn insert_listing(
+		kitty_id: T::KittyIndex,
+		listing: Listing<T::AccountId, BalanceOf<T>, AssetIdOf<T>>,
+	) {
+		<Listings<T>>::insert(kitty_id, listing);
+		ActiveListings::mutate(|count| *count += 1);
+	}
+
+	/// Close a listing, if one is open, keeping the active-listings
+	/// counter in step.
+	fn take_listing(
+		kitty_id: T::KittyIndex,
+	) -> Option<Listing<T::AccountId, BalanceOf<T>, AssetIdOf<T>>> {
+		let listing = <Listings<T>>::take(kitty_id);
+		if listing.is_some() {
+			ActiveListings::mutate(|count| *count = count.saturating_sub(1));
+		}
+		listing
+	}
+
+	/// The amount a buyer actually pays for `listing` right now: the
+	/// stored price, or its conversion through the oracle for a
+	/// reference-priced listing. Errors while the feed has no quote, so
+	/// such listings simply cannot settle until the feed recovers.
+	fn effective_listing_price(
+		listing: &Listing<T::AccountId, BalanceOf<T>, AssetIdOf<T>>,
+	) -> sp_std::result::Result<BalanceOf<T>, DispatchError> {
+		if listing.reference_priced {
+			T::PriceOracle::reference_to_native(listing.price)
+				.ok_or_else(|| Error::<T>::PriceUnavailable.into())
+		} else {
+			Ok(listing.price)
+		}
+	}
+
+	/// The asset-denominated sibling of `settle_payment`, moving the
+	/// payment through `T::Fungibles` with the same fee and split shape.
+	/// The native fee is burned when no beneficiary is configured, which
+	/// the asset interface cannot express, so no fee is taken then. The
+	/// payer's balance is checked up front so the transfer sequence only
+	/// fails on a pathological asset implementation.
+	fn settle_asset_payment(
+		asset: &AssetIdOf<T>,
+		payer: &T::AccountId,
+		seller: &T::AccountId,
+		amount: BalanceOf<T>,
+		splits: &[(T::AccountId, Percent)],
+	) -> sp_std::result::Result<BalanceOf<T>, DispatchError> {
+		ensure!(
+			T::Fungibles::balance_of(asset, payer) >= amount.saturated_into::<u128>(),
+			Error::<T>::InsufficientAssetBalance
+		);
+		let fee = match T::MarketFeeBeneficiary::get() {
+			Some(beneficiary) => {
+				let fee = Self::current_market_fee() * amount;
+				T::Fungibles::transfer(asset, payer, &beneficiary, fee.saturated_into::<u128>())?;
+				fee
+			}
+			None => Zero::zero(),
+		};
+		let net = amount - fee;
+		let mut rest = net;
+		for (beneficiary, share) in splits {
+			let cut = *share * net;
+			T::Fungibles::transfer(asset, payer, beneficiary, cut.saturated_into::<u128>())?;
+			rest -= cut;
+		}
+		T::Fungibles::transfer(asset, payer, seller, rest.saturated_into::<u128>())?;
+		Ok(fee)
+	}
+
+	/// Hand an offer's or bid's funds back: unreserve for the native
+	/// currency, pay back out of the payment pool for assets.
+	fn release_offer_funds(
+		offerer: &T::AccountId,
+		amount: BalanceOf<T>,
+		asset: Option<AssetIdOf<T>>,
+	) {
+		match asset {
+			Some(asset) => {
+				let _ = T::Fungibles::transfer(
+					&asset,
+					&Self::payment_pool_account(),
+					offerer,
+					amount.saturated_into::<u128>(),
+				);
+			}
+			None => {
+				T::Currency::unreserve(offerer, amount);
+			}
+		}
+	}
+
+	/// Settle a single ended auction: deliver the kitty to the winner and pay
+	/// the seller, or pass the auction if there was no (able) winner.
+	fn settle_auction(kitty_id: T::KittyIndex) {
+		let auction = match <Auctions<T>>::take(kitty_id) {
+			Some(auction) => auction,
+			None => return,
+		};
+		<KittyLocks<T>>::remove(kitty_id);
+		if let Some(winner) = auction.top_bidder {
+			if auction.asset.is_none() {
+				T::Currency::unreserve(&winner, auction.top_bid);
+			}
+			let can_deliver = Self::ensure_can_hold_one_more(&winner).is_ok()
+				&& T::Currency::reserve(&winner, T::KittyDeposit::get()).is_ok();
+			if can_deliver {
+				let settled = match auction.asset {
+					Some(asset) => Self::settle_asset_payment(
+						&asset,
+						&Self::payment_pool_account(),
+						&auction.seller,
+						auction.top_bid,
+						&[],
+					),
+					None => Self::settle_payment(&winner, &auction.seller, auction.top_bid, &[]),
+				};
+				match settled {
+					Ok(fee) => {
+						T::Currency::unreserve(&auction.seller, T::KittyDeposit::get());
+						Self::do_transfer(&auction.seller, &winner, kitty_id);
+						Self::note_provenance(kitty_id, &winner, TransferKind::Auction);
+						// Liquidation proceeds landed on the loan pool as
+						// the seller; route them to the lender now.
+						Self::settle_loan_proceeds(kitty_id, auction.top_bid - fee);
+						Self::deposit_event(RawEvent::AuctionSettled(
+							kitty_id, winner, auction.top_bid, fee,
+						));
+						return;
+					}
+					Err(_) => T::Currency::unreserve(&winner, T::KittyDeposit::get()),
+				};
+			}
+			// The pooled bid goes back to a winner who could not settle.
+			if auction.asset.is_some() {
+				Self::release_offer_funds(&winner, auction.top_bid, auction.asset);
+			}
+		}
+		// No bids, or the winner could not pay or take delivery; the kitty
+		// stays with the seller.
+		Self::deposit_event(RawEvent::AuctionPassed(kitty_id));
+	}
+
+	/// Sweep expired and stale market state: transfer offers past their
+	/// expiry, listings whose seller no longer holds the kitty, purchase
+	/// offers that lapsed or whose reserves would otherwise be stranded on
+	/// burned or departed kitties, and lapsed breeding delegations. This FRAME version has no `on_idle`
+	/// hook, so the sweep runs every block under a fixed id budget; the
+	/// cursor makes it resume where the previous block left off and wrap
+	/// around the id space. Finished auctions need no sweeping — they
+	/// settle themselves in `on_initialize`.
+	fn sweep_market_state(now: T::BlockNumber) -> Weight {
+		// The cursor walks dense sequential ids; under content-addressed
+		// ids it would visit nothing real, so the sweep stands down and
+		// expired offers and delegations wait for their explicit cancel
+		// and revoke paths.
+		if T::ContentAddressedIds::get() {
+			return 0;
+		}
+		let total = Self::kitties_count();
+		if total.is_zero() {
+			return 0;
+		}
+		let budget = T::CleanupBudget::get();
+		let mut cursor = Self::cleanup_cursor();
+		let mut removed = 0u32;
+		for _ in 0..budget {
+			if cursor >= total {
+				cursor = Zero::zero();
+			}
+			let kitty_id = cursor;
+			cursor += One::one();
+
+			if let Some(pending) = Self::pending_transfer(kitty_id) {
+				if pending.expires_at < now {
+					<PendingTransfers<T>>::remove(kitty_id);
+					removed += 1;
+				}
+			}
+			if let Some(listing) = Self::listings(kitty_id) {
+				let holder = Self::kitty_owner(kitty_id);
+				let held_for_sale = holder == Some(listing.seller)
+					|| holder == Some(Self::listing_custody_account());
+				if !held_for_sale || Self::is_departed(kitty_id) {
+					Self::take_listing(kitty_id);
+					<KittyLocks<T>>::remove(kitty_id);
+					removed += 1;
+				}
+			}
+			// Purchase offers are released when the kitty can never again
+			// be delivered (burned or departed) or when the offer itself
+			// has lapsed.
+			let undeliverable =
+				!<Kitties<T>>::contains_key(kitty_id) || Self::is_departed(kitty_id);
+			let stranded: Vec<(T::AccountId, BalanceOf<T>, Option<AssetIdOf<T>>)> =
+				<Offers<T>>::iter_prefix(kitty_id)
+					.filter(|(_, (_, expires_at, _))| {
+						undeliverable || expires_at.map_or(false, |expiry| expiry < now)
+					})
+					.map(|(offerer, (amount, _, asset))| (offerer, amount, asset))
+					.collect();
+			for (offerer, amount, asset) in stranded {
+				Self::release_offer_funds(&offerer, amount, asset);
+				<Offers<T>>::remove(kitty_id, &offerer);
+				removed += 1;
+			}
+			let lapsed: Vec<T::AccountId> = <BreedingDelegations<T>>::iter_prefix(kitty_id)
+				.filter(|(_, (_, expiry, _))| *expiry < now)
+				.map(|(delegate, _)| delegate)
+				.collect();
+			let delegations_lapsed = !lapsed.is_empty();
+			for delegate in lapsed {
+				<BreedingDelegations<T>>::remove(kitty_id, &delegate);
+				removed += 1;
+			}
+			if delegations_lapsed {
+				Self::release_delegation_lock(kitty_id);
+			}
+		}
+		<CleanupCursor<T>>::put(cursor);
+		if removed > 0 {
+			Self::deposit_event(RawEvent::MarketStateSwept(removed));
+		}
+		budget as Weight * 10_000
+	}
+
+	/// Settle the sealed auctions whose reveal phase ends at `now`.
+	fn settle_due_sealed_auctions(now: T::BlockNumber) -> Weight {
+		let due = <SealedAuctionsByEnd<T>>::take(now);
+		if due.is_empty() {
+			return 0;
+		}
+
+		let count = due.len() as Weight;
+		for kitty_id in due {
+			Self::settle_sealed_auction(kitty_id);
+		}
+		count * 50_000
+	}
+
+	/// Settle a single sealed auction: slash unrevealed deposits, refund the
+	/// revealed losers, and deliver the kitty to the highest revealed bidder
+	/// at the second price (bounded below by the reserve).
+	fn settle_sealed_auction(kitty_id: T::KittyIndex) {
+		let auction = match <SealedAuctions<T>>::take(kitty_id) {
+			Some(auction) => auction,
+			None => return,
+		};
+		<KittyLocks<T>>::remove(kitty_id);
+		let bids: Vec<(T::AccountId, ([u8; 32], Option<BalanceOf<T>>))> =
+			<SealedBids<T>>::iter_prefix(kitty_id).collect();
+		<SealedBids<T>>::remove_prefix(kitty_id);
+
+		let mut best: Option<(T::AccountId, BalanceOf<T>)> = None;
+		let mut second = auction.reserve_price;
+		for (bidder, (_, revealed)) in bids {
+			let amount = match revealed {
+				Some(amount) => amount,
+				None => {
+					// The commitment was never opened; the deposit is
+					// forfeit and routed like a market fee.
+					let (slashed, _) = T::Currency::slash_reserved(&bidder, auction.bid_deposit);
+					Self::route_fee(slashed);
+					Self::deposit_event(RawEvent::BidDepositSlashed(
+						bidder, kitty_id, auction.bid_deposit,
+					));
+					continue;
+				}
+			};
+			T::Currency::unreserve(&bidder, amount);
+			if amount < auction.reserve_price {
+				continue;
+			}
+			match best.take() {
+				Some((leader, leading)) if leading >= amount => {
+					second = second.max(amount);
+					best = Some((leader, leading));
+				}
+				Some((_, leading)) => {
+					second = second.max(leading);
+					best = Some((bidder, amount));
+				}
+				None => best = Some((bidder, amount)),
+			}
+		}
+
+		if let Some((winner, _)) = best {
+			let can_deliver = Self::ensure_can_hold_one_more(&winner).is_ok()
+				&& T::Currency::reserve(&winner, T::KittyDeposit::get()).is_ok();
+			if can_deliver {
+				match Self::settle_payment(&winner, &auction.seller, second, &[]) {
+					Ok(fee) => {
+						T::Currency::unreserve(&auction.seller, T::KittyDeposit::get());
+						Self::do_transfer(&auction.seller, &winner, kitty_id);
+						Self::note_provenance(kitty_id, &winner, TransferKind::Auction);
+						Self::deposit_event(RawEvent::SealedAuctionSettled(
+							kitty_id, winner, second, fee,
+						));
+						return;
+					}
+					Err(_) => T::Currency::unreserve(&winner, T::KittyDeposit::get()),
+				};
+			}
+		}
+		// No valid reveal, or the winner could not pay or take delivery;
+		// the kitty stays with the seller.
+		Self::deposit_event(RawEvent::SealedAuctionPassed(kitty_id));
+	}
+
+	/// Settle the name auctions ending at `now`. The winner's bid is taken
+	/// in full and routed like a market fee; the winner receives a claim on
+	/// the name.
+	fn settle_due_name_auctions(now: T::BlockNumber) -> Weight {
+		let due = <NameAuctionsByEnd<T>>::take(now);
+		if due.is_empty() {
+			return 0;
+		}
+
+		let count = due.len() as Weight;
+		for name in due {
+			let auction = match <NameAuctions<T>>::take(&name) {
+				Some(auction) => auction,
+				None => continue,
+			};
+			if let Some(winner) = auction.top_bidder {
+				T::Currency::unreserve(&winner, auction.top_bid);
+				match T::Currency::withdraw(
+					&winner,
+					auction.top_bid,
+					WithdrawReason::Fee.into(),
+					ExistenceRequirement::KeepAlive,
+				) {
+					Ok(proceeds) => {
+						match T::MarketFeeBeneficiary::get() {
+							Some(beneficiary) => {
+								T::Currency::resolve_creating(&beneficiary, proceeds)
+							}
+							None => drop(proceeds),
+						}
+						<NameClaims<T>>::insert(&name, &winner);
+						Self::deposit_event(RawEvent::NameAuctionSettled(
+							name, winner, auction.top_bid,
+						));
+						continue;
+					}
+					Err(_) => {}
+				}
+			}
+			Self::deposit_event(RawEvent::NameAuctionPassed(name));
+		}
+		count * 50_000
+	}
+
+	/// Finalize the escrows whose dispute window ends at `now`. Disputed
+	/// escrows are left in place for the arbiter.
+	fn finalize_due_escrows(now: T::BlockNumber) -> Weight {
+		let due = <EscrowsByEnd<T>>::take(now);
+		if due.is_empty() {
+			return 0;
+		}
+
+		let count = due.len() as Weight;
+		for kitty_id in due {
+			let escrow = match Self::escrows(kitty_id) {
+				Some(escrow) => escrow,
+				None => continue,
+			};
+			if escrow.disputed {
+				continue;
+			}
+			<Escrows<T>>::remove(kitty_id);
+			Self::close_escrow(kitty_id, escrow, true);
+		}
+		count * 50_000
+	}
+
+	/// Unwind an escrow that has already been removed from storage. If
+	/// `release_to_buyer` the sale completes: payment settles and the kitty
+	/// changes hands; otherwise, or if the buyer can no longer pay or take
+	/// delivery, the buyer is refunded and the kitty stays with the seller.
+	fn close_escrow(
+		kitty_id: T::KittyIndex,
+		escrow: Escrow<T::AccountId, BalanceOf<T>, T::BlockNumber>,
+		release_to_buyer: bool,
+	) {
+		T::Currency::unreserve(&escrow.buyer, escrow.price);
+		if release_to_buyer {
+			let can_deliver = Self::ensure_can_hold_one_more(&escrow.buyer).is_ok()
+				&& T::Currency::reserve(&escrow.buyer, T::KittyDeposit::get()).is_ok();
+			if can_deliver {
+				match Self::settle_payment(&escrow.buyer, &escrow.seller, escrow.price, &[]) {
+					Ok(fee) => {
+						T::Currency::unreserve(&escrow.seller, T::KittyDeposit::get());
+						Self::do_transfer(&escrow.seller, &escrow.buyer, kitty_id);
+						Self::note_provenance(kitty_id, &escrow.buyer, TransferKind::Sale);
+						Self::deposit_event(RawEvent::EscrowSettled(
+							escrow.seller, escrow.buyer, kitty_id, escrow.price, fee,
+						));
+						return;
+					}
+					Err(_) => T::Currency::unreserve(&escrow.buyer, T::KittyDeposit::get()),
+				};
+			}
+		}
+		Self::deposit_event(RawEvent::EscrowRefunded(kitty_id));
+	}
+
+	/// Default the installment plans whose due date passes at `now`
+	/// unpaid: the down payment moves to the seller, the installments
+	/// paid so far go back to the buyer and the kitty is released from
+	/// its lock. Plans paid early were rescheduled out of this block's
+	/// list and are skipped.
+	fn default_due_installments(now: T::BlockNumber) -> Weight {
+		let due = <InstallmentsByDue<T>>::take(now);
+		if due.is_empty() {
+			return 0;
+		}
+
+		let count = due.len() as Weight;
+		for kitty_id in due {
+			let plan = match Self::installments(kitty_id) {
+				Some(plan) if plan.next_due == now => plan,
+				_ => continue,
+			};
+			<Installments<T>>::remove(kitty_id);
+			<KittyLocks<T>>::remove(kitty_id);
+			let _ = T::Currency::repatriate_reserved(&plan.buyer, &plan.seller, plan.down_payment);
+			T::Currency::unreserve(
+				&plan.buyer,
+				plan.price - plan.outstanding - plan.down_payment,
+			);
+			Self::deposit_event(RawEvent::InstallmentDefaulted(
+				plan.buyer, kitty_id, plan.down_payment,
+			));
+		}
+		count * 50_000
+	}
+
+	/// Deliver every timelocked transfer unlocking at `now`. A delivery
+	/// the recipient cannot take — deposit, holding cap or blacklist —
+	/// leaves the schedule in place for a later `claim_timed_transfer`.
+	fn deliver_due_timed_transfers(now: T::BlockNumber) -> Weight {
+		let due = <TimedTransfersByUnlock<T>>::take(now);
+		let count = due.len() as Weight;
+		for kitty_id in due {
+			if let Some(scheduled) = Self::timed_transfer(kitty_id) {
+				let _ = Self::deliver_timed_transfer(kitty_id, scheduled);
+			}
+		}
+		count * 50_000
+	}
+
+	/// Move a scheduled kitty to its recipient, shuffling the deposit
+	/// like a plain transfer.
+	fn deliver_timed_transfer(
+		kitty_id: T::KittyIndex,
+		scheduled: TimedTransfer<T::AccountId, T::BlockNumber>,
+	) -> DispatchResult {
+		Self::ensure_can_hold_one_more(&scheduled.to)?;
+		Self::ensure_accepts_transfer(&scheduled.to)?;
+		T::Currency::reserve(&scheduled.to, T::KittyDeposit::get())?;
+		T::Currency::unreserve(&scheduled.from, T::KittyDeposit::get());
+		<TimedTransfers<T>>::remove(kitty_id);
+		<KittyLocks<T>>::remove(kitty_id);
+		Self::do_transfer(&scheduled.from, &scheduled.to, kitty_id);
+		Self::note_provenance(kitty_id, &scheduled.to, TransferKind::Transfer);
+		Self::deposit_event(RawEvent::TimedTransferDelivered(
+			scheduled.from,
+			scheduled.to,
+			kitty_id,
+		));
+		Ok(())
+	}
+
+	/// Bump `who`'s holding count, keeping the unique-owner counter and
+	/// the distribution histogram in step.
+	fn credit_holding(who: &T::AccountId) {
+		let before = Self::owned_kitties_count(who);
+		<OwnedKittiesCount<T>>::mutate(who, |count| *count += 1);
+		Self::update_holder_stats(before, before + 1);
+	}
+
+	/// Lower `who`'s holding count, keeping the unique-owner counter and
+	/// the distribution histogram in step.
+	fn debit_holding(who: &T::AccountId) {
+		let before = Self::owned_kitties_count(who);
+		<OwnedKittiesCount<T>>::mutate(who, |count| *count = count.saturating_sub(1));
+		Self::update_holder_stats(before, before.saturating_sub(1));
+	}
+
+	/// Apply a holding-count change to `UniqueOwners` and move the account
+	/// between histogram buckets when it crosses a boundary.
+	fn update_holder_stats(before: u32, after: u32) {
+		if before == 0 && after > 0 {
+			UniqueOwners::mutate(|owners| *owners += 1);
+		} else if before > 0 && after == 0 {
+			UniqueOwners::mutate(|owners| *owners = owners.saturating_sub(1));
+		}
+		let (from, to) = (Self::holding_bucket(before), Self::holding_bucket(after));
+		if from != to {
+			HolderDistribution::mutate(|histogram| {
+				if let Some(bucket) = from {
+					histogram[bucket] = histogram[bucket].saturating_sub(1);
+				}
+				if let Some(bucket) = to {
+					histogram[bucket] += 1;
+				}
+			});
+		}
+	}
+
+	/// The histogram bucket for a holding count: 1, 2–5, 6–20 or 21+.
+	fn holding_bucket(count: u32) -> Option<usize> {
+		match count {
+			0 => None,
+			1 => Some(0),
+			2..=5 => Some(1),
+			6..=20 => Some(2),
+			_ => Some(3),
+		}
+	}
+
+	/// Stamp `who` as active now, resetting their inactivity clock.
+	fn note_activity(who: &T::AccountId) {
+		<LastActivity<T>>::insert(who, <system::Module<T>>::block_number());
+	}
+
+	fn do_transfer(from: &T::AccountId, to: &T::AccountId, kitty_id: T::KittyIndex) {
+		Self::note_activity(from);
+		Self::on_ownership_changed(kitty_id);
+		<Erc721Approvals<T>>::remove(kitty_id);
+		<KittyOwners<T>>::insert(kitty_id, to);
+		Self::debit_holding(from);
+		Self::credit_holding(to);
+		<Counters<T>>::mutate(kitty_id, |c| c.transfers = c.transfers.saturating_add(1));
+		Self::note_ownership_milestones(to, kitty_id);
+	}
+
+	/// Drop the kitty's cattery membership and decrement the cattery's
+	/// member counter.
+	fn leave_cattery(kitty_id: T::KittyIndex, cattery_id: u32) {
+		<KittyCattery<T>>::remove(kitty_id);
+		<Catteries<T>>::mutate(cattery_id, |cattery| {
+			if let Some(cattery) = cattery {
+				cattery.members = cattery.members.saturating_sub(1);
+			}
+		});
+		Self::deposit_event(RawEvent::RemovedFromCattery(kitty_id, cattery_id));
+	}
+
+	/// Whether `who` holds `role` in the kitty's cattery. Membership
+	/// already implies the founder owns the kitty, so the role alone
+	/// settles what a non-owner may do with it.
+	fn has_cattery_role(who: &T::AccountId, kitty_id: T::KittyIndex, role: CatteryRole) -> bool {
+		Self::kitty_cattery(kitty_id)
+			.map(|cattery_id| Self::cattery_role(cattery_id, who) == Some(role))
+			.unwrap_or(false)
+	}
+
+	/// Invalidate market state that named the previous owner, run on every
+	/// ownership change. Settlement paths remove their own state before
+	/// transferring, so anything still standing here is orphaned: stale
+	/// listings and pending transfer offers are dropped, auctions are
+	/// cancelled with every bid reserve refunded, and breeding delegations
+	/// granted by the old owner lapse.
+	fn on_ownership_changed(kitty_id: T::KittyIndex) {
+		let mut invalidated = false;
+		if Self::take_listing(kitty_id).is_some() {
+			<KittyLocks<T>>::remove(kitty_id);
+			invalidated = true;
+		}
+		if <PendingTransfers<T>>::take(kitty_id).is_some() {
+			invalidated = true;
+		}
+		if let Some(plan) = <Installments<T>>::take(kitty_id) {
+			// The buyer gets everything back, down payment included; the
+			// seller chose to move the kitty out from under the plan.
+			T::Currency::unreserve(&plan.buyer, plan.price - plan.outstanding);
+			<InstallmentsByDue<T>>::mutate(plan.next_due, |ids| ids.retain(|id| *id != kitty_id));
+			<KittyLocks<T>>::remove(kitty_id);
+			invalidated = true;
+		}
+		if <HtlcSwaps<T>>::take(kitty_id).is_some() {
+			<KittyLocks<T>>::remove(kitty_id);
+			invalidated = true;
+		}
+		if let Some(scheduled) = <TimedTransfers<T>>::take(kitty_id) {
+			<TimedTransfersByUnlock<T>>::mutate(scheduled.unlock_at, |ids| {
+				ids.retain(|id| *id != kitty_id)
+			});
+			<KittyLocks<T>>::remove(kitty_id);
+			invalidated = true;
+		}
+		if let Some(auction) = <Auctions<T>>::take(kitty_id) {
+			if let Some(bidder) = auction.top_bidder {
+				Self::release_offer_funds(&bidder, auction.top_bid, auction.asset);
+			}
+			<AuctionsByEnd<T>>::mutate(auction.end, |ids| ids.retain(|id| *id != kitty_id));
+			<KittyLocks<T>>::remove(kitty_id);
+			invalidated = true;
+		}
+		if let Some(auction) = <SealedAuctions<T>>::take(kitty_id) {
+			let bids: Vec<(T::AccountId, ([u8; 32], Option<BalanceOf<T>>))> =
+				<SealedBids<T>>::iter_prefix(kitty_id).collect();
+			<SealedBids<T>>::remove_prefix(kitty_id);
+			for (bidder, (_, revealed)) in bids {
+				// Revealed bidders swapped their deposit for the bid
+				// amount; unrevealed ones still hold the deposit.
+				match revealed {
+					Some(amount) => T::Currency::unreserve(&bidder, amount),
+					None => T::Currency::unreserve(&bidder, auction.bid_deposit),
+				};
+			}
+			<SealedAuctionsByEnd<T>>::mutate(auction.reveal_end, |ids| {
+				ids.retain(|id| *id != kitty_id)
+			});
+			<KittyLocks<T>>::remove(kitty_id);
+			invalidated = true;
+		}
+		let delegates: Vec<T::AccountId> =
+			<BreedingDelegations<T>>::iter_prefix(kitty_id).map(|(delegate, _)| delegate).collect();
+		if !delegates.is_empty() {
+			for delegate in delegates {
+				<BreedingDelegations<T>>::remove(kitty_id, &delegate);
+			}
+			Self::release_delegation_lock(kitty_id);
+			invalidated = true;
+		}
+		// Cattery membership is the previous owner's branding; it does not
+		// follow the kitty to a new holder.
+		if let Some(cattery_id) = Self::kitty_cattery(kitty_id) {
+			Self::leave_cattery(kitty_id, cattery_id);
+		}
+		if invalidated {
+			Self::deposit_event(RawEvent::MarketStateInvalidated(kitty_id));
+		}
+	}
+
+	/// Whether `who` earns the chain-level holder perks: enough kitties
+	/// held, or the `MaxRarityOwner` achievement. The achievement is
+	/// permanent by design, so a collector who once held a top-rarity
+	/// kitty keeps qualifying after selling it.
+	pub fn qualifies_for_holder_perks(who: &T::AccountId) -> bool {
+		Self::owned_kitties_count(who) >= T::HolderPerkMinKitties::get()
+			|| Self::achievements(who, Achievement::MaxRarityOwner).is_some()
+	}
+
+	/// `fee` after the holder discount, for the runtime's fee-charging
+	/// adapter. Non-holders pay full price.
+	pub fn discounted_fee(who: &T::AccountId, fee: BalanceOf<T>) -> BalanceOf<T> {
+		if Self::qualifies_for_holder_perks(who) {
+			fee - T::HolderFeeDiscount::get() * fee
+		} else {
+			fee
+		}
+	}
+}
+
+impl<T: Trait> ValidateUnsigned for Module<T> {
+	type Call = Call<T>;
+
+	/// Admit the off-chain worker's unsigned submissions. Both calls are
+	/// pure functions of on-chain state, so validity is checked by
+	/// recomputation; the `provides` tags double as replay protection
+	/// within a transaction pool.
+	fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+		match call {
+			Call::submit_phenotype_hash(kitty_id, hash) => {
+				let render =
+					Self::render_kitty(*kitty_id).ok_or(InvalidTransaction::Custom(1))?;
+				if blake2_256(&render) != *hash || Self::phenotype_hash(*kitty_id).is_some() {
+					return InvalidTransaction::Stale.into();
+				}
+				ValidTransaction::with_tag_prefix("KittiesPhenotype")
+					.priority(TransactionPriority::max_value() / 2)
+					.and_provides(("phenotype", kitty_id))
+					.longevity(16)
+					.propagate(true)
+					.build()
+			},
+			Call::submit_price_suggestion(kitty_id, price) => {
+				let expected =
+					Self::suggest_price(*kitty_id).ok_or(InvalidTransaction::Custom(2))?;
+				let stale = Self::suggested_price(*kitty_id)
+					.map(|(current, _)| current == *price)
+					.unwrap_or(false);
+				if *price != expected || stale {
+					return InvalidTransaction::Stale.into();
+				}
+				ValidTransaction::with_tag_prefix("KittiesPrice")
+					.priority(TransactionPriority::max_value() / 2)
+					.and_provides(("price", kitty_id, price))
+					.longevity(16)
+					.propagate(true)
+					.build()
+			},
+			_ => InvalidTransaction::Call.into(),
+		}
+	}
+}
+
+impl<T: Trait> ProvideInherent for Module<T> {
+	type Call = Call<T>;
+	type Error = MakeFatalError<()>;
+	const INHERENT_IDENTIFIER: InherentIdentifier = INHERENT_IDENTIFIER;
+
+	/// Include the drop scheduled for the block under construction, if
+	/// any. The schedule lives on chain, so no inherent data is read.
+	fn create_inherent(_data: &InherentData) -> Option<Self::Call> {
+		let now = <system::Module<T>>::block_number();
+		Self::gen_zero_drop(now).map(Call::apply_gen_zero_drop)
+	}
+
+	fn check_inherent(call: &Self::Call, _data: &InherentData) -> Result<(), Self::Error> {
+		match call {
+			Call::apply_gen_zero_drop(count) => {
+				let now = <system::Module<T>>::block_number();
+				if Self::gen_zero_drop(now) == Some(*count) {
+					Ok(())
+				} else {
+					Err(().into())
+				}
+			},
+			_ => Ok(()),
+		}
+	}
+}
+
+/// The node-side provider for the gen-0 drop inherent. The schedule is
+/// on-chain state, so there is no data to gather — registering the
+/// provider only reserves the identifier with the authorship machinery.
+#[cfg(feature = "std")]
+pub struct GenZeroDropInherentDataProvider;
+
+#[cfg(feature = "std")]
+impl sp_inherents::ProvideInherentData for GenZeroDropInherentDataProvider {
+	fn inherent_identifier(&self) -> &'static InherentIdentifier {
+		&INHERENT_IDENTIFIER
+	}
+
+	fn provide_inherent_data(
+		&self,
+		inherent_data: &mut InherentData,
+	) -> Result<(), sp_inherents::Error> {
+		inherent_data.put_data(INHERENT_IDENTIFIER, &())
+	}
+
+	fn error_to_string(&self, _error: &[u8]) -> Option<String> {
+		Some("gen-0 drop inherent failed".into())
+	}
+}
+
+/// A signed extension bumping transaction priority for qualifying kitty
+/// holders, giving the NFT utility at the chain level. It charges
+/// nothing and changes no state; the matching fee discount lives in
+/// [`Module::discounted_fee`], which the runtime's fee-charging adapter
+/// applies when computing the final fee.
+#[derive(Encode, Decode, Clone, Eq, PartialEq)]
+pub struct CheckKittyHolder<T: Trait + Send + Sync>(sp_std::marker::PhantomData<T>);
+
+impl<T: Trait + Send + Sync> CheckKittyHolder<T> {
+	pub fn new() -> Self {
+		Self(sp_std::marker::PhantomData)
+	}
+}
+
+impl<T: Trait + Send + Sync> sp_std::fmt::Debug for CheckKittyHolder<T> {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		write!(f, "CheckKittyHolder")
+	}
+
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		Ok(())
+	}
+}
+
+impl<T: Trait + Send + Sync> SignedExtension for CheckKittyHolder<T>
+where
+	<T as system::Trait>::Call: Dispatchable,
+{
+	const IDENTIFIER: &'static str = "CheckKittyHolder";
+	type AccountId = T::AccountId;
+	type Call = <T as system::Trait>::Call;
+	type AdditionalSigned = ();
+	type Pre = ();
+
+	fn additional_signed(&self) -> sp_std::result::Result<(), TransactionValidityError> {
+		Ok(())
+	}
+
+	fn validate(
+		&self,
+		who: &Self::AccountId,
+		_call: &Self::Call,
+		_info: &DispatchInfoOf<Self::Call>,
+		_len: usize,
+	) -> TransactionValidity {
+		let priority = if Module::<T>::qualifies_for_holder_perks(who) {
+			T::HolderPriorityBump::get()
+		} else {
+			0
+		};
+		Ok(ValidTransaction { priority, ..Default::default() })
+	}
+}
@@ -0,0 +1,495 @@
+// Creating mock runtime here
+
+use crate::{Module, Trait};
+use std::cell::RefCell;
+use sp_core::H256;
+use frame_support::{
+	impl_outer_origin, parameter_types,
+	traits::{Contains, Get},
+	weights::Weight,
+};
+use sp_runtime::{
+	traits::{BlakeTwo256, IdentityLookup}, testing::Header, Perbill, Percent,
+};
+use frame_system as system;
+
+impl_outer_origin! {
+	pub enum Origin for Test {}
+}
+
+#[derive(Clone, Eq, PartialEq)]
+pub struct Test;
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const MaximumBlockWeight: Weight = 1024;
+	pub const MaximumBlockLength: u32 = 2 * 1024;
+	pub const AvailableBlockRatio: Perbill = Perbill::from_percent(75);
+}
+impl system::Trait for Test {
+	type Origin = Origin;
+	type Call = ();
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = ();
+	type BlockHashCount = BlockHashCount;
+	type MaximumBlockWeight = MaximumBlockWeight;
+	type DbWeight = ();
+	type BlockExecutionWeight = ();
+	type ExtrinsicBaseWeight = ();
+	type MaximumExtrinsicWeight = MaximumBlockWeight;
+	type MaximumBlockLength = MaximumBlockLength;
+	type AvailableBlockRatio = AvailableBlockRatio;
+	type Version = ();
+	type ModuleToIndex = ();
+	type AccountData = balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+}
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+}
+impl balances::Trait for Test {
+	type Balance = u64;
+	type Event = ();
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+}
+parameter_types! {
+	pub const KittyDeposit: u64 = 100;
+	pub const BreedFee: u64 = 50;
+	pub const BreedCooldown: u64 = 0;
+	pub const MaxKittySupply: u32 = 1_000;
+	pub const GenZeroCap: u32 = 1_000;
+	pub const GenZeroTranchePeriod: u64 = 10;
+	pub const MaxKittiesPerAccount: u32 = 10;
+	pub const MaxAuctionSettlementsPerBlock: u32 = 2;
+	pub const MarketFeePercent: Percent = Percent::from_percent(10);
+	pub const MarketFeeBeneficiary: Option<u64> = Some(999);
+	pub const MaxSaleSplits: u32 = 4;
+	pub const MaxProvenanceEntries: u32 = 4;
+	pub const EscrowDisputeWindow: u64 = 5;
+	pub const InstallmentDownPayment: Percent = Percent::from_percent(25);
+	pub const InstallmentInterval: u64 = 5;
+	pub const MaxInstallments: u32 = 12;
+	pub const MaxMintsPerBlock: u32 = 15;
+	pub const ExpeditedCreateFee: u64 = 40;
+	pub const PowMintEnabled: bool = true;
+	pub const FaucetEra: u64 = 10;
+	pub const MaxNameLength: u32 = 16;
+	pub const ReferralCredit: u64 = 30;
+	pub const BreedingPassDiscount: Percent = Percent::from_percent(20);
+	pub const MaxEquippedItems: u32 = 2;
+	pub const MaxEnergy: u32 = 100;
+	pub const EnergyDecayPerBlock: u32 = 1;
+	pub const FeedCostPerEnergy: u64 = 2;
+	pub const BreedEnergyCost: u32 = 10;
+	pub const PermaDeathEnabled: bool = true;
+	pub const DepartureGracePeriod: u64 = 5;
+	pub const MinHibernationPeriod: u64 = 5;
+	pub const TimedTransferCancelBuffer: u64 = 3;
+	pub const InheritanceChallengeWindow: u64 = 5;
+	pub const MaxLeaderboardSize: u32 = 3;
+	pub const RerollWindow: u64 = 5;
+	pub const RerollFee: u64 = 60;
+	pub const FusionFee: u64 = 80;
+	pub const CleanupBudget: u32 = 4;
+	pub const RentPeriod: u64 = 10;
+	pub const RentPerPeriod: u64 = 5;
+	pub const RentGracePeriod: u64 = 5;
+	pub const ReapFinderFee: u64 = 20;
+	pub const MaxSameBlockEndings: u32 = 4;
+	pub const MaxContestEntrants: u32 = 16;
+	pub const MaxActiveContests: u32 = 8;
+	pub const MaxDifficultyRows: u32 = 4;
+	pub const RelayerThreshold: u32 = 2;
+	pub const HolderPerkMinKitties: u32 = 3;
+	pub const HolderPriorityBump: u64 = 50;
+	pub const HolderFeeDiscount: Percent = Percent::from_percent(20);
+	pub const LoanToValue: Percent = Percent::from_percent(50);
+	pub const LiquidationAuctionLength: u64 = 5;
+	pub const MaxBundleSize: u32 = 3;
+}
+thread_local! {
+	static CREATE_INTERVAL: RefCell<u64> = RefCell::new(0);
+}
+
+/// The free-creation interval, adjustable per test; most tests leave it at
+/// zero so repeated creations in one block keep working.
+pub struct CreateInterval;
+impl Get<u64> for CreateInterval {
+	fn get() -> u64 {
+		CREATE_INTERVAL.with(|interval| *interval.borrow())
+	}
+}
+
+pub fn set_create_interval(interval: u64) {
+	CREATE_INTERVAL.with(|cell| *cell.borrow_mut() = interval);
+}
+
+thread_local! {
+	static GEN_ZERO_TRANCHE: RefCell<u32> = RefCell::new(1_000);
+}
+
+/// The gen-0 tranche size, adjustable per test; most tests leave it large
+/// enough that the release schedule never interferes.
+pub struct GenZeroTrancheSize;
+impl Get<u32> for GenZeroTrancheSize {
+	fn get() -> u32 {
+		GEN_ZERO_TRANCHE.with(|tranche| *tranche.borrow())
+	}
+}
+
+pub fn set_gen_zero_tranche(size: u32) {
+	GEN_ZERO_TRANCHE.with(|cell| *cell.borrow_mut() = size);
+}
+
+thread_local! {
+	static CUSTODIAL_LISTINGS: RefCell<bool> = RefCell::new(false);
+}
+
+/// Whether listings are custodial, adjustable per test; the default is the
+/// classic lock-flag mode.
+pub struct CustodialListings;
+impl Get<bool> for CustodialListings {
+	fn get() -> bool {
+		CUSTODIAL_LISTINGS.with(|mode| *mode.borrow())
+	}
+}
+
+pub fn set_custodial_listings(custodial: bool) {
+	CUSTODIAL_LISTINGS.with(|cell| *cell.borrow_mut() = custodial);
+}
+
+thread_local! {
+	static REFERENCE_RATE: RefCell<Option<u64>> = RefCell::new(None);
+}
+
+/// A stub price feed: one reference unit is worth a settable number of
+/// native units, or nothing while no rate is set (the default).
+pub struct TestPriceOracle;
+impl crate::PriceOracle<u64> for TestPriceOracle {
+	fn reference_to_native(amount: u64) -> Option<u64> {
+		REFERENCE_RATE.with(|rate| rate.borrow().map(|rate| amount * rate))
+	}
+}
+
+pub fn set_reference_rate(rate: Option<u64>) {
+	REFERENCE_RATE.with(|cell| *cell.borrow_mut() = rate);
+}
+
+thread_local! {
+	static VERIFIED_IDENTITIES: RefCell<Vec<u64>> = RefCell::new(Vec::new());
+}
+
+/// A stub identity registry: exactly the accounts a test marks verified.
+pub struct TestIdentity;
+impl crate::IdentityProvider<u64> for TestIdentity {
+	fn is_verified(who: &u64) -> bool {
+		VERIFIED_IDENTITIES.with(|verified| verified.borrow().contains(who))
+	}
+}
+
+pub fn set_identity_verified(who: u64, verified: bool) {
+	VERIFIED_IDENTITIES.with(|cell| {
+		let mut list = cell.borrow_mut();
+		list.retain(|account| *account != who);
+		if verified {
+			list.push(who);
+		}
+	});
+}
+
+thread_local! {
+	static FAUCET_ENABLED: RefCell<bool> = RefCell::new(false);
+}
+
+/// Whether the testnet faucet is open, adjustable per test; closed by
+/// default like a value-bearing chain.
+pub struct FaucetEnabled;
+impl Get<bool> for FaucetEnabled {
+	fn get() -> bool {
+		FAUCET_ENABLED.with(|enabled| *enabled.borrow())
+	}
+}
+
+pub fn set_faucet_enabled(enabled: bool) {
+	FAUCET_ENABLED.with(|cell| *cell.borrow_mut() = enabled);
+}
+
+thread_local! {
+	static PERMISSIONED_MINTING: RefCell<bool> = RefCell::new(false);
+}
+
+/// Whether primary issuance is gated on the minter list, adjustable per
+/// test; open by default.
+pub struct PermissionedMinting;
+impl Get<bool> for PermissionedMinting {
+	fn get() -> bool {
+		PERMISSIONED_MINTING.with(|enabled| *enabled.borrow())
+	}
+}
+
+pub fn set_permissioned_minting(enabled: bool) {
+	PERMISSIONED_MINTING.with(|cell| *cell.borrow_mut() = enabled);
+}
+
+thread_local! {
+	static FEE_BURN_PERCENT: RefCell<Percent> = RefCell::new(Percent::zero());
+}
+
+/// The deflationary burn share, adjustable per test; zero by default so
+/// fee-routing tests see the whole commission.
+pub struct FeeBurnPercent;
+impl Get<Percent> for FeeBurnPercent {
+	fn get() -> Percent {
+		FEE_BURN_PERCENT.with(|percent| *percent.borrow())
+	}
+}
+
+pub fn set_fee_burn_percent(percent: Percent) {
+	FEE_BURN_PERCENT.with(|cell| *cell.borrow_mut() = percent);
+}
+
+thread_local! {
+	static CONTENT_ADDRESSED_IDS: RefCell<bool> = RefCell::new(false);
+}
+
+/// Whether kitty ids are derived from DNA instead of allocated
+/// sequentially, adjustable per test; sequential by default.
+pub struct ContentAddressedIds;
+impl Get<bool> for ContentAddressedIds {
+	fn get() -> bool {
+		CONTENT_ADDRESSED_IDS.with(|enabled| *enabled.borrow())
+	}
+}
+
+pub fn set_content_addressed_ids(enabled: bool) {
+	CONTENT_ADDRESSED_IDS.with(|cell| *cell.borrow_mut() = enabled);
+}
+
+/// The minter list while permissioned minting is on: account 1 only.
+pub struct Minters;
+impl Contains<u64> for Minters {
+	fn sorted_members() -> Vec<u64> {
+		vec![1]
+	}
+}
+
+/// A stub foreign registry: creature 7 exists, is owned by account 2 and
+/// carries all-nines genes.
+pub struct TestCreatures;
+impl crate::ForeignCreature<u64, u32> for TestCreatures {
+	const SPECIES: [u8; 4] = *b"pupy";
+
+	fn owner_of(creature_id: &u32) -> Option<u64> {
+		if *creature_id == 7 { Some(2) } else { None }
+	}
+
+	fn dna_of(creature_id: &u32) -> Option<[u8; 16]> {
+		if *creature_id == 7 { Some([9u8; 16]) } else { None }
+	}
+}
+
+thread_local! {
+	static SHARE_LEDGER: RefCell<Vec<Vec<(u64, u128)>>> = RefCell::new(Vec::new());
+}
+
+/// An in-memory share issuer standing in for `pallet-assets`: each issued
+/// class is a list of `(holder, amount)` entries.
+pub struct TestFungibles;
+impl crate::Fungibles<u64> for TestFungibles {
+	type AssetId = u32;
+
+	fn issue(beneficiary: &u64, total: u128) -> Result<u32, sp_runtime::DispatchError> {
+		SHARE_LEDGER.with(|ledger| {
+			let mut ledger = ledger.borrow_mut();
+			ledger.push(vec![(*beneficiary, total)]);
+			Ok(ledger.len() as u32 - 1)
+		})
+	}
+
+	fn balance_of(asset: &u32, who: &u64) -> u128 {
+		SHARE_LEDGER.with(|ledger| {
+			ledger.borrow()
+				.get(*asset as usize)
+				.map(|holders| {
+					holders.iter()
+						.filter(|(holder, _)| holder == who)
+						.map(|(_, amount)| amount)
+						.sum()
+				})
+				.unwrap_or(0)
+		})
+	}
+
+	fn retire(asset: &u32, _who: &u64) -> frame_support::dispatch::DispatchResult {
+		SHARE_LEDGER.with(|ledger| {
+			ledger.borrow_mut()
+				.get_mut(*asset as usize)
+				.map(|holders| holders.clear())
+				.ok_or_else(|| sp_runtime::DispatchError::Other("unknown share class"))
+		})?;
+		Ok(())
+	}
+
+	fn transfer(
+		asset: &u32,
+		from: &u64,
+		to: &u64,
+		amount: u128,
+	) -> frame_support::dispatch::DispatchResult {
+		if Self::balance_of(asset, from) < amount {
+			return Err(sp_runtime::DispatchError::Other("insufficient shares"));
+		}
+		move_shares(*asset, *from, *to, amount);
+		Ok(())
+	}
+}
+
+/// Move `amount` shares between holders, as a DEX or transfer in the
+/// asset pallet would.
+pub fn move_shares(asset: u32, from: u64, to: u64, amount: u128) {
+	SHARE_LEDGER.with(|ledger| {
+		let mut ledger = ledger.borrow_mut();
+		let holders = &mut ledger[asset as usize];
+		for entry in holders.iter_mut() {
+			if entry.0 == from {
+				entry.1 -= amount;
+			}
+		}
+		holders.push((to, amount));
+	});
+}
+
+/// A trivial EVM address scheme for tests: the account id sits big-endian
+/// in the low eight bytes of the address.
+pub struct TestAddresses;
+impl crate::erc721::AddressMapping<u64> for TestAddresses {
+	fn account(address: &[u8; 20]) -> u64 {
+		let mut raw = [0u8; 8];
+		raw.copy_from_slice(&address[12..]);
+		u64::from_be_bytes(raw)
+	}
+
+	fn address(who: &u64) -> [u8; 20] {
+		let mut out = [0u8; 20];
+		out[12..].copy_from_slice(&who.to_be_bytes());
+		out
+	}
+}
+
+impl Trait for Test {
+	type Event = ();
+	type Currency = Balances;
+	type Randomness = crate::DeterministicRandomness<Test>;
+	type KittyIndex = u32;
+	type ContentAddressedIds = ContentAddressedIds;
+	type KittyDeposit = KittyDeposit;
+	type BreedFee = BreedFee;
+	type BreedCooldown = BreedCooldown;
+	type MaxKittySupply = MaxKittySupply;
+	type GenZeroCap = GenZeroCap;
+	type GenZeroTrancheSize = GenZeroTrancheSize;
+	type GenZeroTranchePeriod = GenZeroTranchePeriod;
+	type MaxKittiesPerAccount = MaxKittiesPerAccount;
+	type MaxAuctionSettlementsPerBlock = MaxAuctionSettlementsPerBlock;
+	type MarketFeePercent = MarketFeePercent;
+	type FeeBurnPercent = FeeBurnPercent;
+	type MarketFeeBeneficiary = MarketFeeBeneficiary;
+	type MaxSaleSplits = MaxSaleSplits;
+	type MaxProvenanceEntries = MaxProvenanceEntries;
+	type MaxNameLength = MaxNameLength;
+	type PowMintEnabled = PowMintEnabled;
+	type PermissionedMinting = PermissionedMinting;
+	type MintOrigin = system::EnsureSignedBy<Minters, u64>;
+	type FaucetEnabled = FaucetEnabled;
+	type FaucetEra = FaucetEra;
+	type CreateInterval = CreateInterval;
+	type ExpeditedCreateFee = ExpeditedCreateFee;
+	type MaxMintsPerBlock = MaxMintsPerBlock;
+	type ReferralCredit = ReferralCredit;
+	type BreedingPassDiscount = BreedingPassDiscount;
+	type MaxEquippedItems = MaxEquippedItems;
+	type MaxEnergy = MaxEnergy;
+	type EnergyDecayPerBlock = EnergyDecayPerBlock;
+	type FeedCostPerEnergy = FeedCostPerEnergy;
+	type BreedEnergyCost = BreedEnergyCost;
+	type PermaDeathEnabled = PermaDeathEnabled;
+	type DepartureGracePeriod = DepartureGracePeriod;
+	type MinHibernationPeriod = MinHibernationPeriod;
+	type TimedTransferCancelBuffer = TimedTransferCancelBuffer;
+	type InheritanceChallengeWindow = InheritanceChallengeWindow;
+	type RerollWindow = RerollWindow;
+	type RerollFee = RerollFee;
+	type FusionFee = FusionFee;
+	type ForeignCreatureId = u32;
+	type ForeignCreature = TestCreatures;
+	type Fungibles = TestFungibles;
+	type EscrowDisputeWindow = EscrowDisputeWindow;
+	type ArbiterOrigin = system::EnsureRoot<u64>;
+	type InstallmentDownPayment = InstallmentDownPayment;
+	type InstallmentInterval = InstallmentInterval;
+	type MaxInstallments = MaxInstallments;
+	type AdminOrigin = system::EnsureRoot<u64>;
+	type ForceOrigin = system::EnsureRoot<u64>;
+	type MaxLeaderboardSize = MaxLeaderboardSize;
+	type CleanupBudget = CleanupBudget;
+	type RentPeriod = RentPeriod;
+	type RentPerPeriod = RentPerPeriod;
+	type RentGracePeriod = RentGracePeriod;
+	type ReapFinderFee = ReapFinderFee;
+	type MaxSameBlockEndings = MaxSameBlockEndings;
+	type MaxContestEntrants = MaxContestEntrants;
+	type MaxActiveContests = MaxActiveContests;
+	type MaxDifficultyRows = MaxDifficultyRows;
+	type RelayerThreshold = RelayerThreshold;
+	type HolderPerkMinKitties = HolderPerkMinKitties;
+	type HolderPriorityBump = HolderPriorityBump;
+	type HolderFeeDiscount = HolderFeeDiscount;
+	type LoanToValue = LoanToValue;
+	type LiquidationAuctionLength = LiquidationAuctionLength;
+	type MaxBundleSize = MaxBundleSize;
+	type CustodialListings = CustodialListings;
+	type PriceOracle = TestPriceOracle;
+	type Identity = TestIdentity;
+}
+/// The test extrinsic type carrying unsigned OCW submissions.
+pub type Extrinsic = sp_runtime::testing::TestXt<crate::Call<Test>, ()>;
+
+impl<LocalCall> frame_system::offchain::SendTransactionTypes<LocalCall> for Test
+where
+	crate::Call<Test>: From<LocalCall>,
+{
+	type OverarchingCall = crate::Call<Test>;
+	type Extrinsic = Extrinsic;
+}
+
+pub type System = system::Module<Test>;
+pub type Balances = balances::Module<Test>;
+pub type KittiesModule = Module<Test>;
+
+// This function basically just builds a genesis storage key/value store according to
+// our desired mockup.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	balances::GenesisConfig::<Test> {
+		balances: vec![(1, 10_000), (2, 10_000), (3, 10_000)],
+	}.assimilate_storage(&mut t).unwrap();
+	t.into()
+}
+
+/// Advance to block `n`, running the kitties module's `on_initialize` for
+/// every block on the way.
+pub fn run_to_block(n: u64) {
+	use frame_support::traits::OnInitialize;
+	while System::block_number() < n {
+		System::set_block_number(System::block_number() + 1);
+		KittiesModule::on_initialize(System::block_number());
+	}
+}
@@ -0,0 +1,3385 @@
+// Tests to be written here
+
+use crate::{Error, NonFungibleCollateral, mock::*};
+use frame_support::{assert_ok, assert_noop};
+use frame_system::RawOrigin;
+use sp_runtime::Percent;
+
+#[test]
+fn create_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_eq!(KittiesModule::kitties_count(), 1);
+		assert_eq!(KittiesModule::kitty_owner(0), Some(1));
+		assert_eq!(KittiesModule::owned_kitties_count(1), 1);
+		// The kitty deposit is reserved from the creator.
+		assert_eq!(Balances::reserved_balance(1), 100);
+	});
+}
+
+#[test]
+fn transfer_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::transfer(Origin::signed(1), 2, 0));
+		assert_eq!(KittiesModule::kitty_owner(0), Some(2));
+		assert_eq!(KittiesModule::owned_kitties_count(1), 0);
+		assert_eq!(KittiesModule::owned_kitties_count(2), 1);
+		// The deposit moves with the kitty.
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(2), 100);
+	});
+}
+
+#[test]
+fn transfer_fails_for_non_owner() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_noop!(
+			KittiesModule::transfer(Origin::signed(2), 3, 0),
+			Error::<Test>::NotKittyOwner
+		);
+	});
+}
+
+#[test]
+fn breed_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::breed(Origin::signed(1), 0, 1));
+		assert_eq!(KittiesModule::kitties_count(), 3);
+		assert_eq!(KittiesModule::kitty_owner(2), Some(1));
+	});
+}
+
+#[test]
+fn locked_kitty_cannot_be_transferred() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::register_collateral_taker(RawOrigin::Root.into(), 9));
+		assert_ok!(KittiesModule::lock_collateral(0, &9));
+		assert_noop!(
+			KittiesModule::transfer(Origin::signed(1), 2, 0),
+			Error::<Test>::KittyLocked
+		);
+		assert_ok!(KittiesModule::release_collateral(0, &9));
+		assert_ok!(KittiesModule::transfer(Origin::signed(1), 2, 0));
+	});
+}
+
+#[test]
+fn seize_collateral_moves_ownership() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::register_collateral_taker(RawOrigin::Root.into(), 9));
+		assert_ok!(KittiesModule::lock_collateral(0, &9));
+		assert_ok!(KittiesModule::seize_collateral(0, &9, &2));
+		assert_eq!(KittiesModule::kitty_owner(0), Some(2));
+		assert_eq!(KittiesModule::kitty_lock(0), None);
+	});
+}
+
+#[test]
+fn auction_settles_automatically_at_end() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::start_auction(Origin::signed(1), 0, 100, 5, None));
+		assert_ok!(KittiesModule::bid(Origin::signed(2), 0, 150));
+		assert_noop!(
+			KittiesModule::bid(Origin::signed(3), 0, 150),
+			Error::<Test>::BidTooLow
+		);
+
+		let seller_free = Balances::free_balance(1);
+		run_to_block(6);
+		assert_eq!(KittiesModule::kitty_owner(0), Some(2));
+		assert_eq!(KittiesModule::auctions(0), None);
+		// The seller receives the bid net of the 10% market fee, plus their
+		// released kitty deposit; the fee goes to the beneficiary.
+		assert_eq!(Balances::free_balance(1), seller_free + 135 + 100);
+		assert_eq!(Balances::free_balance(999), 15);
+	});
+}
+
+#[test]
+fn auction_without_bids_passes() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::start_auction(Origin::signed(1), 0, 100, 5, None));
+		run_to_block(6);
+		assert_eq!(KittiesModule::kitty_owner(0), Some(1));
+		assert_eq!(KittiesModule::auctions(0), None);
+	});
+}
+
+#[test]
+fn sell_and_buy_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::sell(Origin::signed(1), 0, 300, vec![], None, false, None));
+		assert_noop!(KittiesModule::buy(Origin::signed(1), 0), Error::<Test>::OwnKittyMarketAction);
+		let seller_free = Balances::free_balance(1);
+		assert_ok!(KittiesModule::buy(Origin::signed(2), 0));
+		assert_eq!(KittiesModule::kitty_owner(0), Some(2));
+		assert_eq!(KittiesModule::listings(0), None);
+		// 10% commission is deducted from the sale price.
+		assert_eq!(Balances::free_balance(1), seller_free + 270 + 100);
+	});
+}
+
+#[test]
+fn sale_splits_are_paid_from_net_proceeds() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		// 20% of the net proceeds go to account 5; net is 270 after the 10%
+		// market fee, so account 5 receives 54 and the seller 216.
+		assert_ok!(KittiesModule::sell(
+			Origin::signed(1), 0, 300,
+			vec![(5, sp_runtime::Percent::from_percent(20))], None, false, None,
+		));
+		let seller_free = Balances::free_balance(1);
+		assert_ok!(KittiesModule::buy(Origin::signed(2), 0));
+		assert_eq!(Balances::free_balance(5), 54);
+		assert_eq!(Balances::free_balance(1), seller_free + 216 + 100);
+	});
+}
+
+#[test]
+fn cancellation_flows_release_funds_and_state() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+
+		// Listing can be cancelled by the seller.
+		assert_ok!(KittiesModule::sell(Origin::signed(1), 0, 300, vec![], None, false, None));
+		assert_ok!(KittiesModule::cancel_listing(Origin::signed(1), 0));
+		assert_noop!(KittiesModule::buy(Origin::signed(2), 0), Error::<Test>::NotForSale);
+
+		// Offers refund the reserved amount on cancellation.
+		assert_ok!(KittiesModule::make_offer(Origin::signed(2), 0, 200, None, None));
+		assert_eq!(Balances::reserved_balance(2), 200);
+		assert_ok!(KittiesModule::cancel_offer(Origin::signed(2), 0));
+		assert_eq!(Balances::reserved_balance(2), 0);
+
+		// Auctions can only be cancelled before the first bid.
+		assert_ok!(KittiesModule::start_auction(Origin::signed(1), 0, 100, 5, None));
+		assert_ok!(KittiesModule::bid(Origin::signed(2), 0, 150));
+		assert_noop!(
+			KittiesModule::cancel_auction(Origin::signed(1), 0),
+			Error::<Test>::AuctionHasBids
+		);
+	});
+}
+
+#[test]
+fn provenance_records_ownership_changes() {
+	new_test_ext().execute_with(|| {
+		use crate::TransferKind;
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::transfer(Origin::signed(1), 2, 0));
+		assert_eq!(
+			KittiesModule::provenance(0),
+			vec![(1, 1, TransferKind::Mint), (2, 1, TransferKind::Transfer)]
+		);
+
+		// The log is bounded: the oldest entries are dropped first.
+		assert_ok!(KittiesModule::transfer(Origin::signed(2), 1, 0));
+		assert_ok!(KittiesModule::transfer(Origin::signed(1), 2, 0));
+		assert_ok!(KittiesModule::transfer(Origin::signed(2), 1, 0));
+		assert_eq!(KittiesModule::provenance(0).len(), 4);
+		assert_eq!(KittiesModule::provenance(0)[0], (2, 1, TransferKind::Transfer));
+	});
+}
+
+#[test]
+fn breeding_agreement_pays_fee_and_assigns_child() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(2), 0));
+		// Account 1 proposes breeding its kitty 0 with 2's kitty 1, paying 2
+		// a fee of 80 and assigning the child to account 3.
+		assert_ok!(KittiesModule::propose_breeding(Origin::signed(1), 0, 1, 80, 3));
+
+		let proposer_free = Balances::free_balance(1);
+		let acceptor_free = Balances::free_balance(2);
+		assert_ok!(KittiesModule::accept_breeding(Origin::signed(2), 0, 1));
+		assert_eq!(KittiesModule::kitty_owner(2), Some(3));
+		assert_eq!(Balances::free_balance(1), proposer_free - 80);
+		assert_eq!(Balances::free_balance(2), acceptor_free + 80);
+		assert_eq!(KittiesModule::breeding_agreements(0, 1), None);
+	});
+}
+
+#[test]
+fn breeding_agreement_requires_acceptor_ownership() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(2), 0));
+		assert_ok!(KittiesModule::propose_breeding(Origin::signed(1), 0, 1, 0, 1));
+		assert_noop!(
+			KittiesModule::accept_breeding(Origin::signed(3), 0, 1),
+			Error::<Test>::NotKittyOwner
+		);
+	});
+}
+
+#[test]
+fn delegated_breeding_works_and_consumes_uses() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_noop!(
+			KittiesModule::breed(Origin::signed(2), 0, 1),
+			Error::<Test>::NoBreedingRights
+		);
+
+		assert_ok!(KittiesModule::delegate_breeding(Origin::signed(1), 0, 2, 1, 100, Percent::zero()));
+		assert_ok!(KittiesModule::delegate_breeding(Origin::signed(1), 1, 2, 1, 100, Percent::zero()));
+		assert_ok!(KittiesModule::breed(Origin::signed(2), 0, 1));
+		// The child belongs to the owner, not the delegate.
+		assert_eq!(KittiesModule::kitty_owner(2), Some(1));
+		// Single-use delegations are exhausted.
+		assert_eq!(KittiesModule::breeding_delegations(0, 2), None);
+		assert_noop!(
+			KittiesModule::breed(Origin::signed(2), 0, 1),
+			Error::<Test>::NoBreedingRights
+		);
+	});
+}
+
+#[test]
+fn expired_breeding_delegation_is_rejected() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::delegate_breeding(Origin::signed(1), 0, 2, 5, 3, Percent::zero()));
+		assert_ok!(KittiesModule::delegate_breeding(Origin::signed(1), 1, 2, 5, 3, Percent::zero()));
+		run_to_block(4);
+		assert_noop!(
+			KittiesModule::breed(Origin::signed(2), 0, 1),
+			Error::<Test>::NoBreedingRights
+		);
+	});
+}
+
+#[test]
+fn counters_track_transfers_and_breedings() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::breed(Origin::signed(1), 0, 1));
+		assert_ok!(KittiesModule::transfer(Origin::signed(1), 2, 0));
+		assert_eq!(KittiesModule::counters(0).breedings, 1);
+		assert_eq!(KittiesModule::counters(0).transfers, 1);
+		assert_eq!(KittiesModule::counters(1).breedings, 1);
+		assert_eq!(KittiesModule::counters(2).transfers, 0);
+	});
+}
+
+#[test]
+fn genesis_kitties_are_derived_from_seed() {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	crate::GenesisConfig::<Test> {
+		kitties: vec![(1, b"seed".to_vec())],
+	}.assimilate_storage(&mut t).unwrap();
+	sp_io::TestExternalities::from(t).execute_with(|| {
+		let dna = sp_io::hashing::blake2_128(b"seed");
+		assert_eq!(KittiesModule::kitties(0), Some(crate::Kitty(dna)));
+		assert_eq!(KittiesModule::kitty_owner(0), Some(1));
+
+		// The canonical phenotype decoding is derived from the DNA bytes.
+		let attributes = KittiesModule::attributes(0).unwrap();
+		assert_eq!(attributes.fur, dna[0] % 8);
+		assert_eq!(attributes.eyes, dna[1] % 8);
+		assert_eq!(attributes.pattern, dna[2] % 16);
+		assert_eq!(KittiesModule::attributes(42), None);
+	});
+}
+
+#[test]
+fn deterministic_randomness_is_reproducible() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		// Same block, same sender: the raw roll collides, so the second
+		// kitty's DNA is re-derived from the first with nonce zero.
+		let dna = KittiesModule::kitties(0).unwrap().0;
+		let rerolled = sp_io::hashing::blake2_128(&codec::Encode::encode(&(dna, 0u8)));
+		assert_eq!(KittiesModule::kitties(1), Some(crate::Kitty(rerolled)));
+
+		run_to_block(2);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		// A different block yields different DNA.
+		assert_ne!(KittiesModule::kitties(0), KittiesModule::kitties(2));
+	});
+}
+
+#[test]
+fn breed_fails_for_same_parent() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_noop!(
+			KittiesModule::breed(Origin::signed(1), 0, 0),
+			Error::<Test>::RequireDifferentParent
+		);
+	});
+}
+
+#[test]
+fn escrow_sale_finalizes_after_dispute_window() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::sell(Origin::signed(1), 0, 300, vec![], None, false, None));
+		assert_ok!(KittiesModule::buy_in_escrow(Origin::signed(2), 0));
+
+		// While in escrow the kitty is frozen and the payment reserved.
+		assert_eq!(Balances::reserved_balance(2), 300);
+		assert_noop!(
+			KittiesModule::transfer(Origin::signed(1), 3, 0),
+			Error::<Test>::KittyInEscrow
+		);
+
+		// Window is 5 blocks; settlement happens automatically at its end.
+		run_to_block(6);
+		assert_eq!(KittiesModule::escrows(0), None);
+		assert_eq!(KittiesModule::kitty_owner(0), Some(2));
+		assert_eq!(Balances::free_balance(1), 9_900 + 270 + 100);
+		assert_eq!(Balances::free_balance(2), 10_000 - 100 - 300);
+		assert_eq!(Balances::free_balance(999), 30);
+	});
+}
+
+#[test]
+fn disputed_escrow_waits_for_arbiter() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::sell(Origin::signed(1), 0, 300, vec![], None, false, None));
+		assert_ok!(KittiesModule::buy_in_escrow(Origin::signed(2), 0));
+		assert_ok!(KittiesModule::raise_escrow_dispute(Origin::signed(2), 0));
+		assert_noop!(
+			KittiesModule::raise_escrow_dispute(Origin::signed(3), 0),
+			Error::<Test>::NotEscrowParty
+		);
+
+		// A disputed escrow does not finalize when the window passes.
+		run_to_block(6);
+		assert_eq!(KittiesModule::kitty_owner(0), Some(1));
+		assert_eq!(Balances::reserved_balance(2), 300);
+
+		// The arbiter rules for the seller: the buyer is made whole.
+		assert_ok!(KittiesModule::resolve_escrow_dispute(RawOrigin::Root.into(), 0, false));
+		assert_eq!(KittiesModule::escrows(0), None);
+		assert_eq!(KittiesModule::kitty_owner(0), Some(1));
+		assert_eq!(Balances::free_balance(2), 10_000);
+	});
+}
+
+#[test]
+fn tips_pay_the_owner_and_accumulate() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::tip_kitty(Origin::signed(2), 0, 25));
+		assert_ok!(KittiesModule::tip_kitty(Origin::signed(3), 0, 75));
+		assert_eq!(Balances::free_balance(1), 9_900 + 100);
+		assert_eq!(KittiesModule::lifetime_tips(0), 100);
+
+		// The counter follows the kitty, not the owner.
+		assert_ok!(KittiesModule::transfer(Origin::signed(1), 2, 0));
+		assert_ok!(KittiesModule::tip_kitty(Origin::signed(3), 0, 10));
+		assert_eq!(KittiesModule::lifetime_tips(0), 110);
+		assert_noop!(
+			KittiesModule::tip_kitty(Origin::signed(2), 0, 10),
+			Error::<Test>::OwnKittyMarketAction
+		);
+		assert_noop!(KittiesModule::tip_kitty(Origin::signed(3), 0, 0), Error::<Test>::ZeroTip);
+	});
+}
+
+#[test]
+fn feeding_restores_energy_after_decay() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_eq!(KittiesModule::current_energy(0), 100);
+
+		// Energy decays lazily, one point per block in the mock.
+		run_to_block(11);
+		assert_eq!(KittiesModule::current_energy(0), 90);
+
+		// Two units of balance buy one point of energy, capped at the max.
+		assert_ok!(KittiesModule::feed(Origin::signed(2), 0, 12));
+		assert_eq!(KittiesModule::current_energy(0), 96);
+		assert_ok!(KittiesModule::feed(Origin::signed(2), 0, 50));
+		assert_eq!(KittiesModule::current_energy(0), 100);
+		assert_eq!(Balances::free_balance(2), 10_000 - 12 - 50);
+		assert_noop!(
+			KittiesModule::feed(Origin::signed(2), 0, 1),
+			Error::<Test>::FeedTooSmall
+		);
+	});
+}
+
+#[test]
+fn breeding_spends_parent_energy() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::breed(Origin::signed(1), 0, 1));
+		assert_eq!(KittiesModule::current_energy(0), 90);
+		assert_eq!(KittiesModule::current_energy(1), 90);
+		// The newborn starts well-fed.
+		assert_eq!(KittiesModule::current_energy(2), 100);
+	});
+}
+
+#[test]
+fn neglected_kitty_departs_and_can_be_buried() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+
+		// Energy empties at block 101; the 5-block grace runs out after 106.
+		run_to_block(106);
+		assert!(!KittiesModule::is_departed(0));
+		assert_noop!(
+			KittiesModule::bury_departed(Origin::signed(2), 0),
+			Error::<Test>::KittyNotDeparted
+		);
+
+		run_to_block(107);
+		assert!(KittiesModule::is_departed(0));
+		assert_noop!(
+			KittiesModule::transfer(Origin::signed(1), 2, 0),
+			Error::<Test>::KittyDeparted
+		);
+		assert_noop!(
+			KittiesModule::breed(Origin::signed(1), 0, 1),
+			Error::<Test>::KittyDeparted
+		);
+
+		let free = Balances::free_balance(1);
+		assert_ok!(KittiesModule::bury_departed(Origin::signed(2), 0));
+		assert_eq!(Balances::free_balance(1), free + 100);
+		assert_eq!(KittiesModule::kitties(0), None);
+		assert_eq!(KittiesModule::kitty_owner(0), None);
+		assert_eq!(KittiesModule::owned_kitties_count(1), 1);
+	});
+}
+
+#[test]
+fn effective_stats_combine_dna_and_equipment() {
+	new_test_ext().execute_with(|| {
+		use crate::KittyStats;
+
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		let dna = KittiesModule::kitties(0).unwrap().0;
+		let base = KittiesModule::base_stats(0).unwrap();
+		assert_eq!(base.strength, (dna[3] % 100) as u32);
+		assert_eq!(base.agility, (dna[4] % 100) as u32);
+		assert_eq!(base.stamina, (dna[5] % 100) as u32);
+		assert_eq!(base.charm, (dna[6] % 100) as u32);
+		assert_eq!(KittiesModule::effective_stats(0), Some(base));
+
+		let bonus = KittyStats { strength: 5, agility: 0, stamina: 3, charm: 1 };
+		assert_ok!(KittiesModule::register_item(RawOrigin::Root.into(), 7, bonus));
+		assert_noop!(
+			KittiesModule::equip(Origin::signed(1), 0, 8),
+			Error::<Test>::UnknownItem
+		);
+		assert_ok!(KittiesModule::equip(Origin::signed(1), 0, 7));
+		let buffed = KittiesModule::effective_stats(0).unwrap();
+		assert_eq!(buffed.strength, base.strength + 5);
+		assert_eq!(buffed.stamina, base.stamina + 3);
+
+		assert_ok!(KittiesModule::unequip(Origin::signed(1), 0, 7));
+		assert_eq!(KittiesModule::effective_stats(0), Some(base));
+		assert_eq!(KittiesModule::effective_stats(42), None);
+	});
+}
+
+#[test]
+fn breeding_season_gates_do_breed() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+
+		// Open for the first 3 blocks of every 10-block cycle.
+		assert_ok!(KittiesModule::set_breeding_season(RawOrigin::Root.into(), Some((3, 10))));
+		assert_ok!(KittiesModule::breed(Origin::signed(1), 0, 1));
+
+		run_to_block(5);
+		assert_eq!(KittiesModule::next_breeding_window(5), 10);
+		assert_noop!(
+			KittiesModule::breed(Origin::signed(1), 0, 1),
+			Error::<Test>::BreedingClosed
+		);
+
+		run_to_block(10);
+		assert_ok!(KittiesModule::breed(Origin::signed(1), 0, 1));
+
+		assert_ok!(KittiesModule::set_breeding_season(RawOrigin::Root.into(), None));
+		run_to_block(15);
+		assert_ok!(KittiesModule::breed(Origin::signed(1), 0, 1));
+		assert_noop!(
+			KittiesModule::set_breeding_season(RawOrigin::Root.into(), Some((11, 10))),
+			Error::<Test>::InvalidBreedingSeason
+		);
+	});
+}
+
+#[test]
+fn kitty_provider_reflects_registry_state() {
+	new_test_ext().execute_with(|| {
+		use crate::KittyProvider;
+
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_eq!(<KittiesModule as KittyProvider<u64, u32>>::owner_of(0), Some(1));
+		assert!(<KittiesModule as KittyProvider<u64, u32>>::exists(0));
+		assert_eq!(
+			<KittiesModule as KittyProvider<u64, u32>>::dna_of(0),
+			Some(KittiesModule::kitties(0).unwrap().0)
+		);
+		assert!(<KittiesModule as KittyProvider<u64, u32>>::can_transfer(0));
+
+		assert_ok!(KittiesModule::register_collateral_taker(RawOrigin::Root.into(), 9));
+		assert_ok!(KittiesModule::lock_collateral(0, &9));
+		assert!(!<KittiesModule as KittyProvider<u64, u32>>::can_transfer(0));
+		assert!(!<KittiesModule as KittyProvider<u64, u32>>::exists(42));
+	});
+}
+
+#[test]
+fn achievements_unlock_once() {
+	new_test_ext().execute_with(|| {
+		use crate::Achievement;
+
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_eq!(KittiesModule::achievements(1, Achievement::FirstKitty), Some(1));
+
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		for i in 0..10 {
+			assert_ok!(KittiesModule::breed(Origin::signed(1), 0, 1));
+			// Hand each kitten off to stay under the per-account cap.
+			assert_ok!(KittiesModule::transfer(Origin::signed(1), 3, 2 + i));
+		}
+		assert_eq!(KittiesModule::breeder_score(1), 10);
+		assert_eq!(KittiesModule::achievements(1, Achievement::TenBreedings), Some(1));
+
+		// A transferred kitty still counts as the recipient's first.
+		assert_ok!(KittiesModule::transfer(Origin::signed(1), 2, 0));
+		assert_eq!(KittiesModule::achievements(2, Achievement::FirstKitty), Some(1));
+	});
+}
+
+#[test]
+fn generations_follow_the_elder_parent() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_eq!(KittiesModule::generation(0), 0);
+
+		assert_ok!(KittiesModule::breed(Origin::signed(1), 0, 1));
+		assert_eq!(KittiesModule::generation(2), 1);
+		assert_ok!(KittiesModule::breed(Origin::signed(1), 0, 2));
+		assert_eq!(KittiesModule::generation(3), 2);
+	});
+}
+
+#[test]
+fn referrals_credit_breeding_fees() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_noop!(
+			KittiesModule::create_with_referral(Origin::signed(2), 2, 0),
+			Error::<Test>::SelfReferral
+		);
+		assert_noop!(
+			KittiesModule::create_with_referral(Origin::signed(1), 2, 0),
+			Error::<Test>::NotNewAccount
+		);
+
+		assert_ok!(KittiesModule::create_with_referral(Origin::signed(2), 1, 0));
+		assert_eq!(KittiesModule::referred_by(2), Some(1));
+		assert_eq!(KittiesModule::fee_credits(1), 30);
+
+		// The credit covers part of the next breeding fee (50 in the mock).
+		let free = Balances::free_balance(1);
+		assert_ok!(KittiesModule::breed(Origin::signed(1), 0, 1));
+		assert_eq!(Balances::free_balance(1), free - 100 - 20);
+		assert_eq!(KittiesModule::fee_credits(1), 0);
+
+		// One referral per account, ever.
+		assert_noop!(
+			KittiesModule::create_with_referral(Origin::signed(2), 1, 0),
+			Error::<Test>::NotNewAccount
+		);
+	});
+}
+
+#[test]
+fn per_block_minting_cap_is_enforced() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		for _ in 0..10 {
+			assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		}
+		for _ in 0..5 {
+			assert_ok!(KittiesModule::create(Origin::signed(2), 0));
+		}
+		assert_eq!(KittiesModule::mints_this_block(), 15);
+		assert_noop!(
+			KittiesModule::create(Origin::signed(2), 0),
+			Error::<Test>::MintRateExceeded
+		);
+
+		// The counter resets with the next block.
+		run_to_block(2);
+		assert_ok!(KittiesModule::create(Origin::signed(2), 0));
+	});
+}
+
+#[test]
+fn free_creations_are_rate_limited_per_account() {
+	new_test_ext().execute_with(|| {
+		set_create_interval(3);
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_noop!(
+			KittiesModule::create(Origin::signed(1), 0),
+			Error::<Test>::CreateRateLimited
+		);
+		// Other accounts are unaffected.
+		assert_ok!(KittiesModule::create(Origin::signed(2), 0));
+
+		// Paying the expedited fee bypasses the interval; the fee is burned.
+		let free = Balances::free_balance(1);
+		assert_ok!(KittiesModule::create_expedited(Origin::signed(1)));
+		assert_eq!(Balances::free_balance(1), free - 100 - 40);
+
+		run_to_block(4);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+	});
+}
+
+#[test]
+fn pow_difficulty_gates_free_creation() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		// No difficulty set: any nonce passes.
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+
+		// An impossible target rejects every nonce.
+		assert_ok!(KittiesModule::set_mint_difficulty(RawOrigin::Root.into(), Some([0u8; 32])));
+		assert_noop!(
+			KittiesModule::create(Origin::signed(2), 0),
+			Error::<Test>::InvalidPowNonce
+		);
+
+		// A trivial target accepts every nonce again.
+		assert_ok!(KittiesModule::set_mint_difficulty(RawOrigin::Root.into(), Some([0xff; 32])));
+		assert_ok!(KittiesModule::create(Origin::signed(2), 7));
+
+		assert_ok!(KittiesModule::set_mint_difficulty(RawOrigin::Root.into(), None));
+		assert_ok!(KittiesModule::create(Origin::signed(3), 0));
+	});
+}
+
+#[test]
+fn reserved_names_require_winning_an_auction() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(2), 0));
+
+		// Plain names are first come, first served and exclusive.
+		assert_ok!(KittiesModule::set_name(Origin::signed(1), 0, b"felix".to_vec()));
+		assert_noop!(
+			KittiesModule::set_name(Origin::signed(2), 1, b"felix".to_vec()),
+			Error::<Test>::NameAlreadyTaken
+		);
+
+		assert_ok!(KittiesModule::reserve_name(RawOrigin::Root.into(), b"nyan".to_vec()));
+		assert_noop!(
+			KittiesModule::set_name(Origin::signed(2), 1, b"nyan".to_vec()),
+			Error::<Test>::NameIsReserved
+		);
+
+		assert_ok!(KittiesModule::start_name_auction(
+			RawOrigin::Root.into(), b"nyan".to_vec(), 100, 5
+		));
+		assert_ok!(KittiesModule::bid_name(Origin::signed(2), b"nyan".to_vec(), 120));
+		run_to_block(6);
+		// The winning bid went to the fee beneficiary; the winner holds a
+		// claim they can redeem once.
+		assert_eq!(Balances::free_balance(999), 120);
+		assert_eq!(KittiesModule::name_claims(b"nyan".to_vec()), Some(2));
+		assert_ok!(KittiesModule::set_name(Origin::signed(2), 1, b"nyan".to_vec()));
+		assert_eq!(KittiesModule::names(1), Some(b"nyan".to_vec()));
+		assert_eq!(KittiesModule::name_claims(b"nyan".to_vec()), None);
+	});
+}
+
+#[test]
+fn dna_index_stays_unique() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		// Both creations land in the same block with the same seed, which
+		// would produce duplicate DNA without the uniqueness index.
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		let dna0 = KittiesModule::kitties(0).unwrap().0;
+		let dna1 = KittiesModule::kitties(1).unwrap().0;
+		assert_ne!(dna0, dna1);
+		assert_eq!(KittiesModule::dna_index(dna0), Some(0));
+		assert_eq!(KittiesModule::dna_index(dna1), Some(1));
+	});
+}
+
+#[test]
+fn reroll_replaces_dna_once_within_window() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		let old_dna = KittiesModule::kitties(0).unwrap().0;
+		let before = Balances::free_balance(1);
+
+		run_to_block(3);
+		assert_ok!(KittiesModule::reroll(Origin::signed(1), 0));
+		let new_dna = KittiesModule::kitties(0).unwrap().0;
+		assert_ne!(old_dna, new_dna);
+		// The re-roll fee is burned and the DNA index follows the new look.
+		assert_eq!(Balances::free_balance(1), before - 60);
+		assert_eq!(KittiesModule::dna_index(old_dna), None);
+		assert_eq!(KittiesModule::dna_index(new_dna), Some(0));
+
+		assert_noop!(
+			KittiesModule::reroll(Origin::signed(1), 0),
+			Error::<Test>::AlreadyRerolled
+		);
+	});
+}
+
+#[test]
+fn reroll_window_closes_after_birth() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		run_to_block(7);
+		assert_noop!(
+			KittiesModule::reroll(Origin::signed(1), 0),
+			Error::<Test>::RerollWindowClosed
+		);
+	});
+}
+
+#[test]
+fn force_set_dna_requires_admin_and_refreshes_index() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		let old_dna = KittiesModule::kitties(0).unwrap().0;
+		let new_dna = [7u8; 16];
+
+		assert_noop!(
+			KittiesModule::force_set_dna(Origin::signed(1), 0, new_dna),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_ok!(KittiesModule::force_set_dna(Origin::root(), 0, new_dna));
+		assert_eq!(KittiesModule::kitties(0).unwrap().0, new_dna);
+		assert_eq!(KittiesModule::dna_index(old_dna), None);
+		assert_eq!(KittiesModule::dna_index(new_dna), Some(0));
+	});
+}
+
+#[test]
+fn rarity_leaderboard_stays_sorted_and_bounded() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		for _ in 0..4 {
+			assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		}
+		let board = KittiesModule::rarity_leaderboard();
+		// Bounded at MaxLeaderboardSize and sorted by descending score.
+		assert_eq!(board.len(), 3);
+		assert!(board.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+		for (kitty_id, score) in board {
+			assert_eq!(KittiesModule::rarity_score(kitty_id), Some(score));
+		}
+
+		// Splicing in the rarest possible DNA puts the kitty on top.
+		assert_ok!(KittiesModule::force_set_dna(Origin::root(), 0, [0xff; 16]));
+		let board = KittiesModule::rarity_leaderboard();
+		assert_eq!(board[0].0, 0);
+		assert_eq!(board[0].1, KittiesModule::rarity_score(0).unwrap());
+	});
+}
+
+#[test]
+fn generation_index_tracks_mints_breeds_and_burials() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::breed(Origin::signed(1), 0, 1));
+		// Mints are filed under generation zero, kittens under their own.
+		assert_eq!(KittiesModule::generation_page(0, 0, 10), vec![0, 1]);
+		assert_eq!(KittiesModule::generation_page(1, 0, 10), vec![2]);
+		// Pagination honours the start id and the limit.
+		assert_eq!(KittiesModule::generation_page(0, 1, 10), vec![1]);
+		assert_eq!(KittiesModule::generation_page(0, 0, 1), vec![0]);
+	});
+}
+
+#[test]
+fn transfer_preferences_refuse_and_cap_incoming() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+
+		assert_ok!(KittiesModule::set_account_preferences(Origin::signed(2), false, None));
+		assert_noop!(
+			KittiesModule::transfer(Origin::signed(1), 2, 0),
+			Error::<Test>::RecipientRefusesTransfers
+		);
+
+		assert_ok!(KittiesModule::set_account_preferences(Origin::signed(2), true, Some(1)));
+		assert_ok!(KittiesModule::transfer(Origin::signed(1), 2, 0));
+		assert_noop!(
+			KittiesModule::transfer(Origin::signed(1), 2, 1),
+			Error::<Test>::RecipientAtCapacity
+		);
+
+		// A market purchase the recipient initiated is always accepted.
+		assert_ok!(KittiesModule::sell(Origin::signed(1), 1, 500, vec![], None, false, None));
+		assert_ok!(KittiesModule::buy(Origin::signed(2), 1));
+	});
+}
+
+#[test]
+fn two_phase_transfer_requires_a_claim() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::offer_transfer(Origin::signed(1), 0, 2, 10));
+		// Nothing moves until the claim: owner and deposit are unchanged.
+		assert_eq!(KittiesModule::kitty_owner(0), Some(1));
+		assert_eq!(Balances::reserved_balance(1), 100);
+
+		assert_noop!(
+			KittiesModule::claim_transfer(Origin::signed(3), 0),
+			Error::<Test>::NotTransferRecipient
+		);
+		assert_ok!(KittiesModule::claim_transfer(Origin::signed(2), 0));
+		assert_eq!(KittiesModule::kitty_owner(0), Some(2));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(2), 100);
+		assert_eq!(KittiesModule::pending_transfer(0), None);
+	});
+}
+
+#[test]
+fn expired_transfer_offers_cannot_be_claimed() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::offer_transfer(Origin::signed(1), 0, 2, 3));
+		run_to_block(4);
+		assert_noop!(
+			KittiesModule::claim_transfer(Origin::signed(2), 0),
+			Error::<Test>::TransferOfferExpired
+		);
+		// The kitty stayed with the sender throughout.
+		assert_eq!(KittiesModule::kitty_owner(0), Some(1));
+	});
+}
+
+#[test]
+fn blacklisted_accounts_are_locked_out() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(2), 0));
+		assert_ok!(KittiesModule::set_blacklist(Origin::root(), 2, true));
+
+		assert_noop!(
+			KittiesModule::create(Origin::signed(2), 0),
+			Error::<Test>::Blacklisted
+		);
+		assert_noop!(
+			KittiesModule::sell(Origin::signed(2), 1, 300, vec![], None, false, None),
+			Error::<Test>::Blacklisted
+		);
+		assert_noop!(
+			KittiesModule::transfer(Origin::signed(1), 2, 0),
+			Error::<Test>::Blacklisted
+		);
+
+		// Delisting restores full access.
+		assert_ok!(KittiesModule::set_blacklist(Origin::root(), 2, false));
+		assert_ok!(KittiesModule::transfer(Origin::signed(1), 2, 0));
+	});
+}
+
+#[test]
+fn transfer_all_migrates_holdings_and_deposits() {
+	new_test_ext().execute_with(|| {
+		for _ in 0..3 {
+			assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		}
+		assert_eq!(Balances::reserved_balance(1), 300);
+
+		assert_ok!(KittiesModule::transfer_all(Origin::signed(1), 2));
+		assert_eq!(KittiesModule::owned_kitties_count(1), 0);
+		assert_eq!(KittiesModule::owned_kitties_count(2), 3);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(2), 300);
+		for kitty_id in 0..3 {
+			assert_eq!(KittiesModule::kitty_owner(kitty_id), Some(2));
+		}
+	});
+}
+
+#[test]
+fn kitty_renders_are_deterministic_json() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		let attributes = KittiesModule::attributes(0).unwrap();
+		let render = KittiesModule::render_kitty(0).unwrap();
+		let expected_prefix = format!(
+			"{{\"fur\":{},\"eyes\":{},\"pattern\":{},",
+			attributes.fur, attributes.eyes, attributes.pattern,
+		);
+		assert!(render.starts_with(expected_prefix.as_bytes()));
+		// Same DNA, same bytes: the render is a pure function of state.
+		assert_eq!(KittiesModule::render_kitty(0).unwrap(), render);
+		assert_eq!(KittiesModule::render_kitty(1), None);
+	});
+}
+
+#[test]
+fn tournament_pays_the_pool_to_a_single_winner() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(2), 0));
+		let before_1 = Balances::free_balance(1);
+		let before_2 = Balances::free_balance(2);
+
+		assert_ok!(KittiesModule::create_tournament(Origin::signed(1), 10, 4, 3, 10));
+		assert_ok!(KittiesModule::enter(Origin::signed(1), 0, 0));
+		assert_ok!(KittiesModule::enter(Origin::signed(2), 0, 1));
+		assert_noop!(
+			KittiesModule::enter(Origin::signed(2), 0, 1),
+			Error::<Test>::AlreadyEntered
+		);
+		assert_eq!(KittiesModule::tournaments(0).unwrap().prize_pool, 20);
+		// Entering counts as a battle appearance.
+		assert_eq!(KittiesModule::counters(0).battles, 1);
+
+		run_to_block(5);
+		// The bracket resolved: the tournament is gone and the pool moved
+		// intact to exactly one of the two entrants.
+		assert_eq!(KittiesModule::tournaments(0), None);
+		assert!(KittiesModule::active_tournaments().is_empty());
+		let delta_1 = Balances::free_balance(1) as i64 - before_1 as i64;
+		let delta_2 = Balances::free_balance(2) as i64 - before_2 as i64;
+		assert_eq!(delta_1 + delta_2, 0);
+		assert!(delta_1 == 10 || delta_2 == 10);
+	});
+}
+
+#[test]
+fn tournament_entry_closes_at_start() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create_tournament(Origin::signed(1), 10, 4, 3, 10));
+		run_to_block(3);
+		assert_noop!(
+			KittiesModule::enter(Origin::signed(1), 0, 0),
+			Error::<Test>::TournamentClosed
+		);
+	});
+}
+
+#[test]
+fn race_pools_fees_and_pays_top_finishers() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(2), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(3), 0));
+		let before: Vec<u64> = (1..=3).map(Balances::free_balance).collect();
+
+		assert_ok!(KittiesModule::create_race(Origin::signed(1), 20, 8, 3));
+		assert_ok!(KittiesModule::enter_race(Origin::signed(1), 0, 0));
+		assert_ok!(KittiesModule::enter_race(Origin::signed(2), 0, 1));
+		assert_ok!(KittiesModule::enter_race(Origin::signed(3), 0, 2));
+		assert_eq!(KittiesModule::races(0).unwrap().prize_pool, 60);
+
+		run_to_block(3);
+		// The race ran: pool paid out 50/30/20 across the three runners.
+		assert_eq!(KittiesModule::races(0), None);
+		let mut deltas: Vec<i64> = (1..=3)
+			.map(|who| Balances::free_balance(who) as i64 - before[who as usize - 1] as i64)
+			.collect();
+		deltas.sort();
+		assert_eq!(deltas, vec![-8, -2, 10]);
+
+		// Records: everyone raced and made the podium, one kitty won.
+		let records: Vec<_> = (0..3).map(KittiesModule::race_record).collect();
+		assert!(records.iter().all(|record| record.races == 1 && record.podiums == 1));
+		assert_eq!(records.iter().map(|record| record.wins).sum::<u32>(), 1);
+	});
+}
+
+#[test]
+fn race_entry_closes_at_start() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create_race(Origin::signed(1), 20, 8, 3));
+		run_to_block(3);
+		assert_noop!(
+			KittiesModule::enter_race(Origin::signed(1), 0, 0),
+			Error::<Test>::RaceClosed
+		);
+	});
+}
+
+#[test]
+fn edition_mints_carry_the_marker_and_respect_the_cap() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::open_edition(
+			Origin::root(), b"winter".to_vec(), 2, 0xAB, 1, 10,
+		));
+		assert_ok!(KittiesModule::create_in_edition(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create_in_edition(Origin::signed(2), 0));
+		assert_eq!(KittiesModule::kitties(0).unwrap().0[0], 0xAB);
+		assert_eq!(KittiesModule::kitty_edition(0), Some(0));
+		assert_eq!(KittiesModule::kitty_edition(1), Some(0));
+
+		assert_noop!(
+			KittiesModule::create_in_edition(Origin::signed(1), 0),
+			Error::<Test>::EditionSoldOut
+		);
+
+		// Plain mints stay outside the edition.
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_eq!(KittiesModule::kitty_edition(2), None);
+	});
+}
+
+#[test]
+fn edition_minting_is_window_gated() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::open_edition(
+			Origin::root(), b"spring".to_vec(), 10, 0x01, 3, 5,
+		));
+		assert_noop!(
+			KittiesModule::create_in_edition(Origin::signed(1), 0),
+			Error::<Test>::EditionClosed
+		);
+		run_to_block(3);
+		assert_ok!(KittiesModule::create_in_edition(Origin::signed(1), 0));
+		run_to_block(5);
+		assert_noop!(
+			KittiesModule::create_in_edition(Origin::signed(1), 0),
+			Error::<Test>::EditionClosed
+		);
+	});
+}
+
+#[test]
+fn soulbound_kitties_can_never_change_hands() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::mint_soulbound(Origin::root(), 1));
+		assert!(KittiesModule::is_soulbound(0));
+		assert_eq!(KittiesModule::kitty_owner(0), Some(1));
+
+		assert_noop!(
+			KittiesModule::transfer(Origin::signed(1), 2, 0),
+			Error::<Test>::SoulboundKitty
+		);
+		assert_noop!(
+			KittiesModule::sell(Origin::signed(1), 0, 300, vec![], None, false, None),
+			Error::<Test>::SoulboundKitty
+		);
+		assert_noop!(
+			KittiesModule::start_auction(Origin::signed(1), 0, 100, 5, None),
+			Error::<Test>::SoulboundKitty
+		);
+		assert_noop!(
+			KittiesModule::offer_transfer(Origin::signed(1), 0, 2, 10),
+			Error::<Test>::SoulboundKitty
+		);
+		assert_noop!(
+			KittiesModule::make_offer(Origin::signed(2), 0, 100, None, None),
+			Error::<Test>::SoulboundKitty
+		);
+
+		// transfer_all leaves the soulbound kitty behind.
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::transfer_all(Origin::signed(1), 2));
+		assert_eq!(KittiesModule::kitty_owner(0), Some(1));
+		assert_eq!(KittiesModule::kitty_owner(1), Some(2));
+	});
+}
+
+#[test]
+fn fusion_burns_parents_and_forges_an_upgraded_kitty() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		let dna_1 = KittiesModule::kitties(0).unwrap().0;
+		let dna_2 = KittiesModule::kitties(1).unwrap().0;
+		let before = Balances::free_balance(1);
+
+		assert_ok!(KittiesModule::fuse(Origin::signed(1), 0, 1));
+		// Both parents are gone; the forged kitty takes the best byte of
+		// each pair and sits one tier up.
+		assert_eq!(KittiesModule::kitties(0), None);
+		assert_eq!(KittiesModule::kitties(1), None);
+		let forged = KittiesModule::kitties(2).unwrap().0;
+		for i in 0..16 {
+			assert_eq!(forged[i], dna_1[i].max(dna_2[i]));
+		}
+		assert_eq!(KittiesModule::tier(2), 1);
+		assert_eq!(KittiesModule::owned_kitties_count(1), 1);
+		// One deposit came back net, the fusion fee was burned.
+		assert_eq!(Balances::reserved_balance(1), 100);
+		assert_eq!(Balances::free_balance(1), before + 100 - 80);
+
+		assert_noop!(
+			KittiesModule::fuse(Origin::signed(1), 2, 2),
+			Error::<Test>::RequireDifferentParent
+		);
+	});
+}
+
+#[test]
+fn crossbreeding_records_the_foreign_parent() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(2), 0));
+
+		// Creature 7 lives in the stub registry and belongs to account 2.
+		assert_noop!(
+			KittiesModule::crossbreed(Origin::signed(2), 0, 8),
+			Error::<Test>::ForeignCreatureNotFound
+		);
+		assert_ok!(KittiesModule::crossbreed(Origin::signed(2), 0, 7));
+
+		assert_eq!(KittiesModule::kitty_owner(1), Some(2));
+		assert_eq!(KittiesModule::generation(1), 1);
+		assert_eq!(
+			KittiesModule::pedigree(1),
+			vec![
+				crate::ParentRef::Kitty(0),
+				crate::ParentRef::Foreign(*b"pupy", 7),
+			]
+		);
+	});
+}
+
+#[test]
+fn crossbreeding_requires_owning_both_parents() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		// Account 1 owns the kitty but not creature 7.
+		assert_noop!(
+			KittiesModule::crossbreed(Origin::signed(1), 0, 7),
+			Error::<Test>::NotForeignCreatureOwner
+		);
+	});
+}
+
+#[test]
+fn difficulty_table_scales_the_breeding_fee_with_supply() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::set_difficulty_table(
+			Origin::root(),
+			vec![(3, 200, 300), (5, 400, 600)],
+		));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+
+		// Below the first threshold the base fee of 50 applies.
+		assert_eq!(KittiesModule::current_breed_fee(), 50);
+		let before = Balances::free_balance(1);
+		assert_ok!(KittiesModule::breed(Origin::signed(1), 0, 1));
+		assert_eq!(Balances::free_balance(1), before - 100 - 50);
+
+		// Three kitties exist now, so the 200% row kicks in lazily.
+		assert_eq!(KittiesModule::current_breed_fee(), 100);
+		let before = Balances::free_balance(1);
+		assert_ok!(KittiesModule::breed(Origin::signed(1), 0, 1));
+		assert_eq!(Balances::free_balance(1), before - 100 - 100);
+
+		// At five kitties the steepest row applies.
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_eq!(KittiesModule::current_breed_fee(), 200);
+	});
+}
+
+#[test]
+fn difficulty_table_must_have_ascending_thresholds() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			KittiesModule::set_difficulty_table(Origin::root(), vec![(5, 200, 200), (5, 400, 400)]),
+			Error::<Test>::InvalidDifficultyTable
+		);
+		assert_noop!(
+			KittiesModule::set_difficulty_table(Origin::signed(1), vec![(5, 200, 200)]),
+			sp_runtime::DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn birth_records_let_anyone_audit_minted_dna() {
+	new_test_ext().execute_with(|| {
+		run_to_block(3);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		let record = KittiesModule::birth_record(0).unwrap();
+		assert_eq!(record.block, 3);
+		assert_eq!(record.minter, 1);
+		// The recorded inputs reproduce the stored DNA.
+		assert_eq!(KittiesModule::verify_dna(0), Some(true));
+
+		// Bred kitties inherit genes instead of drawing them, so they
+		// carry no record and cannot be audited this way.
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::breed(Origin::signed(1), 0, 1));
+		assert_eq!(KittiesModule::birth_record(2), None);
+		assert_eq!(KittiesModule::verify_dna(2), None);
+
+		// Rewriting the DNA breaks the derivation, and the audit says so.
+		assert_ok!(KittiesModule::force_set_dna(Origin::root(), 0, [0x42; 16]));
+		assert_eq!(KittiesModule::verify_dna(0), Some(false));
+	});
+}
+
+/// The commitment hash for a sealed bid, as `reveal_bid` checks it.
+fn sealed_commitment(bidder: u64, kitty_id: u32, amount: u64, salt: [u8; 32]) -> [u8; 32] {
+	use codec::Encode;
+	sp_io::hashing::blake2_256(&(bidder, kitty_id, amount, salt).encode())
+}
+
+#[test]
+fn vickrey_winner_pays_the_second_price() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		// Commit phase runs to block 3, reveal phase to block 5.
+		assert_ok!(KittiesModule::start_sealed_auction(Origin::signed(1), 0, 100, 10, 2, 2));
+		assert_ok!(KittiesModule::commit_bid(
+			Origin::signed(2), 0, sealed_commitment(2, 0, 500, [1; 32]),
+		));
+		assert_ok!(KittiesModule::commit_bid(
+			Origin::signed(3), 0, sealed_commitment(3, 0, 300, [2; 32]),
+		));
+
+		run_to_block(3);
+		assert_ok!(KittiesModule::reveal_bid(Origin::signed(2), 0, 500, [1; 32]));
+		assert_ok!(KittiesModule::reveal_bid(Origin::signed(3), 0, 300, [2; 32]));
+
+		let seller_free = Balances::free_balance(1);
+		let winner_free = Balances::free_balance(2);
+		let loser_free = Balances::free_balance(3);
+		run_to_block(5);
+
+		// The highest bidder wins but pays the runner-up's 300, less the
+		// 10% market fee; the deposit travels with the kitty.
+		assert_eq!(KittiesModule::kitty_owner(0), Some(2));
+		assert_eq!(Balances::free_balance(1), seller_free + 270 + 100);
+		assert_eq!(Balances::free_balance(2), winner_free + 500 - 300 - 100);
+		assert_eq!(Balances::free_balance(3), loser_free + 300);
+		assert_eq!(KittiesModule::sealed_auctions(0), None);
+		assert_eq!(KittiesModule::sealed_bids(0, 2), None);
+	});
+}
+
+#[test]
+fn unrevealed_sealed_bids_forfeit_their_deposit() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::start_sealed_auction(Origin::signed(1), 0, 100, 10, 2, 2));
+		let before = Balances::free_balance(2);
+		assert_ok!(KittiesModule::commit_bid(
+			Origin::signed(2), 0, sealed_commitment(2, 0, 500, [1; 32]),
+		));
+
+		run_to_block(5);
+		// Nothing was revealed: the kitty stays put and the silent
+		// bidder's deposit went to the fee beneficiary.
+		assert_eq!(KittiesModule::kitty_owner(0), Some(1));
+		assert_eq!(Balances::free_balance(2), before - 10);
+		assert_eq!(Balances::free_balance(999), 10);
+	});
+}
+
+#[test]
+fn sealed_bids_are_phase_gated_and_must_match() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::start_sealed_auction(Origin::signed(1), 0, 100, 2, 2, 2));
+		assert_ok!(KittiesModule::commit_bid(
+			Origin::signed(2), 0, sealed_commitment(2, 0, 500, [1; 32]),
+		));
+		// Revealing during the commit phase leaks information; refused.
+		assert_noop!(
+			KittiesModule::reveal_bid(Origin::signed(2), 0, 500, [1; 32]),
+			Error::<Test>::NotInRevealPhase
+		);
+
+		run_to_block(3);
+		assert_noop!(
+			KittiesModule::commit_bid(Origin::signed(3), 0, sealed_commitment(3, 0, 300, [2; 32])),
+			Error::<Test>::CommitPhaseClosed
+		);
+		// A reveal that does not open the commitment is rejected.
+		assert_noop!(
+			KittiesModule::reveal_bid(Origin::signed(2), 0, 400, [1; 32]),
+			Error::<Test>::BadBidReveal
+		);
+		assert_ok!(KittiesModule::reveal_bid(Origin::signed(2), 0, 500, [1; 32]));
+		assert_noop!(
+			KittiesModule::reveal_bid(Origin::signed(2), 0, 500, [1; 32]),
+			Error::<Test>::BidAlreadyRevealed
+		);
+	});
+}
+
+#[test]
+fn sweep_releases_stranded_offers_and_expired_state() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		let before = Balances::free_balance(2);
+		assert_ok!(KittiesModule::make_offer(Origin::signed(2), 0, 200, None, None));
+		assert_ok!(KittiesModule::delegate_breeding(Origin::signed(1), 2, 2, 5, 2, Percent::zero()));
+
+		// Fusing burns both parents but leaves the offer's reserve behind.
+		assert_ok!(KittiesModule::fuse(Origin::signed(1), 0, 1));
+		assert_eq!(Balances::free_balance(2), before - 200);
+
+		run_to_block(4);
+		// The sweep found the burned kitty and handed the reserve back;
+		// the lapsed delegation is gone too, along with its trade lock.
+		assert_eq!(KittiesModule::offers(0, 2), None);
+		assert_eq!(Balances::free_balance(2), before);
+		assert_eq!(KittiesModule::breeding_delegations(2, 2), None);
+		assert_eq!(KittiesModule::kitty_lock(2), None);
+	});
+}
+
+#[test]
+fn sweep_advances_its_cursor_under_the_budget() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		for _ in 0..6 {
+			assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		}
+		// The budget is 4 ids per block; the cursor resumes across blocks
+		// and wraps at the end of the id space.
+		run_to_block(2);
+		assert_eq!(KittiesModule::cleanup_cursor(), 4);
+		run_to_block(3);
+		assert_eq!(KittiesModule::cleanup_cursor(), 2);
+	});
+}
+
+#[test]
+fn reaping_a_rent_lapsed_kitty_pays_a_finders_fee() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		// The mint covers the first period: blocks 1 through 11.
+		assert_eq!(KittiesModule::rent_paid_until(0), 11);
+		let dna = KittiesModule::kitties(0).unwrap().0;
+
+		run_to_block(16);
+		assert_noop!(
+			KittiesModule::reap_kitty(Origin::signed(2), 0),
+			Error::<Test>::RentNotLapsed
+		);
+
+		run_to_block(17);
+		assert_ok!(KittiesModule::reap_kitty(Origin::signed(2), 0));
+		assert_eq!(KittiesModule::kitties(0), None);
+		assert_eq!(KittiesModule::archived_kitty(0), Some((dna, 1)));
+		// The finder's fee comes out of the owner's deposit.
+		assert_eq!(Balances::free_balance(2), 10_000 + 20);
+		assert_eq!(Balances::free_balance(1), 10_000 - 20);
+		assert_eq!(Balances::reserved_balance(1), 0);
+	});
+}
+
+#[test]
+fn paying_rent_defers_the_reaper() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		let before = Balances::free_balance(1);
+		assert_ok!(KittiesModule::pay_rent(Origin::signed(1), 0, 2));
+		assert_eq!(KittiesModule::rent_paid_until(0), 31);
+		assert_eq!(Balances::free_balance(1), before - 10);
+
+		run_to_block(17);
+		assert_noop!(
+			KittiesModule::reap_kitty(Origin::signed(2), 0),
+			Error::<Test>::RentNotLapsed
+		);
+		assert_noop!(
+			KittiesModule::pay_rent(Origin::signed(1), 0, 0),
+			Error::<Test>::ZeroRentPeriods
+		);
+	});
+}
+
+#[test]
+fn queue_like_storage_vectors_are_bounded() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		for _ in 0..5 {
+			assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		}
+		// At most four auctions may end at the same block.
+		for kitty_id in 0..4 {
+			assert_ok!(KittiesModule::start_auction(Origin::signed(1), kitty_id, 100, 10, None));
+		}
+		assert_noop!(
+			KittiesModule::start_auction(Origin::signed(1), 4, 100, 10, None),
+			Error::<Test>::TooManyEndingAtBlock
+		);
+
+		// Contest entrant caps cannot exceed the global limit.
+		assert_noop!(
+			KittiesModule::create_tournament(Origin::signed(1), 10, 17, 5, 9),
+			Error::<Test>::ContestTooLarge
+		);
+		assert_noop!(
+			KittiesModule::create_race(Origin::signed(1), 10, 17, 5),
+			Error::<Test>::ContestTooLarge
+		);
+
+		// The difficulty table is bounded too.
+		assert_noop!(
+			KittiesModule::set_difficulty_table(
+				Origin::root(),
+				vec![(1, 100, 100), (2, 100, 100), (3, 100, 100), (4, 100, 100), (5, 100, 100)],
+			),
+			Error::<Test>::InvalidDifficultyTable
+		);
+	});
+}
+
+fn abi_address(who: u64) -> [u8; 32] {
+	let mut word = [0u8; 32];
+	word[24..].copy_from_slice(&who.to_be_bytes());
+	word
+}
+
+fn abi_token(kitty_id: u32) -> [u8; 32] {
+	let mut word = [0u8; 32];
+	word[28..].copy_from_slice(&kitty_id.to_be_bytes());
+	word
+}
+
+fn erc721(caller: u64, selector: [u8; 4], words: &[[u8; 32]]) -> Result<Vec<u8>, crate::erc721::Erc721Error> {
+	let mut input = selector.to_vec();
+	for word in words {
+		input.extend(word);
+	}
+	let mut address = [0u8; 20];
+	address[12..].copy_from_slice(&caller.to_be_bytes());
+	crate::erc721::Erc721::<Test, TestAddresses>::call(address, &input)
+}
+
+#[test]
+fn erc721_views_read_the_registry() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+
+		let owner = erc721(2, crate::erc721::SELECTOR_OWNER_OF, &[abi_token(0)]).unwrap();
+		assert_eq!(owner, abi_address(1).to_vec());
+		let balance = erc721(2, crate::erc721::SELECTOR_BALANCE_OF, &[abi_address(1)]).unwrap();
+		assert_eq!(balance, abi_token(1).to_vec());
+
+		let uri = erc721(2, crate::erc721::SELECTOR_TOKEN_URI, &[abi_token(0)]).unwrap();
+		// Offset word, length word, then the padded string itself.
+		assert_eq!(&uri[31..32], &[32]);
+		assert_eq!(&uri[63..64], &[11]);
+		assert_eq!(&uri[64..75], b"kitties://0");
+
+		assert_eq!(
+			erc721(2, crate::erc721::SELECTOR_OWNER_OF, &[abi_token(9)]),
+			Err(crate::erc721::Erc721Error::TokenNotFound)
+		);
+	});
+}
+
+#[test]
+fn erc721_transfer_from_honors_approvals() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+
+		// A stranger cannot move the token, and the owner must approve first.
+		assert_eq!(
+			erc721(2, crate::erc721::SELECTOR_TRANSFER_FROM, &[abi_address(1), abi_address(2), abi_token(0)]),
+			Err(crate::erc721::Erc721Error::NotAuthorized)
+		);
+		assert_eq!(
+			erc721(2, crate::erc721::SELECTOR_APPROVE, &[abi_address(2), abi_token(0)]),
+			Err(crate::erc721::Erc721Error::NotAuthorized)
+		);
+		assert_ok!(erc721(1, crate::erc721::SELECTOR_APPROVE, &[abi_address(2), abi_token(0)]));
+		assert_eq!(KittiesModule::erc721_approval(0), Some(2));
+
+		// The approved spender moves it; the deposit follows the kitty and
+		// the approval is spent.
+		assert_ok!(erc721(2, crate::erc721::SELECTOR_TRANSFER_FROM, &[abi_address(1), abi_address(3), abi_token(0)]));
+		assert_eq!(KittiesModule::kitty_owner(0), Some(3));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(3), 100);
+		assert_eq!(KittiesModule::erc721_approval(0), None);
+	});
+}
+
+#[test]
+fn chain_extension_exposes_the_kitty_primitives() {
+	use crate::chain_ext::{self, KittiesExt};
+	use codec::Encode;
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+
+		let owner = KittiesExt::<Test>::call(chain_ext::FUNC_OWNER_OF, 2, &0u32.encode());
+		assert_eq!(owner, Ok(1u64.encode()));
+		let attributes =
+			KittiesExt::<Test>::call(chain_ext::FUNC_ATTRIBUTES, 2, &0u32.encode());
+		assert_eq!(attributes, Ok(KittiesModule::attributes(0).unwrap().encode()));
+
+		// Only the contract that owns a kitty may move or breed it.
+		assert_eq!(
+			KittiesExt::<Test>::call(chain_ext::FUNC_TRANSFER, 2, &(3u64, 0u32).encode()),
+			Err(Error::<Test>::NotKittyOwner.into())
+		);
+		assert_ok!(KittiesExt::<Test>::call(
+			chain_ext::FUNC_TRANSFER,
+			1,
+			&(3u64, 0u32).encode()
+		));
+		assert_eq!(KittiesModule::kitty_owner(0), Some(3));
+
+		assert_eq!(
+			KittiesExt::<Test>::call(chain_ext::FUNC_BREED, 2, &(0u32, 1u32).encode()),
+			Err(Error::<Test>::NoBreedingRights.into())
+		);
+
+		// Every function carries a price and unknown ids are rejected.
+		assert!(KittiesExt::<Test>::weight_of(chain_ext::FUNC_BREED) > 0);
+		assert!(KittiesExt::<Test>::call(0xdead, 1, &[]).is_err());
+	});
+}
+
+#[test]
+fn fractionalizing_locks_the_kitty_until_all_shares_return() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_noop!(
+			KittiesModule::fractionalize(Origin::signed(1), 0, 1),
+			Error::<Test>::TooFewShares
+		);
+		assert_ok!(KittiesModule::fractionalize(Origin::signed(1), 0, 100));
+		let (asset, total) = KittiesModule::fraction_shares(0).unwrap();
+		assert_eq!(total, 100);
+		assert_eq!(
+			KittiesModule::kitty_lock(0),
+			Some(KittiesModule::fraction_lock_account())
+		);
+
+		// The kitty itself is frozen while shares circulate.
+		assert_noop!(
+			KittiesModule::transfer(Origin::signed(1), 2, 0),
+			Error::<Test>::KittyFractionalized
+		);
+		assert_noop!(
+			KittiesModule::sell(Origin::signed(1), 0, 500, vec![], None, false, None),
+			Error::<Test>::KittyFractionalized
+		);
+		assert_noop!(
+			KittiesModule::fractionalize(Origin::signed(1), 0, 100),
+			Error::<Test>::KittyFractionalized
+		);
+
+		// A partial holding cannot redeem; the full supply can, and takes
+		// custody of kitty and deposit.
+		move_shares(asset, 1, 2, 40);
+		assert_noop!(
+			KittiesModule::redeem(Origin::signed(2), 0),
+			Error::<Test>::NotAllShares
+		);
+		move_shares(asset, 1, 2, 60);
+		assert_ok!(KittiesModule::redeem(Origin::signed(2), 0));
+		assert_eq!(KittiesModule::kitty_owner(0), Some(2));
+		assert_eq!(KittiesModule::fraction_shares(0), None);
+		assert_eq!(KittiesModule::kitty_lock(0), None);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(2), 100);
+		assert_ok!(KittiesModule::transfer(Origin::signed(2), 3, 0));
+	});
+}
+
+#[test]
+fn bridged_out_kitties_are_inert_until_relayers_release_them() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::add_relayer(Origin::root(), 8));
+		assert_ok!(KittiesModule::add_relayer(Origin::root(), 9));
+
+		assert_ok!(KittiesModule::bridge_out(Origin::signed(1), 0, [0xab; 20]));
+		assert_eq!(KittiesModule::bridged_out(0), Some((1, [0xab; 20])));
+		assert_eq!(
+			KittiesModule::kitty_lock(0),
+			Some(KittiesModule::bridge_lock_account())
+		);
+		assert_noop!(
+			KittiesModule::transfer(Origin::signed(1), 2, 0),
+			Error::<Test>::KittyBridgedOut
+		);
+		assert_noop!(
+			KittiesModule::sell(Origin::signed(1), 0, 500, vec![], None, false, None),
+			Error::<Test>::KittyBridgedOut
+		);
+		assert_noop!(
+			KittiesModule::breed(Origin::signed(1), 0, 1),
+			Error::<Test>::KittyBridgedOut
+		);
+		assert_noop!(
+			KittiesModule::bridge_out(Origin::signed(1), 0, [0xab; 20]),
+			Error::<Test>::KittyBridgedOut
+		);
+
+		// Only relayers vote, one vote each, and the threshold releases.
+		assert_noop!(
+			KittiesModule::approve_unlock(Origin::signed(2), 0),
+			Error::<Test>::NotRelayer
+		);
+		assert_ok!(KittiesModule::approve_unlock(Origin::signed(8), 0));
+		assert_noop!(
+			KittiesModule::approve_unlock(Origin::signed(8), 0),
+			Error::<Test>::AlreadyApproved
+		);
+		assert_eq!(KittiesModule::bridged_out(0).is_some(), true);
+		assert_ok!(KittiesModule::approve_unlock(Origin::signed(9), 0));
+		assert_eq!(KittiesModule::bridged_out(0), None);
+		assert_eq!(KittiesModule::kitty_lock(0), None);
+		assert_eq!(KittiesModule::unlock_votes(0), Vec::<u64>::new());
+		assert_ok!(KittiesModule::transfer(Origin::signed(1), 2, 0));
+	});
+}
+
+#[test]
+fn ownership_queries_round_trip_in_both_directions() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+
+		// Answering a sibling's query: the verdict tracks live custody.
+		assert!(KittiesModule::attest_ownership(0, &1));
+		assert!(!KittiesModule::attest_ownership(0, &2));
+		assert_ok!(KittiesModule::answer_ownership_query(Origin::root(), 7, 0, 1));
+		assert_noop!(
+			KittiesModule::answer_ownership_query(Origin::signed(1), 7, 0, 1),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_ok!(KittiesModule::bridge_out(Origin::signed(1), 0, [0xab; 20]));
+		assert!(!KittiesModule::attest_ownership(0, &1));
+
+		// Issuing our own: ids are sequential and responses retire them.
+		assert_ok!(KittiesModule::open_ownership_query(Origin::signed(2), 2000, 0, 1));
+		assert_eq!(KittiesModule::ownership_query(0), Some((2000, 0, 1)));
+		assert_eq!(KittiesModule::next_query_id(), 1);
+		assert_ok!(KittiesModule::resolve_ownership_query(Origin::root(), 0, true));
+		assert_eq!(KittiesModule::ownership_query(0), None);
+		assert_noop!(
+			KittiesModule::resolve_ownership_query(Origin::root(), 0, true),
+			Error::<Test>::QueryNotFound
+		);
+	});
+}
+
+#[test]
+fn kitty_holders_earn_priority_and_fee_discounts() {
+	use sp_runtime::traits::SignedExtension;
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		let extension = crate::CheckKittyHolder::<Test>::new();
+		let info = Default::default();
+
+		// Below the holding threshold there is no perk.
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert!(!KittiesModule::qualifies_for_holder_perks(&1));
+		assert_eq!(extension.validate(&1, &(), &info, 0).unwrap().priority, 0);
+		assert_eq!(KittiesModule::discounted_fee(&1, 100), 100);
+
+		// Three kitties qualify for the bump and the discount.
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert!(KittiesModule::qualifies_for_holder_perks(&1));
+		assert_eq!(extension.validate(&1, &(), &info, 0).unwrap().priority, 50);
+		assert_eq!(KittiesModule::discounted_fee(&1, 100), 80);
+	});
+}
+
+#[test]
+fn unsigned_ocw_submissions_are_verified_by_recomputation() {
+	use frame_support::unsigned::ValidateUnsigned;
+	use sp_io::hashing::blake2_256;
+	use sp_runtime::transaction_validity::{InvalidTransaction, TransactionSource};
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		let hash = blake2_256(&KittiesModule::render_kitty(0).unwrap());
+		let price = KittiesModule::suggest_price(0).unwrap();
+
+		// A correct hash validates and lands; a wrong one never enters the pool.
+		let good = crate::Call::<Test>::submit_phenotype_hash(0, hash);
+		assert_ok!(KittiesModule::validate_unsigned(TransactionSource::Local, &good));
+		assert_eq!(
+			KittiesModule::validate_unsigned(
+				TransactionSource::Local,
+				&crate::Call::<Test>::submit_phenotype_hash(0, [0; 32]),
+			),
+			Err(InvalidTransaction::Stale.into())
+		);
+		assert_ok!(KittiesModule::submit_phenotype_hash(RawOrigin::None.into(), 0, hash));
+		assert_eq!(KittiesModule::phenotype_hash(0), Some(hash));
+
+		// Recording is once-only: replays are invalid at the pool and fail in a block.
+		assert_eq!(
+			KittiesModule::validate_unsigned(TransactionSource::Local, &good),
+			Err(InvalidTransaction::Stale.into())
+		);
+		assert_noop!(
+			KittiesModule::submit_phenotype_hash(RawOrigin::None.into(), 0, hash),
+			Error::<Test>::HashAlreadyRecorded
+		);
+
+		// Price suggestions must match the canonical derivation, and only
+		// a changed output may be resubmitted.
+		assert_noop!(
+			KittiesModule::submit_price_suggestion(RawOrigin::None.into(), 0, price + 1),
+			Error::<Test>::BadPriceSuggestion
+		);
+		assert_ok!(KittiesModule::submit_price_suggestion(RawOrigin::None.into(), 0, price));
+		assert_eq!(KittiesModule::suggested_price(0), Some((price, 1)));
+		assert_noop!(
+			KittiesModule::submit_price_suggestion(RawOrigin::None.into(), 0, price),
+			Error::<Test>::StaleSuggestion
+		);
+
+		// Signed submissions are rejected outright.
+		assert_noop!(
+			KittiesModule::submit_phenotype_hash(Origin::signed(1), 1, [0; 32]),
+			sp_runtime::DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn scheduled_drops_mint_into_the_pool_and_are_claimable() {
+	use frame_support::inherent::ProvideInherent;
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_noop!(
+			KittiesModule::schedule_gen_zero_drop(Origin::root(), 5, 0),
+			Error::<Test>::BadDropCount
+		);
+		assert_noop!(
+			KittiesModule::schedule_gen_zero_drop(Origin::signed(1), 5, 3),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_ok!(KittiesModule::schedule_gen_zero_drop(Origin::root(), 5, 3));
+
+		// Before the block arrives the inherent proposes nothing.
+		assert_eq!(KittiesModule::create_inherent(&Default::default()), None);
+		run_to_block(5);
+		assert_eq!(
+			KittiesModule::create_inherent(&Default::default()),
+			Some(crate::Call::apply_gen_zero_drop(3))
+		);
+		assert_ok!(KittiesModule::apply_gen_zero_drop(RawOrigin::None.into(), 3));
+		assert_eq!(KittiesModule::distribution_pool().len(), 3);
+		assert_eq!(KittiesModule::owned_kitties_count(KittiesModule::pool_account()), 3);
+		// The schedule entry is consumed with the drop.
+		assert_noop!(
+			KittiesModule::apply_gen_zero_drop(RawOrigin::None.into(), 3),
+			Error::<Test>::NoScheduledDrop
+		);
+
+		// Claims are first come, first served and post the deposit.
+		let first = KittiesModule::distribution_pool()[0];
+		assert_ok!(KittiesModule::claim_drop(Origin::signed(2)));
+		assert_eq!(KittiesModule::kitty_owner(first), Some(2));
+		assert_eq!(Balances::reserved_balance(2), 100);
+		assert_eq!(KittiesModule::distribution_pool().len(), 2);
+	});
+}
+
+#[test]
+fn gen_zero_mints_are_priced_by_the_bonding_curve() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		// A free curve is the status quo: only the deposit is taken.
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_eq!(Balances::free_balance(1), 9_900);
+
+		assert_ok!(KittiesModule::set_bonding_curve(Origin::root(), 10, 5));
+		assert_eq!(KittiesModule::gen_zero_supply(), 1);
+		assert_eq!(KittiesModule::mint_price(), 15);
+
+		// Each mint pays the quoted price to the treasury and moves the curve.
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_eq!(Balances::free_balance(1), 9_785);
+		assert_eq!(Balances::free_balance(999), 15);
+		assert_eq!(KittiesModule::mint_price(), 20);
+
+		// Breeding is not a gen-0 mint: the supply and price are unmoved.
+		assert_ok!(KittiesModule::breed(Origin::signed(1), 0, 1));
+		assert_eq!(KittiesModule::gen_zero_supply(), 2);
+		assert_eq!(KittiesModule::mint_price(), 20);
+
+		// Only the admin origin may retune the curve.
+		assert_noop!(
+			KittiesModule::set_bonding_curve(Origin::signed(1), 0, 0),
+			sp_runtime::DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn gen_zero_allowance_is_released_in_tranches_up_to_the_cap() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		set_gen_zero_tranche(2);
+
+		// The first tranche is available from genesis.
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_noop!(
+			KittiesModule::create(Origin::signed(2), 0),
+			Error::<Test>::GenZeroAllowanceExhausted
+		);
+
+		// Breeding is not gen-0 minting and ignores the schedule.
+		assert_ok!(KittiesModule::breed(Origin::signed(1), 0, 1));
+
+		// The next tranche unlocks a period later.
+		run_to_block(10);
+		assert_ok!(KittiesModule::create(Origin::signed(2), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(2), 0));
+		assert_noop!(
+			KittiesModule::create(Origin::signed(2), 0),
+			Error::<Test>::GenZeroAllowanceExhausted
+		);
+		assert_eq!(KittiesModule::gen_zero_minted(), 4);
+	});
+}
+
+#[test]
+fn stat_sheets_are_cached_and_refreshed_with_their_inputs() {
+	new_test_ext().execute_with(|| {
+		use crate::KittyStats;
+
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		// The sheet is written at mint and matches the gene math.
+		assert_eq!(KittiesModule::stat_sheet(0), KittiesModule::base_stats(0));
+
+		let bonus = KittyStats { strength: 5, agility: 2, stamina: 3, charm: 1 };
+		assert_ok!(KittiesModule::register_item(RawOrigin::Root.into(), 7, bonus));
+		assert_ok!(KittiesModule::equip(Origin::signed(1), 0, 7));
+		let buffed = KittiesModule::stat_sheet(0).unwrap();
+		assert_eq!(buffed.charm, KittiesModule::base_stats(0).unwrap().charm + 1);
+		assert_eq!(KittiesModule::effective_stats(0), Some(buffed));
+
+		assert_ok!(KittiesModule::unequip(Origin::signed(1), 0, 7));
+		assert_eq!(KittiesModule::stat_sheet(0), KittiesModule::base_stats(0));
+
+		// A reroll replaces the DNA and the cached sheet with it.
+		assert_ok!(KittiesModule::reroll(Origin::signed(1), 0));
+		assert_eq!(KittiesModule::stat_sheet(0), KittiesModule::base_stats(0));
+	});
+}
+
+#[test]
+fn breed_fee_is_split_with_the_sire_owner() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(2), 0));
+		assert_ok!(KittiesModule::delegate_breeding(
+			Origin::signed(2), 1, 1, 1, 100, Percent::from_percent(40),
+		));
+
+		let payer_before = Balances::free_balance(1);
+		let sire_before = Balances::free_balance(2);
+		assert_ok!(KittiesModule::breed(Origin::signed(1), 0, 1));
+
+		// The breeder still covers the full fee and the child's deposit;
+		// the sire's owner receives their agreed cut and the remainder is
+		// burned as before.
+		assert_eq!(Balances::free_balance(1), payer_before - 100 - 50);
+		assert_eq!(Balances::free_balance(2), sire_before + 20);
+		assert_eq!(KittiesModule::kitty_owner(2), Some(1));
+	});
+}
+
+#[test]
+fn breeding_passes_prepay_fees_at_a_discount() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_noop!(
+			KittiesModule::buy_breeding_pass(Origin::signed(1), 0),
+			Error::<Test>::ZeroPassCount
+		);
+
+		// Two passes at 20% off the 50-unit fee cost 80 up front.
+		let before = Balances::free_balance(1);
+		assert_ok!(KittiesModule::buy_breeding_pass(Origin::signed(1), 2));
+		assert_eq!(Balances::free_balance(1), before - 80);
+		assert_eq!(KittiesModule::breeding_passes(1), 2);
+
+		// A pass covers the whole fee: only the child's deposit moves.
+		let before = Balances::free_balance(1);
+		assert_ok!(KittiesModule::breed(Origin::signed(1), 0, 1));
+		assert_eq!(Balances::free_balance(1), before - 100);
+		assert_eq!(KittiesModule::breeding_passes(1), 1);
+
+		assert_ok!(KittiesModule::breed(Origin::signed(1), 0, 1));
+		assert_eq!(KittiesModule::breeding_passes(1), 0);
+
+		// With the passes spent the per-breed fee applies again.
+		let before = Balances::free_balance(1);
+		assert_ok!(KittiesModule::breed(Origin::signed(1), 0, 1));
+		assert_eq!(Balances::free_balance(1), before - 100 - 50);
+	});
+}
+
+#[test]
+fn loans_lock_collateral_and_accrue_interest() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+
+		// Principal is capped by the loan-to-value fraction of the floor.
+		assert_noop!(
+			KittiesModule::request_loan(Origin::signed(1), 0, 5_000, 1, 10),
+			Error::<Test>::LoanExceedsValuation
+		);
+		assert_ok!(KittiesModule::request_loan(Origin::signed(1), 0, 50, 1, 10));
+		assert_noop!(
+			KittiesModule::transfer(Origin::signed(1), 2, 0),
+			Error::<Test>::KittyLocked
+		);
+		assert_noop!(
+			KittiesModule::fund_loan(Origin::signed(1), 0),
+			Error::<Test>::CannotFundOwnLoan
+		);
+
+		assert_ok!(KittiesModule::fund_loan(Origin::signed(2), 0));
+		assert_eq!(Balances::free_balance(2), 9_950);
+
+		// Five blocks of interest at 1 per block on a 50 principal.
+		run_to_block(6);
+		assert_ok!(KittiesModule::repay_loan(Origin::signed(1), 0));
+		assert_eq!(Balances::free_balance(2), 10_005);
+		assert_eq!(KittiesModule::loans(0), None);
+		assert_ok!(KittiesModule::transfer(Origin::signed(1), 2, 0));
+	});
+}
+
+#[test]
+fn overdue_loans_liquidate_through_the_auction_subsystem() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::request_loan(Origin::signed(1), 0, 50, 1, 5));
+		assert_ok!(KittiesModule::fund_loan(Origin::signed(2), 0));
+
+		assert_noop!(
+			KittiesModule::liquidate_loan(Origin::signed(3), 0),
+			Error::<Test>::LoanNotDue
+		);
+		run_to_block(8);
+		assert_ok!(KittiesModule::liquidate_loan(Origin::signed(3), 0));
+		// The collateral sits with the loan pool while it is auctioned.
+		assert_eq!(KittiesModule::kitty_owner(0), Some(KittiesModule::loan_account()));
+		assert_eq!(Balances::free_balance(1), 10_050);
+
+		assert_ok!(KittiesModule::bid(Origin::signed(3), 0, 60));
+		run_to_block(13);
+
+		// The winner took delivery; the lender got the net proceeds (the
+		// 10% market fee came off the top), all short of the 62 debt.
+		assert_eq!(KittiesModule::kitty_owner(0), Some(3));
+		assert_eq!(Balances::free_balance(2), 9_950 + 54);
+		assert_eq!(Balances::free_balance(3), 10_000 - 60 - 100);
+		assert_eq!(KittiesModule::loans(0), None);
+	});
+}
+
+#[test]
+fn snapshots_freeze_holder_counts() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(2), 0));
+		assert_noop!(
+			KittiesModule::take_snapshot(Origin::signed(1)),
+			sp_runtime::DispatchError::BadOrigin
+		);
+
+		assert_ok!(KittiesModule::take_snapshot(RawOrigin::Root.into()));
+		assert_eq!(KittiesModule::snapshot_meta(0), Some((1, 2)));
+		assert_eq!(KittiesModule::snapshots(0, 1), 2);
+		assert_eq!(KittiesModule::snapshots(0, 2), 1);
+
+		// Later transfers do not touch the frozen counts.
+		assert_ok!(KittiesModule::transfer(Origin::signed(1), 2, 0));
+		assert_eq!(KittiesModule::snapshots(0, 1), 2);
+
+		// A second snapshot gets a fresh id and the new distribution.
+		assert_ok!(KittiesModule::take_snapshot(RawOrigin::Root.into()));
+		assert_eq!(KittiesModule::snapshots(1, 1), 1);
+		assert_eq!(KittiesModule::snapshots(1, 2), 2);
+		let mut holders = KittiesModule::snapshot_holders(1);
+		holders.sort();
+		assert_eq!(holders, vec![(1, 1), (2, 2)]);
+	});
+}
+
+#[test]
+fn holder_statistics_track_mints_transfers_and_removals() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_eq!(KittiesModule::unique_owners(), 0);
+
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(2), 0));
+		assert_eq!(KittiesModule::unique_owners(), 2);
+		assert_eq!(KittiesModule::holder_distribution(), [2, 0, 0, 0]);
+
+		// Account 1's second kitty moves it into the 2-5 bucket.
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_eq!(KittiesModule::holder_distribution(), [1, 1, 0, 0]);
+
+		// Transferring account 2's only kitty away drops it out entirely.
+		assert_ok!(KittiesModule::transfer(Origin::signed(2), 3, 1));
+		assert_eq!(KittiesModule::unique_owners(), 2);
+		assert_eq!(KittiesModule::holder_distribution(), [1, 1, 0, 0]);
+
+		// Fusing burns both of account 1's kitties and mints the fused one.
+		assert_ok!(KittiesModule::fuse(Origin::signed(1), 0, 2));
+		assert_eq!(KittiesModule::unique_owners(), 2);
+		assert_eq!(KittiesModule::holder_distribution(), [2, 0, 0, 0]);
+	});
+}
+
+#[test]
+fn bundles_sell_atomically_with_fees_and_splits() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_noop!(
+			KittiesModule::list_bundle(Origin::signed(1), vec![0, 0], 400, vec![]),
+			Error::<Test>::DuplicateBundleKitty
+		);
+
+		let splits = vec![(3, Percent::from_percent(10))];
+		assert_ok!(KittiesModule::list_bundle(Origin::signed(1), vec![0, 1], 400, splits));
+		// Bundled kitties are locked against individual market actions.
+		assert_noop!(
+			KittiesModule::transfer(Origin::signed(1), 2, 0),
+			Error::<Test>::KittyLocked
+		);
+		assert_noop!(
+			KittiesModule::sell(Origin::signed(1), 1, 100, vec![], None, false, None),
+			Error::<Test>::KittyLocked
+		);
+
+		let fee_pot = Balances::free_balance(999);
+		assert_ok!(KittiesModule::buy_bundle(Origin::signed(2), 0));
+		// Both kitties arrive together, deposits move with them.
+		assert_eq!(KittiesModule::kitty_owner(0), Some(2));
+		assert_eq!(KittiesModule::kitty_owner(1), Some(2));
+		assert_eq!(Balances::reserved_balance(2), 200);
+		// 10% market fee off the top, a 10% split of the net, rest to the
+		// seller: 400 - 40 - 36 = 324, plus the two freed deposits.
+		assert_eq!(Balances::free_balance(999), fee_pot + 40);
+		assert_eq!(Balances::free_balance(3), 10_000 + 36);
+		assert_eq!(Balances::free_balance(1), 10_000 - 200 + 200 + 324);
+		assert_eq!(KittiesModule::bundles(0), None);
+		assert_eq!(KittiesModule::bundle_of(0), None);
+
+		// Cancelling returns the lock.
+		assert_ok!(KittiesModule::list_bundle(Origin::signed(2), vec![0], 100, vec![]));
+		assert_ok!(KittiesModule::cancel_bundle(Origin::signed(2), 1));
+		assert_ok!(KittiesModule::transfer(Origin::signed(2), 3, 0));
+	});
+}
+
+#[test]
+fn swaps_settle_kitties_and_sweeteners_atomically() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(2), 0));
+
+		// "My kitty plus 50 for yours plus 20."
+		assert_ok!(KittiesModule::propose_swap(Origin::signed(1), 0, 1, 50, 20));
+		assert_eq!(Balances::reserved_balance(1), 150);
+		assert_noop!(
+			KittiesModule::propose_swap(Origin::signed(1), 0, 1, 0, 0),
+			Error::<Test>::SwapAlreadyProposed
+		);
+
+		assert_ok!(KittiesModule::accept_swap(Origin::signed(2), 0, 1));
+		assert_eq!(KittiesModule::kitty_owner(0), Some(2));
+		assert_eq!(KittiesModule::kitty_owner(1), Some(1));
+		// Sweeteners crossed; deposits stayed with their holders.
+		assert_eq!(Balances::free_balance(1), 9_870);
+		assert_eq!(Balances::free_balance(2), 9_930);
+		assert_eq!(Balances::reserved_balance(1), 100);
+		assert_eq!(KittiesModule::swap_proposal(0, 1), None);
+
+		// Cancelling releases the reserved sweetener.
+		assert_ok!(KittiesModule::propose_swap(Origin::signed(1), 1, 0, 30, 0));
+		assert_ok!(KittiesModule::cancel_swap(Origin::signed(1), 1, 0));
+		assert_eq!(Balances::reserved_balance(1), 100);
+
+		// A stale proposal dies once the offered kitty changes hands.
+		assert_ok!(KittiesModule::propose_swap(Origin::signed(1), 1, 0, 0, 0));
+		assert_ok!(KittiesModule::transfer(Origin::signed(1), 3, 1));
+		assert_noop!(
+			KittiesModule::accept_swap(Origin::signed(2), 1, 0),
+			Error::<Test>::SwapNotFound
+		);
+	});
+}
+
+#[test]
+fn collection_offers_fill_against_stored_attributes() {
+	new_test_ext().execute_with(|| {
+		use crate::OfferFilter;
+
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		let attributes = KittiesModule::attributes(0).unwrap();
+
+		// An any-gen-0 offer plus one pinned to a different fur colour.
+		let open = OfferFilter { max_generation: 0, fur: None, eyes: None, pattern: None };
+		let picky = OfferFilter { fur: Some(attributes.fur + 1), ..open };
+		assert_ok!(KittiesModule::make_collection_offer(Origin::signed(2), 200, picky));
+		assert_ok!(KittiesModule::make_collection_offer(Origin::signed(2), 200, open));
+		assert_eq!(Balances::reserved_balance(2), 400);
+
+		assert_noop!(
+			KittiesModule::fill_collection_offer(Origin::signed(1), 0, 0),
+			Error::<Test>::FilterMismatch
+		);
+
+		assert_ok!(KittiesModule::fill_collection_offer(Origin::signed(1), 1, 0));
+		assert_eq!(KittiesModule::kitty_owner(0), Some(2));
+		// Seller nets the price less the 10% fee, plus the freed deposit.
+		assert_eq!(Balances::free_balance(1), 10_000 - 100 + 100 + 180);
+		assert_eq!(KittiesModule::collection_offer(1), None);
+
+		// Bred kitties are generation 1 and miss a gen-0 filter.
+		assert_ok!(KittiesModule::create(Origin::signed(2), 0));
+		assert_ok!(KittiesModule::breed(Origin::signed(2), 0, 1));
+		assert_noop!(
+			KittiesModule::fill_collection_offer(Origin::signed(2), 0, 2),
+			Error::<Test>::OwnKittyMarketAction
+		);
+		assert_ok!(KittiesModule::cancel_collection_offer(Origin::signed(2), 0));
+		assert_eq!(Balances::reserved_balance(2), 300);
+	});
+}
+
+#[test]
+fn expired_offers_cannot_settle_and_are_swept() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+
+		assert_noop!(
+			KittiesModule::make_offer(Origin::signed(2), 0, 200, Some(1), None),
+			Error::<Test>::InvalidOfferExpiry
+		);
+		assert_ok!(KittiesModule::make_offer(Origin::signed(2), 0, 200, Some(2), None));
+		assert_eq!(Balances::reserved_balance(2), 200);
+
+		// Advance past the expiry without running the sweep: the offer is
+		// dead for acceptance even while it still sits in storage.
+		System::set_block_number(3);
+		assert_noop!(
+			KittiesModule::accept_offer(Origin::signed(1), 0, 2),
+			Error::<Test>::OfferExpired
+		);
+
+		// The sweep hands the lapsed reserve back.
+		run_to_block(4);
+		assert_eq!(KittiesModule::offers(0, 2), None);
+		assert_eq!(Balances::reserved_balance(2), 0);
+	});
+}
+
+#[test]
+fn active_market_state_trade_locks_the_kitty() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+
+		// A listing locks the kitty against transfer until delisted.
+		assert_ok!(KittiesModule::sell(Origin::signed(1), 0, 300, vec![], None, false, None));
+		assert_noop!(
+			KittiesModule::transfer(Origin::signed(1), 2, 0),
+			Error::<Test>::KittyLocked
+		);
+		assert_noop!(
+			KittiesModule::start_auction(Origin::signed(1), 0, 100, 5, None),
+			Error::<Test>::KittyLocked
+		);
+		assert_ok!(KittiesModule::cancel_listing(Origin::signed(1), 0));
+		assert_eq!(KittiesModule::kitty_lock(0), None);
+
+		// So does a running auction, released again on cancellation.
+		assert_ok!(KittiesModule::start_auction(Origin::signed(1), 0, 100, 5, None));
+		assert_noop!(
+			KittiesModule::transfer(Origin::signed(1), 2, 0),
+			Error::<Test>::KittyLocked
+		);
+		assert_ok!(KittiesModule::cancel_auction(Origin::signed(1), 0));
+		assert_eq!(KittiesModule::kitty_lock(0), None);
+
+		// A breeding delegation holds the lock until the last one is gone;
+		// further delegations may share it, other market state may not.
+		assert_ok!(KittiesModule::delegate_breeding(Origin::signed(1), 0, 2, 5, 10, Percent::zero()));
+		assert_ok!(KittiesModule::delegate_breeding(Origin::signed(1), 0, 3, 5, 10, Percent::zero()));
+		assert_noop!(
+			KittiesModule::sell(Origin::signed(1), 0, 300, vec![], None, false, None),
+			Error::<Test>::KittyLocked
+		);
+		assert_ok!(KittiesModule::revoke_breeding_delegation(Origin::signed(1), 0, 2));
+		assert!(KittiesModule::kitty_lock(0).is_some());
+		assert_ok!(KittiesModule::revoke_breeding_delegation(Origin::signed(1), 0, 3));
+		assert_eq!(KittiesModule::kitty_lock(0), None);
+		assert_ok!(KittiesModule::transfer(Origin::signed(1), 2, 0));
+	});
+}
+
+#[test]
+fn ownership_change_invalidates_stale_market_state() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::offer_transfer(Origin::signed(1), 0, 2, 10));
+
+		// A direct transfer kills the still-open two-phase offer on the
+		// spot instead of leaving it for the sweep.
+		assert_ok!(KittiesModule::transfer(Origin::signed(1), 3, 0));
+		assert_eq!(KittiesModule::pending_transfer(0), None);
+		assert_noop!(
+			KittiesModule::claim_transfer(Origin::signed(2), 0),
+			Error::<Test>::NoPendingTransfer
+		);
+	});
+}
+
+#[test]
+fn custodial_listings_hold_the_kitty_in_custody() {
+	new_test_ext().execute_with(|| {
+		set_custodial_listings(true);
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+
+		// Listing hands the kitty and its deposit over to the custody
+		// account, so the seller has nothing left to transfer away.
+		assert_ok!(KittiesModule::sell(Origin::signed(1), 0, 300, vec![], None, false, None));
+		assert_eq!(KittiesModule::kitty_owner(0), Some(KittiesModule::listing_custody_account()));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_noop!(
+			KittiesModule::transfer(Origin::signed(1), 2, 0),
+			Error::<Test>::NotKittyOwner
+		);
+		assert_noop!(
+			KittiesModule::buy_in_escrow(Origin::signed(2), 0),
+			Error::<Test>::ListingInCustody
+		);
+
+		// Cancellation reclaims the kitty and re-posts the deposit.
+		assert_ok!(KittiesModule::cancel_listing(Origin::signed(1), 0));
+		assert_eq!(KittiesModule::kitty_owner(0), Some(1));
+		assert_eq!(Balances::reserved_balance(1), 100);
+
+		// A sale settles from custody: the seller is paid net of the fee
+		// and the buyer posts a fresh deposit.
+		assert_ok!(KittiesModule::sell(Origin::signed(1), 0, 300, vec![], None, false, None));
+		let seller_before = Balances::free_balance(1);
+		assert_ok!(KittiesModule::buy(Origin::signed(2), 0));
+		assert_eq!(KittiesModule::kitty_owner(0), Some(2));
+		assert_eq!(Balances::free_balance(1), seller_before + 270);
+		assert_eq!(Balances::reserved_balance(2), 100);
+		set_custodial_listings(false);
+	});
+}
+
+#[test]
+fn asset_denominated_sales_settle_through_the_fungibles_interface() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		let shares = <TestFungibles as crate::Fungibles<u64>>::issue(&2, 1_000).unwrap();
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+
+		// A listing priced in an asset settles in that asset: the seller
+		// is paid net of the fee, the beneficiary collects the fee, and
+		// no native currency changes hands beyond the deposit shuffle.
+		assert_ok!(KittiesModule::sell(Origin::signed(1), 0, 300, vec![], Some(shares), false, None));
+		let seller_native = Balances::free_balance(1);
+		assert_ok!(KittiesModule::buy(Origin::signed(2), 0));
+		assert_eq!(KittiesModule::kitty_owner(0), Some(2));
+		assert_eq!(<TestFungibles as crate::Fungibles<u64>>::balance_of(&shares, &1), 270);
+		assert_eq!(<TestFungibles as crate::Fungibles<u64>>::balance_of(&shares, &999), 30);
+		assert_eq!(Balances::free_balance(1), seller_native + 100);
+
+		// An asset-denominated offer parks the funds with the payment
+		// pool, since assets cannot be reserved in place.
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::make_offer(Origin::signed(2), 1, 200, None, Some(shares)));
+		let pool = KittiesModule::payment_pool_account();
+		assert_eq!(<TestFungibles as crate::Fungibles<u64>>::balance_of(&shares, &pool), 200);
+		assert_ok!(KittiesModule::cancel_offer(Origin::signed(2), 1));
+		assert_eq!(<TestFungibles as crate::Fungibles<u64>>::balance_of(&shares, &pool), 0);
+		assert_eq!(<TestFungibles as crate::Fungibles<u64>>::balance_of(&shares, &2), 700);
+
+		// Escrow purchases need the native currency's reserve machinery.
+		assert_ok!(KittiesModule::sell(Origin::signed(1), 1, 200, vec![], Some(shares), false, None));
+		assert_noop!(
+			KittiesModule::buy_in_escrow(Origin::signed(2), 1),
+			Error::<Test>::EscrowRequiresNative
+		);
+	});
+}
+
+#[test]
+fn reference_priced_listings_convert_at_settlement() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+
+		// No listing without a quote, and none in an asset.
+		assert_noop!(
+			KittiesModule::sell(Origin::signed(1), 0, 50, vec![], None, true, None),
+			Error::<Test>::PriceUnavailable
+		);
+		set_reference_rate(Some(3));
+		assert_noop!(
+			KittiesModule::sell(Origin::signed(1), 0, 50, vec![], Some(0), true, None),
+			Error::<Test>::ReferencePricingRequiresNative
+		);
+		assert_ok!(KittiesModule::sell(Origin::signed(1), 0, 50, vec![], None, true, None));
+
+		// The buyer pays the conversion at the quote current when the sale
+		// settles, not the one at listing time.
+		set_reference_rate(Some(4));
+		let seller_before = Balances::free_balance(1);
+		assert_ok!(KittiesModule::buy(Origin::signed(2), 0));
+		// 50 reference units at 4 native each, minus the 10% fee, plus
+		// the returned deposit.
+		assert_eq!(Balances::free_balance(1), seller_before + 180 + 100);
+
+		// A feed outage freezes settlement until it recovers.
+		assert_ok!(KittiesModule::sell(Origin::signed(2), 0, 50, vec![], None, true, None));
+		set_reference_rate(None);
+		assert_noop!(
+			KittiesModule::buy(Origin::signed(1), 0),
+			Error::<Test>::PriceUnavailable
+		);
+		set_reference_rate(Some(1));
+		assert_ok!(KittiesModule::buy(Origin::signed(1), 0));
+		set_reference_rate(None);
+	});
+}
+
+#[test]
+fn installment_purchases_settle_over_their_schedule() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::sell(Origin::signed(1), 0, 400, vec![], None, false, None));
+
+		// 25% down, the rest in three installments of 100.
+		assert_noop!(
+			KittiesModule::buy_in_installments(Origin::signed(2), 0, 0),
+			Error::<Test>::InvalidInstallmentCount
+		);
+		assert_ok!(KittiesModule::buy_in_installments(Origin::signed(2), 0, 3));
+		assert_eq!(KittiesModule::listings(0), None);
+		assert_eq!(Balances::reserved_balance(2), 100);
+		// The kitty stays with the seller but cannot move.
+		assert_eq!(KittiesModule::kitty_owner(0), Some(1));
+		assert_noop!(
+			KittiesModule::transfer(Origin::signed(1), 3, 0),
+			Error::<Test>::KittyLocked
+		);
+		assert_noop!(
+			KittiesModule::pay_installment(Origin::signed(3), 0),
+			Error::<Test>::NotInstallmentBuyer
+		);
+
+		// Each payment grows the buyer's reserve; the final one settles
+		// the full price (net of the 10% fee) and delivers the kitty.
+		assert_ok!(KittiesModule::pay_installment(Origin::signed(2), 0));
+		assert_ok!(KittiesModule::pay_installment(Origin::signed(2), 0));
+		assert_eq!(Balances::reserved_balance(2), 300);
+		assert_ok!(KittiesModule::pay_installment(Origin::signed(2), 0));
+		assert_eq!(KittiesModule::installments(0), None);
+		assert_eq!(KittiesModule::kitty_owner(0), Some(2));
+		assert_eq!(Balances::free_balance(1), 10_360);
+		assert_eq!(Balances::free_balance(2), 9_500);
+		assert_eq!(Balances::reserved_balance(2), 100);
+	});
+}
+
+#[test]
+fn missed_installments_forfeit_the_down_payment() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::sell(Origin::signed(1), 0, 400, vec![], None, false, None));
+		assert_ok!(KittiesModule::buy_in_installments(Origin::signed(2), 0, 3));
+		assert_ok!(KittiesModule::pay_installment(Origin::signed(2), 0));
+		let seller_before = Balances::free_balance(1);
+		let buyer_before = Balances::free_balance(2);
+
+		// The next installment falls due at block 6 and goes unpaid: the
+		// down payment moves to the seller, the paid installment comes
+		// back and the kitty is free to list again.
+		run_to_block(6);
+		assert_eq!(KittiesModule::installments(0), None);
+		assert_eq!(KittiesModule::kitty_lock(0), None);
+		assert_eq!(KittiesModule::kitty_owner(0), Some(1));
+		assert_eq!(Balances::free_balance(1), seller_before + 100);
+		assert_eq!(Balances::free_balance(2), buyer_before + 100);
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_ok!(KittiesModule::sell(Origin::signed(1), 0, 400, vec![], None, false, None));
+	});
+}
+
+#[test]
+fn breeder_registration_requires_a_verified_identity() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_noop!(
+			KittiesModule::register_breeder(Origin::signed(1)),
+			Error::<Test>::NoVerifiedIdentity
+		);
+		set_identity_verified(1, true);
+		assert_ok!(KittiesModule::register_breeder(Origin::signed(1)));
+		assert_eq!(KittiesModule::breeder_id(1), Some(0));
+		assert_noop!(
+			KittiesModule::register_breeder(Origin::signed(1)),
+			Error::<Test>::AlreadyRegisteredBreeder
+		);
+
+		// Ids are never reused: a fresh registration gets the next one.
+		assert_ok!(KittiesModule::deregister_breeder(Origin::signed(1)));
+		assert_eq!(KittiesModule::breeder_id(1), None);
+		assert_ok!(KittiesModule::register_breeder(Origin::signed(1)));
+		assert_eq!(KittiesModule::breeder_id(1), Some(1));
+		assert_noop!(
+			KittiesModule::deregister_breeder(Origin::signed(2)),
+			Error::<Test>::NotRegisteredBreeder
+		);
+		set_identity_verified(1, false);
+	});
+}
+
+#[test]
+fn hibernating_kitties_neither_decay_nor_owe_rent() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		run_to_block(3);
+		assert_eq!(KittiesModule::current_energy(0), 98);
+		let rent_until = KittiesModule::rent_paid_until(0);
+		assert_ok!(KittiesModule::hibernate(Origin::signed(1), 0));
+
+		// Asleep: no breeding, battling or listing, and no early wake.
+		assert_noop!(
+			KittiesModule::breed(Origin::signed(1), 0, 1),
+			Error::<Test>::KittyHibernating
+		);
+		assert_noop!(
+			KittiesModule::sell(Origin::signed(1), 0, 300, vec![], None, false, None),
+			Error::<Test>::KittyHibernating
+		);
+		assert_noop!(
+			KittiesModule::start_auction(Origin::signed(1), 0, 100, 5, None),
+			Error::<Test>::KittyHibernating
+		);
+		assert_noop!(KittiesModule::wake(Origin::signed(1), 0), Error::<Test>::HibernationTooShort);
+
+		// Long past the rent and departure horizons, a sleeping kitty has
+		// neither decayed nor become reapable.
+		run_to_block(20);
+		assert_eq!(KittiesModule::current_energy(0), 98);
+		assert_noop!(
+			KittiesModule::reap_kitty(Origin::signed(2), 0),
+			Error::<Test>::KittyHibernating
+		);
+
+		// Waking pushes the rent clock out by the time slept and resumes
+		// decay from the wake block.
+		assert_ok!(KittiesModule::wake(Origin::signed(1), 0));
+		assert_eq!(KittiesModule::rent_paid_until(0), rent_until + 17);
+		run_to_block(22);
+		assert_eq!(KittiesModule::current_energy(0), 96);
+		assert_noop!(
+			KittiesModule::reap_kitty(Origin::signed(2), 0),
+			Error::<Test>::RentNotLapsed
+		);
+	});
+}
+
+#[test]
+fn aggregate_stats_follow_mints_burns_and_trades() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::breed(Origin::signed(1), 0, 1));
+
+		assert_ok!(KittiesModule::sell(Origin::signed(1), 2, 300, vec![], None, false, None));
+		assert_eq!(KittiesModule::active_listings(), 1);
+		assert_ok!(KittiesModule::buy(Origin::signed(2), 2));
+		assert_ok!(KittiesModule::fuse(Origin::signed(1), 0, 1));
+
+		// Two gen-0 parents fused into one gen-0 kitty, the bred gen-1
+		// kitten sold on; one native sale of 300 settled.
+		assert_eq!(KittiesModule::aggregate_stats(), crate::AggregateStats {
+			total_supply: 2,
+			generations: vec![(0, 1), (1, 1)],
+			total_burned: 2,
+			trade_volume: 300,
+			active_listings: 0,
+		});
+	});
+}
+
+#[test]
+fn governed_parameters_override_compiled_defaults() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_noop!(
+			KittiesModule::set_breed_fee(Origin::signed(1), Some(80)),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_ok!(KittiesModule::set_breed_fee(RawOrigin::Root.into(), Some(80)));
+		assert_eq!(KittiesModule::current_breed_fee(), 80);
+		assert_ok!(KittiesModule::set_market_fee(RawOrigin::Root.into(), Some(Percent::from_percent(20))));
+		assert_noop!(
+			KittiesModule::set_market_fee(RawOrigin::Root.into(), Some(Percent::from_percent(60))),
+			Error::<Test>::MarketFeeTooHigh
+		);
+
+		// A sale now pays the governed 20% commission, and the governed
+		// breed fee is what breeding actually charges.
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		let before = Balances::free_balance(1);
+		assert_ok!(KittiesModule::breed(Origin::signed(1), 0, 1));
+		assert_eq!(Balances::free_balance(1), before - 80 - 100);
+		assert_ok!(KittiesModule::sell(Origin::signed(1), 2, 300, vec![], None, false, None));
+		let seller_before = Balances::free_balance(1);
+		assert_ok!(KittiesModule::buy(Origin::signed(2), 2));
+		assert_eq!(Balances::free_balance(1), seller_before + 240 + 100);
+
+		// Clearing an override returns to the compiled default.
+		assert_ok!(KittiesModule::set_breed_fee(RawOrigin::Root.into(), None));
+		assert_eq!(KittiesModule::current_breed_fee(), 50);
+	});
+}
+
+#[test]
+fn exit_proofs_burn_the_kitty_and_fold_into_the_root() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_noop!(
+			KittiesModule::generate_exit_proof(Origin::signed(2), 0),
+			Error::<Test>::NotKittyOwner
+		);
+
+		assert_ok!(KittiesModule::generate_exit_proof(Origin::signed(1), 0));
+		// The kitty is gone, the deposit is back, and the commitment
+		// records the final owner.
+		assert_eq!(KittiesModule::kitty_owner(0), None);
+		assert_eq!(Balances::reserved_balance(1), 100);
+		let commitment = KittiesModule::exit_commitment(0).unwrap();
+		assert_eq!(commitment.owner, 1);
+		assert_eq!(KittiesModule::exit_count(), 1);
+		let first_root = KittiesModule::exits_root();
+		assert!(first_root != [0u8; 32]);
+
+		// Each further exit chains the root onward.
+		assert_ok!(KittiesModule::generate_exit_proof(Origin::signed(1), 1));
+		assert_eq!(KittiesModule::exit_count(), 2);
+		assert!(KittiesModule::exits_root() != first_root);
+	});
+}
+
+#[test]
+fn faucet_claims_are_free_and_once_per_era() {
+	new_test_ext().execute_with(|| {
+		// Closed by default, like a value-bearing chain.
+		assert_noop!(
+			KittiesModule::claim_faucet_kitty(Origin::signed(4)),
+			Error::<Test>::FaucetClosed
+		);
+
+		set_faucet_enabled(true);
+		// Account 4 holds no tokens at all; the faucet mints the deposit.
+		assert_eq!(Balances::free_balance(4), 0);
+		assert_ok!(KittiesModule::claim_faucet_kitty(Origin::signed(4)));
+		assert_eq!(KittiesModule::owned_kitties_count(4), 1);
+		assert_eq!(Balances::reserved_balance(4), 100);
+		assert_eq!(Balances::free_balance(4), 0);
+
+		// One per era.
+		assert_noop!(
+			KittiesModule::claim_faucet_kitty(Origin::signed(4)),
+			Error::<Test>::FaucetAlreadyClaimed
+		);
+
+		// The next era opens a fresh claim.
+		run_to_block(10);
+		assert_ok!(KittiesModule::claim_faucet_kitty(Origin::signed(4)));
+		assert_eq!(KittiesModule::owned_kitties_count(4), 2);
+		assert_eq!(Balances::reserved_balance(4), 200);
+
+		set_faucet_enabled(false);
+	});
+}
+
+#[test]
+fn kitty_struct_migration_rehearses_cleanly() {
+	new_test_ext().execute_with(|| {
+		use codec::Encode;
+		use frame_support::{storage::migration::put_storage_value, StorageValue};
+		use sp_io::hashing::blake2_256;
+
+		// Lay down two kitties in the legacy layout: bare dna arrays
+		// under the opaque hasher.
+		for id in 0u32..2 {
+			put_storage_value(b"Kitties", b"Kitties", &blake2_256(&id.encode()), [id as u8; 16]);
+		}
+		crate::KittiesCount::<Test>::put(2);
+
+		let state = crate::migration::KittyStructMigration::<Test>::pre_upgrade().unwrap();
+		crate::migration::KittyStructMigration::<Test>::on_runtime_upgrade();
+		assert_ok!(crate::migration::KittyStructMigration::<Test>::post_upgrade(state));
+
+		assert_eq!(KittiesModule::kitties(1), Some(crate::Kitty([1u8; 16])));
+	});
+}
+
+#[test]
+fn owned_index_migration_rebuilds_the_counters() {
+	new_test_ext().execute_with(|| {
+		use frame_support::{storage::migration::put_storage_value, StorageMap, StorageValue};
+
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(2), 0));
+
+		// Wind the state back to the pre-redesign layout: a tuple-keyed
+		// linked-list entry exists and the derived counters are unset.
+		put_storage_value(
+			b"Kitties",
+			b"OwnedKitties",
+			&[0u8; 16],
+			(Option::<u32>::None, Option::<u32>::None),
+		);
+		<crate::OwnedKittiesCount<Test>>::remove_all();
+		crate::UniqueOwners::kill();
+		crate::HolderDistribution::kill();
+
+		let state = crate::migration::OwnedIndexMigration::<Test>::pre_upgrade().unwrap();
+		crate::migration::OwnedIndexMigration::<Test>::on_runtime_upgrade();
+		assert_ok!(crate::migration::OwnedIndexMigration::<Test>::post_upgrade(state));
+
+		assert_eq!(KittiesModule::owned_kitties_count(1), 2);
+		assert_eq!(KittiesModule::owned_kitties_count(2), 1);
+		assert_eq!(KittiesModule::unique_owners(), 2);
+		assert_eq!(KittiesModule::holder_distribution(), [1, 1, 0, 0]);
+	});
+}
+
+#[test]
+fn first_creation_is_feeless_and_single_use() {
+	new_test_ext().execute_with(|| {
+		use frame_support::weights::{DispatchClass, GetDispatchInfo, Pays};
+
+		// The feeless path advertises `Pays::No`; the heavy admin calls
+		// ride the operational class.
+		assert_eq!(crate::Call::<Test>::create_first(0).get_dispatch_info().pays_fee, Pays::No);
+		assert_eq!(
+			crate::Call::<Test>::take_snapshot().get_dispatch_info().class,
+			DispatchClass::Operational
+		);
+
+		assert_ok!(KittiesModule::create_first(Origin::signed(1), 0));
+		assert_eq!(KittiesModule::owned_kitties_count(1), 1);
+		assert_noop!(
+			KittiesModule::create_first(Origin::signed(1), 0),
+			Error::<Test>::NotFirstCreation
+		);
+
+		// Any prior mint, even through the paying path, spends the free
+		// creation too.
+		assert_ok!(KittiesModule::create(Origin::signed(2), 0));
+		assert_noop!(
+			KittiesModule::create_first(Origin::signed(2), 0),
+			Error::<Test>::NotFirstCreation
+		);
+	});
+}
+
+#[test]
+fn htlc_claims_need_the_preimage_before_timeout() {
+	new_test_ext().execute_with(|| {
+		use sp_io::hashing::sha2_256;
+
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		let secret = b"shared secret".to_vec();
+		assert_ok!(KittiesModule::htlc_lock(Origin::signed(1), 0, sha2_256(&secret), 10, 2));
+		// The swap holds a trade lock, so the kitty cannot be moved out
+		// from under it.
+		assert_noop!(KittiesModule::transfer(Origin::signed(1), 2, 0), Error::<Test>::KittyLocked);
+
+		assert_noop!(
+			KittiesModule::htlc_claim(Origin::signed(2), 0, b"wrong".to_vec()),
+			Error::<Test>::HtlcPreimageMismatch
+		);
+		assert_noop!(
+			KittiesModule::htlc_claim(Origin::signed(3), 0, secret.clone()),
+			Error::<Test>::NotHtlcRecipient
+		);
+		assert_noop!(
+			KittiesModule::htlc_refund(Origin::signed(1), 0),
+			Error::<Test>::HtlcNotExpired
+		);
+
+		assert_ok!(KittiesModule::htlc_claim(Origin::signed(2), 0, secret));
+		assert_eq!(KittiesModule::kitty_owner(0), Some(2));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(2), 100);
+		assert!(KittiesModule::kitty_lock(0).is_none());
+	});
+}
+
+#[test]
+fn htlc_timeouts_hand_the_kitty_back() {
+	new_test_ext().execute_with(|| {
+		use sp_io::hashing::sha2_256;
+
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		let secret = b"shared secret".to_vec();
+		assert_ok!(KittiesModule::htlc_lock(Origin::signed(1), 0, sha2_256(&secret), 10, 2));
+
+		run_to_block(11);
+		assert_noop!(
+			KittiesModule::htlc_claim(Origin::signed(2), 0, secret),
+			Error::<Test>::HtlcExpired
+		);
+		assert_ok!(KittiesModule::htlc_refund(Origin::signed(1), 0));
+		assert_eq!(KittiesModule::kitty_owner(0), Some(1));
+		assert!(KittiesModule::kitty_lock(0).is_none());
+		assert_ok!(KittiesModule::transfer(Origin::signed(1), 2, 0));
+	});
+}
+
+#[test]
+fn timed_transfers_deliver_at_the_unlock_block() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::transfer_at(Origin::signed(1), 0, 2, 10));
+		assert_noop!(KittiesModule::transfer(Origin::signed(1), 2, 0), Error::<Test>::KittyLocked);
+		assert_noop!(
+			KittiesModule::claim_timed_transfer(Origin::signed(2), 0),
+			Error::<Test>::TimedTransferStillLocked
+		);
+
+		// Inside the cancel buffer (3 blocks before unlock) the delivery
+		// promise is firm.
+		run_to_block(8);
+		assert_noop!(
+			KittiesModule::cancel_timed_transfer(Origin::signed(1), 0),
+			Error::<Test>::TimedTransferCutoffPassed
+		);
+
+		run_to_block(10);
+		assert_eq!(KittiesModule::kitty_owner(0), Some(2));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(2), 100);
+		assert!(KittiesModule::kitty_lock(0).is_none());
+		assert!(KittiesModule::timed_transfer(0).is_none());
+	});
+}
+
+#[test]
+fn undeliverable_timed_transfers_stay_claimable() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		// Account 4 cannot post the deposit when the unlock block comes.
+		assert_ok!(KittiesModule::transfer_at(Origin::signed(1), 0, 4, 10));
+		run_to_block(10);
+		assert_eq!(KittiesModule::kitty_owner(0), Some(1));
+		assert!(KittiesModule::timed_transfer(0).is_some());
+
+		// Once funded, the recipient claims; the sender could equally
+		// cancel now that the scheduled moment has passed.
+		assert_ok!(Balances::transfer(Origin::signed(2), 4, 200));
+		assert_ok!(KittiesModule::claim_timed_transfer(Origin::signed(4), 0));
+		assert_eq!(KittiesModule::kitty_owner(0), Some(4));
+		assert!(KittiesModule::kitty_lock(0).is_none());
+	});
+}
+
+#[test]
+fn heirs_claim_after_inactivity_and_a_challenge_window() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::set_heir(Origin::signed(1), Some((2, 10))));
+
+		// The owner was active at block 1, so ten quiet blocks have to
+		// pass before the heir can even open a claim.
+		run_to_block(5);
+		assert_noop!(
+			KittiesModule::claim_inheritance(Origin::signed(2), 1),
+			Error::<Test>::OwnerStillActive
+		);
+		assert_noop!(
+			KittiesModule::claim_inheritance(Origin::signed(3), 1),
+			Error::<Test>::NotDesignatedHeir
+		);
+
+		run_to_block(11);
+		assert_ok!(KittiesModule::claim_inheritance(Origin::signed(2), 1));
+		assert_noop!(
+			KittiesModule::finalize_inheritance(Origin::signed(2), 1),
+			Error::<Test>::ChallengeWindowOpen
+		);
+
+		run_to_block(16);
+		assert_ok!(KittiesModule::finalize_inheritance(Origin::signed(2), 1));
+		assert_eq!(KittiesModule::kitty_owner(0), Some(2));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(2), 100);
+		assert!(KittiesModule::heir(1).is_none());
+	});
+}
+
+#[test]
+fn a_heartbeat_vetoes_an_open_inheritance_claim() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::set_heir(Origin::signed(1), Some((2, 10))));
+
+		run_to_block(11);
+		assert_ok!(KittiesModule::claim_inheritance(Origin::signed(2), 1));
+
+		// Any sign of life from the owner withdraws the claim and resets
+		// the inactivity clock.
+		run_to_block(12);
+		assert_ok!(KittiesModule::heartbeat(Origin::signed(1)));
+		assert!(KittiesModule::inheritance_claim(1).is_none());
+		assert_noop!(
+			KittiesModule::finalize_inheritance(Origin::signed(2), 1),
+			Error::<Test>::NoInheritanceClaim
+		);
+		assert_noop!(
+			KittiesModule::claim_inheritance(Origin::signed(2), 1),
+			Error::<Test>::OwnerStillActive
+		);
+	});
+}
+
+#[test]
+fn donations_route_to_approved_charities_at_settlement() {
+	new_test_ext().execute_with(|| {
+		use sp_runtime::Percent;
+
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		// Only governance-approved charities can be named.
+		assert_noop!(
+			KittiesModule::sell(
+				Origin::signed(1), 0, 300, vec![], None, false,
+				Some((7, Percent::from_percent(10))),
+			),
+			Error::<Test>::CharityNotApproved
+		);
+
+		assert_ok!(KittiesModule::set_charity(Origin::root(), 7, true));
+		assert_ok!(KittiesModule::sell(
+			Origin::signed(1), 0, 300, vec![], None, false,
+			Some((7, Percent::from_percent(10))),
+		));
+
+		// Net proceeds are 270 after the 10% market fee; the charity gets
+		// 10% of that and the seller the rest.
+		let seller_free = Balances::free_balance(1);
+		assert_ok!(KittiesModule::buy(Origin::signed(2), 0));
+		assert_eq!(Balances::free_balance(7), 27);
+		assert_eq!(Balances::free_balance(1), seller_free + 243);
+	});
+}
+
+#[test]
+fn verified_collections_flag_official_drops() {
+	new_test_ext().execute_with(|| {
+		use crate::VerifiedScope;
+
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert!(!KittiesModule::is_verified(0));
+
+		assert_noop!(
+			KittiesModule::verify_collection(
+				Origin::root(),
+				b"genesis drop".to_vec(),
+				VerifiedScope::Range(1, 0),
+			),
+			Error::<Test>::InvalidVerifiedRange
+		);
+		assert_ok!(KittiesModule::verify_collection(
+			Origin::root(),
+			b"genesis drop".to_vec(),
+			VerifiedScope::Range(0, 0),
+		));
+		assert!(KittiesModule::is_verified(0));
+		assert!(!KittiesModule::is_verified(1));
+
+		assert_ok!(KittiesModule::revoke_collection(Origin::root(), 0));
+		assert!(!KittiesModule::is_verified(0));
+		assert_noop!(
+			KittiesModule::revoke_collection(Origin::root(), 0),
+			Error::<Test>::VerifiedCollectionNotFound
+		);
+	});
+}
+
+#[test]
+fn index_repair_prunes_holes_and_rebuilds_counters() {
+	new_test_ext().execute_with(|| {
+		use frame_support::{StorageMap, StorageValue};
+
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(2), 0));
+
+		// Damage the state the way the old transfer logic did: a dangling
+		// owner entry at a hole index and a drifted holding count.
+		<crate::KittyOwners<Test>>::insert(99, 3);
+		<crate::OwnedKittiesCount<Test>>::insert(1, 7);
+		crate::UniqueOwners::put(9);
+
+		let state = crate::migration::IndexRepairMigration::<Test>::pre_upgrade().unwrap();
+		crate::migration::IndexRepairMigration::<Test>::run_once();
+		assert_ok!(crate::migration::IndexRepairMigration::<Test>::post_upgrade(state));
+
+		assert!(KittiesModule::kitty_owner(99).is_none());
+		assert_eq!(KittiesModule::owned_kitties_count(1), 2);
+		assert_eq!(KittiesModule::owned_kitties_count(2), 1);
+		assert_eq!(KittiesModule::unique_owners(), 2);
+		assert!(KittiesModule::index_repaired());
+
+		// The flag makes later upgrades a no-op.
+		<crate::OwnedKittiesCount<Test>>::insert(1, 7);
+		crate::migration::IndexRepairMigration::<Test>::run_once();
+		assert_eq!(KittiesModule::owned_kitties_count(1), 7);
+		<crate::OwnedKittiesCount<Test>>::insert(1, 2);
+	});
+}
+
+#[test]
+fn ownership_proof_keys_cover_the_owner_entries() {
+	new_test_ext().execute_with(|| {
+		use frame_support::storage::unhashed;
+
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		let keys = KittiesModule::ownership_proof_keys(0);
+		assert_eq!(keys.len(), 3);
+
+		// A read proof over these keys shows exactly what the chain
+		// holds: the kitty, its owner, and that it has not bridged out.
+		assert_eq!(unhashed::get::<crate::Kitty>(&keys[0]), KittiesModule::kitties(0));
+		assert_eq!(unhashed::get::<u64>(&keys[1]), Some(1));
+		assert!(unhashed::get::<(u64, [u8; 20])>(&keys[2]).is_none());
+
+		assert_ok!(KittiesModule::transfer(Origin::signed(1), 2, 0));
+		assert_eq!(unhashed::get::<u64>(&keys[1]), Some(2));
+	});
+}
+
+#[test]
+#[cfg(feature = "dev")]
+fn dev_helpers_clone_and_reset_cooldowns() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::dev_clone(Origin::signed(1), 0, 3));
+		assert_eq!(KittiesModule::owned_kitties_count(1), 4);
+		assert_eq!(Balances::reserved_balance(1), 400);
+
+		assert_ok!(KittiesModule::dev_skip_cooldowns(Origin::signed(1), 0));
+		assert!(KittiesModule::last_breed_at(0) == 0);
+	});
+}
+
+#[test]
+#[cfg(not(feature = "dev"))]
+fn dev_helpers_are_rejected_outside_dev_builds() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_noop!(KittiesModule::dev_clone(Origin::signed(1), 0, 3), Error::<Test>::DevOnly);
+		assert_noop!(
+			KittiesModule::dev_skip_cooldowns(Origin::signed(1), 0),
+			Error::<Test>::DevOnly
+		);
+	});
+}
+
+#[test]
+fn catteries_group_and_count_their_kitties() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create_cattery(Origin::signed(1), b"Moonpaw".to_vec()));
+		assert_ok!(KittiesModule::create_cattery(Origin::signed(2), b"Starfur".to_vec()));
+
+		// Only the founder may assign, and only their own kitties.
+		assert_noop!(
+			KittiesModule::assign_to_cattery(Origin::signed(1), 0, 1),
+			Error::<Test>::NotCatteryOwner
+		);
+		assert_noop!(
+			KittiesModule::assign_to_cattery(Origin::signed(2), 0, 1),
+			Error::<Test>::NotKittyOwner
+		);
+		assert_noop!(
+			KittiesModule::assign_to_cattery(Origin::signed(1), 0, 9),
+			Error::<Test>::CatteryNotFound
+		);
+
+		assert_ok!(KittiesModule::assign_to_cattery(Origin::signed(1), 0, 0));
+		assert_ok!(KittiesModule::assign_to_cattery(Origin::signed(1), 1, 0));
+		assert_eq!(KittiesModule::kitty_cattery(0), Some(0));
+		assert_eq!(KittiesModule::cattery(0).unwrap().members, 2);
+		assert_noop!(
+			KittiesModule::assign_to_cattery(Origin::signed(1), 0, 0),
+			Error::<Test>::AlreadyInCattery
+		);
+
+		assert_ok!(KittiesModule::remove_from_cattery(Origin::signed(1), 1));
+		assert_eq!(KittiesModule::kitty_cattery(1), None);
+		assert_eq!(KittiesModule::cattery(0).unwrap().members, 1);
+		assert_noop!(
+			KittiesModule::remove_from_cattery(Origin::signed(1), 1),
+			Error::<Test>::NotInCattery
+		);
+	});
+}
+
+#[test]
+fn cattery_membership_does_not_follow_a_sold_kitty() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create_cattery(Origin::signed(1), b"Moonpaw".to_vec()));
+		assert_ok!(KittiesModule::assign_to_cattery(Origin::signed(1), 0, 0));
+
+		assert_ok!(KittiesModule::transfer(Origin::signed(1), 2, 0));
+		assert_eq!(KittiesModule::kitty_cattery(0), None);
+		assert_eq!(KittiesModule::cattery(0).unwrap().members, 0);
+	});
+}
+
+#[test]
+fn cattery_managers_list_and_breeders_breed() {
+	use crate::CatteryRole;
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create_cattery(Origin::signed(1), b"Moonpaw".to_vec()));
+		assert_ok!(KittiesModule::assign_to_cattery(Origin::signed(1), 0, 0));
+		assert_ok!(KittiesModule::assign_to_cattery(Origin::signed(1), 1, 0));
+		assert_noop!(
+			KittiesModule::set_cattery_role(Origin::signed(2), 0, 3, Some(CatteryRole::Manager)),
+			Error::<Test>::NotCatteryOwner
+		);
+		assert_ok!(KittiesModule::set_cattery_role(Origin::signed(1), 0, 2, Some(CatteryRole::Manager)));
+		assert_ok!(KittiesModule::set_cattery_role(Origin::signed(1), 0, 3, Some(CatteryRole::Breeder)));
+
+		// The manager lists and delists; the founder stays the seller of
+		// record. The breeder holds no market powers.
+		assert_noop!(
+			KittiesModule::sell(Origin::signed(3), 0, 500, vec![], None, false, None),
+			Error::<Test>::NotKittyOwner
+		);
+		assert_ok!(KittiesModule::sell(Origin::signed(2), 0, 500, vec![], None, false, None));
+		assert_eq!(KittiesModule::listings(0).unwrap().seller, 1);
+		assert_ok!(KittiesModule::cancel_listing(Origin::signed(2), 0));
+		assert_eq!(KittiesModule::kitty_owner(0), Some(1));
+
+		// The breeder breeds; the child goes to the founder. The manager
+		// holds no breeding powers.
+		assert_noop!(
+			KittiesModule::breed(Origin::signed(2), 0, 1),
+			Error::<Test>::NoBreedingRights
+		);
+		assert_ok!(KittiesModule::breed(Origin::signed(3), 0, 1));
+		assert_eq!(KittiesModule::kitty_owner(2), Some(1));
+
+		// A viewer can do neither, and a cleared role grants nothing.
+		assert_ok!(KittiesModule::set_cattery_role(Origin::signed(1), 0, 2, Some(CatteryRole::Viewer)));
+		assert_noop!(
+			KittiesModule::sell(Origin::signed(2), 0, 500, vec![], None, false, None),
+			Error::<Test>::NotKittyOwner
+		);
+		assert_ok!(KittiesModule::set_cattery_role(Origin::signed(1), 0, 3, None));
+		assert_noop!(
+			KittiesModule::breed(Origin::signed(3), 0, 1),
+			Error::<Test>::NoBreedingRights
+		);
+	});
+}
+
+#[test]
+fn permissioned_minting_gates_the_create_family() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		set_permissioned_minting(true);
+
+		// Only the minter list creates; breeding and trading stay open.
+		assert_noop!(
+			KittiesModule::create(Origin::signed(2), 0),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_noop!(
+			KittiesModule::create_first(Origin::signed(2), 0),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_noop!(
+			KittiesModule::create_expedited(Origin::signed(2)),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_noop!(
+			KittiesModule::create_with_referral(Origin::signed(2), 1, 0),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_noop!(
+			KittiesModule::create_in_edition(Origin::signed(2), 0),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::breed(Origin::signed(1), 0, 1));
+		assert_ok!(KittiesModule::transfer(Origin::signed(1), 2, 2));
+
+		set_permissioned_minting(false);
+		assert_ok!(KittiesModule::create(Origin::signed(2), 0));
+	});
+}
+
+#[test]
+fn buyback_burns_pay_from_the_pool_while_it_lasts() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_noop!(
+			KittiesModule::redeem_burn(Origin::signed(1), 0),
+			Error::<Test>::BuybackClosed
+		);
+
+		assert_ok!(KittiesModule::set_buyback_schedule(Origin::root(), Some((100, 1))));
+		let quote = KittiesModule::buyback_quote(0).unwrap();
+		assert!(quote >= 100);
+		assert_noop!(
+			KittiesModule::redeem_burn(Origin::signed(1), 0),
+			Error::<Test>::BuybackPoolDry
+		);
+
+		assert_ok!(KittiesModule::fund_buyback(Origin::signed(2), 2_000));
+		assert_eq!(Balances::free_balance(KittiesModule::buyback_pool_account()), 2_000);
+		let before = Balances::free_balance(1);
+		assert_ok!(KittiesModule::redeem_burn(Origin::signed(1), 0));
+
+		// The payout arrives, the deposit comes back and the kitty is
+		// gone for good.
+		assert_eq!(Balances::free_balance(1), before + quote + 100);
+		assert_eq!(KittiesModule::kitty_owner(0), None);
+		assert_eq!(KittiesModule::burned_kitties(), 1);
+		assert_noop!(
+			KittiesModule::redeem_burn(Origin::signed(1), 0),
+			Error::<Test>::InvalidKittyId
+		);
+	});
+}
+
+#[test]
+fn fee_burn_splits_commissions_and_breed_fees() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		set_fee_burn_percent(Percent::from_percent(50));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::sell(Origin::signed(1), 0, 500, vec![], None, false, None));
+		let issuance = Balances::total_issuance();
+		assert_ok!(KittiesModule::buy(Origin::signed(2), 0));
+
+		// Half the 10% commission burns, the other half reaches the
+		// beneficiary, and the tally matches the issuance drop.
+		assert_eq!(Balances::free_balance(999), 25);
+		assert_eq!(KittiesModule::total_fees_burned(), 25);
+		assert_eq!(Balances::total_issuance(), issuance - 25);
+
+		// The breed fee takes the same split after sire shares; with
+		// both parents the breeder's own, that is the whole fee.
+		assert_ok!(KittiesModule::create(Origin::signed(2), 0));
+		assert_ok!(KittiesModule::breed(Origin::signed(2), 0, 1));
+		assert_eq!(Balances::free_balance(999), 25 + 25);
+		assert_eq!(KittiesModule::total_fees_burned(), 25 + 25);
+		set_fee_burn_percent(Percent::zero());
+	});
+}
+
+#[test]
+fn content_addressed_ids_derive_from_dna() {
+	use codec::Encode;
+	use frame_support::IterableStorageMap;
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		set_content_addressed_ids(true);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+
+		let (kitty_id, dna) = <crate::Kitties<Test>>::iter()
+			.map(|(id, kitty)| (id, kitty.0))
+			.next()
+			.unwrap();
+		let hash = (dna, 0u8).using_encoded(sp_io::hashing::blake2_128);
+		assert_eq!(kitty_id, u32::from_le_bytes([hash[0], hash[1], hash[2], hash[3]]));
+		assert_eq!(KittiesModule::kitty_owner(kitty_id), Some(1));
+		// The count stays a pure mint counter, not an id allocator.
+		assert_eq!(KittiesModule::kitties_count(), 1);
+		set_content_addressed_ids(false);
+	});
+}
+
+#[test]
+fn content_addressed_ids_bump_the_disambiguator_on_collision() {
+	use codec::Encode;
+	use frame_support::StorageMap;
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		set_content_addressed_ids(true);
+		let dna = [7u8; 16];
+		let first = (dna, 0u8).using_encoded(sp_io::hashing::blake2_128);
+		let first_id = u32::from_le_bytes([first[0], first[1], first[2], first[3]]);
+
+		// Occupy the kitty's first-choice id with a different kitty.
+		<crate::Kitties<Test>>::insert(first_id, crate::Kitty([9u8; 16]));
+		let second = (dna, 1u8).using_encoded(sp_io::hashing::blake2_128);
+		let second_id = u32::from_le_bytes([second[0], second[1], second[2], second[3]]);
+		assert_eq!(KittiesModule::kitty_id_for(&dna).unwrap(), second_id);
+		set_content_addressed_ids(false);
+	});
+}
+
+#[test]
+fn transfer_all_covers_content_addressed_ids() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		set_content_addressed_ids(true);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+
+		assert_ok!(KittiesModule::transfer_all(Origin::signed(1), 2));
+		assert_eq!(KittiesModule::owned_kitties_count(1), 0);
+		assert_eq!(KittiesModule::owned_kitties_count(2), 2);
+		set_content_addressed_ids(false);
+	});
+}
+
+#[test]
+fn escrowed_kitties_take_no_offers() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::make_offer(Origin::signed(3), 0, 200, None, None));
+		assert_ok!(KittiesModule::sell(Origin::signed(1), 0, 500, vec![], None, false, None));
+		assert_ok!(KittiesModule::buy_in_escrow(Origin::signed(2), 0));
+
+		// The escrowed sale is pending; the seller cannot double-settle
+		// through a standing offer, and no new offers can pile up.
+		assert_noop!(
+			KittiesModule::accept_offer(Origin::signed(1), 0, 3),
+			Error::<Test>::KittyInEscrow
+		);
+		assert_noop!(
+			KittiesModule::make_offer(Origin::signed(3), 0, 300, None, None),
+			Error::<Test>::KittyInEscrow
+		);
+	});
+}
@@ -30,7 +30,7 @@ use sp_version::NativeVersion;
 pub use sp_runtime::BuildStorage;
 pub use timestamp::Call as TimestampCall;
 pub use balances::Call as BalancesCall;
-pub use sp_runtime::{Permill, Perbill};
+pub use sp_runtime::{Permill, Perbill, Percent};
 pub use frame_support::{
 	construct_runtime, parameter_types, StorageValue,
 	traits::{KeyOwnerProofSystem, Randomness},
@@ -43,6 +43,9 @@ pub use frame_support::{
 /// Importing a template pallet
 pub use template;
 
+/// Importing the kitties pallet
+pub use kitties;
+
 /// An index to a block.
 pub type BlockNumber = u32;
 
@@ -257,6 +260,179 @@ impl template::Trait for Runtime {
 	type Event = Event;
 }
 
+parameter_types! {
+	/// Reserved from the owner for every kitty they hold.
+	pub const KittyDeposit: Balance = 1_000;
+	/// Charged for breeding two kitties.
+	pub const BreedFee: Balance = 500;
+	/// A kitty must rest this long between breedings.
+	pub const BreedCooldown: BlockNumber = 10 * MINUTES;
+	pub const MaxKittySupply: u32 = 50_000;
+	pub const GenZeroCap: u32 = 45_000;
+	pub const GenZeroTrancheSize: u32 = 500;
+	pub const GenZeroTranchePeriod: BlockNumber = 1 * DAYS;
+	pub const MaxKittiesPerAccount: u32 = 1_000;
+	/// Keep sequential ids; only flip on a fresh chain (see the kitties
+	/// pallet's `ContentAddressedIds` documentation for migration notes).
+	pub const ContentAddressedIds: bool = false;
+	pub const MaxAuctionSettlementsPerBlock: u32 = 20;
+	/// Commission taken on every kitty sale.
+	pub const MarketFeePercent: Percent = Percent::from_percent(2);
+	// With no beneficiary configured the whole fee burns anyway; the
+	// split starts mattering once a treasury account is wired in.
+	pub const FeeBurnPercent: Percent = Percent::from_percent(20);
+	/// No treasury yet: burn the market commission.
+	pub const MarketFeeBeneficiary: Option<AccountId> = None;
+	pub const MaxSaleSplits: u32 = 4;
+	pub const MaxProvenanceEntries: u32 = 32;
+	pub const MaxNameLength: u32 = 32;
+	/// Token fees are the sybil cost here; no mining needed to mint.
+	pub const PowMintEnabled: bool = false;
+	pub const PermissionedMinting: bool = false;
+	pub const FaucetEnabled: bool = false;
+	pub const FaucetEra: BlockNumber = 1 * DAYS;
+	/// An account may only create one free kitty per minute.
+	pub const CreateInterval: BlockNumber = 1 * MINUTES;
+	/// Burned to skip the free-creation interval.
+	pub const ExpeditedCreateFee: Balance = 500;
+	/// Mint-flood protection; see the kitties pallet's `MaxMintsPerBlock`.
+	pub const MaxMintsPerBlock: u32 = 50;
+	/// Fee credit a referrer earns per onboarded creator.
+	pub const ReferralCredit: Balance = 250;
+	pub const BreedingPassDiscount: Percent = Percent::from_percent(10);
+	pub const MaxEquippedItems: u32 = 4;
+	/// A freshly fed kitty holds this much energy.
+	pub const MaxEnergy: u32 = 10_000;
+	/// Roughly 16 hours from full to empty at one point per block.
+	pub const EnergyDecayPerBlock: u32 = 1;
+	pub const FeedCostPerEnergy: Balance = 10;
+	pub const BreedEnergyCost: u32 = 500;
+	/// Neglect is not fatal on this chain; flip for game-oriented deployments.
+	pub const PermaDeathEnabled: bool = false;
+	pub const DepartureGracePeriod: BlockNumber = 7 * DAYS;
+	pub const MinHibernationPeriod: BlockNumber = 1 * DAYS;
+	pub const TimedTransferCancelBuffer: BlockNumber = 6 * HOURS;
+	pub const InheritanceChallengeWindow: BlockNumber = 7 * DAYS;
+	/// How long an escrowed sale stays open to disputes before it settles.
+	pub const EscrowDisputeWindow: BlockNumber = 1 * DAYS;
+	pub const InstallmentDownPayment: Percent = Percent::from_percent(25);
+	pub const InstallmentInterval: BlockNumber = 7 * DAYS;
+	pub const MaxInstallments: u32 = 12;
+	pub const MaxLeaderboardSize: u32 = 100;
+	/// A newborn's look can be re-rolled for a day after birth.
+	pub const RerollWindow: BlockNumber = 1 * DAYS;
+	pub const RerollFee: Balance = 300;
+	pub const FusionFee: Balance = 1_000;
+	/// How many kitty ids the per-block market-state sweep examines.
+	pub const CleanupBudget: u32 = 50;
+	/// One rent payment keeps a kitty's state current for a month.
+	pub const RentPeriod: BlockNumber = 30 * DAYS;
+	pub const RentPerPeriod: Balance = 100;
+	/// Lapsed kitties stay safe from reaping for another two months.
+	pub const RentGracePeriod: BlockNumber = 60 * DAYS;
+	pub const ReapFinderFee: Balance = 100;
+	/// Explicit bounds for the queue-like storage vectors; this Substrate
+	/// version has no `BoundedVec`.
+	pub const MaxSameBlockEndings: u32 = 50;
+	pub const MaxContestEntrants: u32 = 64;
+	pub const MaxActiveContests: u32 = 32;
+	pub const MaxDifficultyRows: u32 = 16;
+	pub const RelayerThreshold: u32 = 3;
+	pub const HolderPerkMinKitties: u32 = 3;
+	pub const HolderPriorityBump: u64 = 100;
+	pub const HolderFeeDiscount: Percent = Percent::from_percent(20);
+	pub const LoanToValue: Percent = Percent::from_percent(50);
+	pub const LiquidationAuctionLength: BlockNumber = 1 * DAYS;
+	pub const MaxBundleSize: u32 = 10;
+	pub const CustodialListings: bool = false;
+}
+
+impl kitties::Trait for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type Randomness = RandomnessCollectiveFlip;
+	type KittyIndex = u32;
+	type ContentAddressedIds = ContentAddressedIds;
+	type KittyDeposit = KittyDeposit;
+	type BreedFee = BreedFee;
+	type BreedCooldown = BreedCooldown;
+	type MaxKittySupply = MaxKittySupply;
+	type GenZeroCap = GenZeroCap;
+	type GenZeroTrancheSize = GenZeroTrancheSize;
+	type GenZeroTranchePeriod = GenZeroTranchePeriod;
+	type MaxKittiesPerAccount = MaxKittiesPerAccount;
+	type MaxAuctionSettlementsPerBlock = MaxAuctionSettlementsPerBlock;
+	type MarketFeePercent = MarketFeePercent;
+	type MarketFeeBeneficiary = MarketFeeBeneficiary;
+	type FeeBurnPercent = FeeBurnPercent;
+	type MaxSaleSplits = MaxSaleSplits;
+	type MaxProvenanceEntries = MaxProvenanceEntries;
+	type MaxNameLength = MaxNameLength;
+	type PowMintEnabled = PowMintEnabled;
+	type PermissionedMinting = PermissionedMinting;
+	// Open issuance; compliance deployments flip the switch and point
+	// this at `EnsureSignedBy` over a breeders membership instance.
+	type MintOrigin = system::EnsureSigned<AccountId>;
+	type FaucetEnabled = FaucetEnabled;
+	type FaucetEra = FaucetEra;
+	type CreateInterval = CreateInterval;
+	type ExpeditedCreateFee = ExpeditedCreateFee;
+	type MaxMintsPerBlock = MaxMintsPerBlock;
+	type ReferralCredit = ReferralCredit;
+	type BreedingPassDiscount = BreedingPassDiscount;
+	type MaxEquippedItems = MaxEquippedItems;
+	type MaxEnergy = MaxEnergy;
+	type EnergyDecayPerBlock = EnergyDecayPerBlock;
+	type FeedCostPerEnergy = FeedCostPerEnergy;
+	type BreedEnergyCost = BreedEnergyCost;
+	type PermaDeathEnabled = PermaDeathEnabled;
+	type DepartureGracePeriod = DepartureGracePeriod;
+	type MinHibernationPeriod = MinHibernationPeriod;
+	type TimedTransferCancelBuffer = TimedTransferCancelBuffer;
+	type InheritanceChallengeWindow = InheritanceChallengeWindow;
+	type RerollWindow = RerollWindow;
+	type RerollFee = RerollFee;
+	type FusionFee = FusionFee;
+	type ForeignCreatureId = u32;
+	type ForeignCreature = kitties::NoForeignCreatures;
+	type Fungibles = kitties::NoFungibles;
+	type EscrowDisputeWindow = EscrowDisputeWindow;
+	type ArbiterOrigin = system::EnsureRoot<AccountId>;
+	type InstallmentDownPayment = InstallmentDownPayment;
+	type InstallmentInterval = InstallmentInterval;
+	type MaxInstallments = MaxInstallments;
+	type AdminOrigin = system::EnsureRoot<AccountId>;
+	type ForceOrigin = system::EnsureRoot<AccountId>;
+	type MaxLeaderboardSize = MaxLeaderboardSize;
+	type CleanupBudget = CleanupBudget;
+	type RentPeriod = RentPeriod;
+	type RentPerPeriod = RentPerPeriod;
+	type RentGracePeriod = RentGracePeriod;
+	type ReapFinderFee = ReapFinderFee;
+	type MaxSameBlockEndings = MaxSameBlockEndings;
+	type MaxContestEntrants = MaxContestEntrants;
+	type MaxActiveContests = MaxActiveContests;
+	type MaxDifficultyRows = MaxDifficultyRows;
+	type RelayerThreshold = RelayerThreshold;
+	type HolderPerkMinKitties = HolderPerkMinKitties;
+	type HolderPriorityBump = HolderPriorityBump;
+	type HolderFeeDiscount = HolderFeeDiscount;
+	type LoanToValue = LoanToValue;
+	type LiquidationAuctionLength = LiquidationAuctionLength;
+	type MaxBundleSize = MaxBundleSize;
+	type CustodialListings = CustodialListings;
+	type PriceOracle = kitties::NoPriceOracle;
+	type Identity = kitties::NoIdentity;
+}
+
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Runtime
+where
+	Call: From<C>,
+{
+	type OverarchingCall = Call;
+	type Extrinsic = UncheckedExtrinsic;
+}
+
 construct_runtime!(
 	pub enum Runtime where
 		Block = Block,
@@ -273,6 +449,7 @@ construct_runtime!(
 		Sudo: sudo::{Module, Call, Config<T>, Storage, Event<T>},
 		// Used for the module template in `./template.rs`
 		TemplateModule: template::{Module, Call, Storage, Event<T>},
+		Kitties: kitties::{Module, Call, Storage, Config<T>, Event<T>, ValidateUnsigned, Inherent},
 	}
 );
 
@@ -294,7 +471,8 @@ pub type SignedExtra = (
 	system::CheckEra<Runtime>,
 	system::CheckNonce<Runtime>,
 	system::CheckWeight<Runtime>,
-	transaction_payment::ChargeTransactionPayment<Runtime>
+	transaction_payment::ChargeTransactionPayment<Runtime>,
+	kitties::CheckKittyHolder<Runtime>
 );
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<Address, Call, Signature, SignedExtra>;
@@ -386,6 +564,52 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl kitties_runtime_api::KittiesApi<Block, u32, Balance, AccountId> for Runtime {
+		fn attributes(kitty_id: u32) -> Option<kitties::KittyAttributes> {
+			Kitties::attributes(kitty_id)
+		}
+
+		fn effective_stats(kitty_id: u32) -> Option<kitties::KittyStats> {
+			Kitties::effective_stats(kitty_id)
+		}
+
+		fn rarity_leaderboard() -> Vec<(u32, u32)> {
+			Kitties::rarity_leaderboard()
+		}
+
+		fn generation_page(generation: u32, start: u32, limit: u32) -> Vec<u32> {
+			Kitties::generation_page(generation, start, limit)
+		}
+
+		fn mint_price() -> Balance {
+			Kitties::mint_price()
+		}
+
+		fn snapshot(snapshot_id: u32) -> Vec<(AccountId, u32)> {
+			Kitties::snapshot_holders(snapshot_id)
+		}
+
+		fn holder_stats() -> (u32, [u32; 4]) {
+			(Kitties::unique_owners(), Kitties::holder_distribution())
+		}
+
+		fn stats() -> kitties::AggregateStats<Balance> {
+			Kitties::aggregate_stats()
+		}
+
+		fn exits_root() -> ([u8; 32], u32) {
+			(Kitties::exits_root(), Kitties::exit_count())
+		}
+
+		fn is_verified(kitty_id: u32) -> bool {
+			Kitties::is_verified(kitty_id)
+		}
+
+		fn ownership_proof_keys(kitty_id: u32) -> Vec<Vec<u8>> {
+			Kitties::ownership_proof_keys(kitty_id)
+		}
+	}
+
 	impl fg_primitives::GrandpaApi<Block> for Runtime {
 		fn grandpa_authorities() -> GrandpaAuthorityList {
 			Grandpa::grandpa_authorities()